@@ -0,0 +1,122 @@
+use crate::{
+    error::Error,
+    parameters::BasefoldParameters,
+    protocol::{BasefoldCommitmentData, BasefoldProof},
+    prover::BasefoldProver,
+    verifier::BasefoldVerifier,
+};
+use ark_ff::PrimeField;
+use merkle::hasher::MerkleHasher;
+use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+use sumcheck::{commitment::MultilinearCommitment, transcript::Transcript};
+
+/// Basefold needs no setup: the prover and the verifier share the same parameters.
+impl<F: PrimeField, H: MerkleHasher<F>> MultilinearCommitment<F> for BasefoldProver<F, H> {
+    type ProverParameters = BasefoldParameters<H>;
+    type VerifierParameters = BasefoldParameters<H>;
+    type Commitment = H::Digest;
+    type CommitmentData = BasefoldCommitmentData<F, H>;
+    type Proof = BasefoldProof<F, H::Digest>;
+    type Error = Error;
+
+    fn commit(
+        parameters: &BasefoldParameters<H>,
+        poly: &MultilinearPoly<F>,
+    ) -> Result<(H::Digest, BasefoldCommitmentData<F, H>), Error> {
+        Self::commit(parameters, poly)
+    }
+
+    fn open<T: Transcript<F>>(
+        parameters: &BasefoldParameters<H>,
+        poly: &MultilinearPoly<F>,
+        data: &BasefoldCommitmentData<F, H>,
+        point: &[F],
+        transcript: &mut T,
+    ) -> Result<(F, BasefoldProof<F, H::Digest>), Error> {
+        Self::open(parameters, poly, data, point, transcript)
+    }
+
+    fn verify<T: Transcript<F>>(
+        parameters: &BasefoldParameters<H>,
+        commitment: &H::Digest,
+        point: &[F],
+        value: F,
+        proof: &BasefoldProof<F, H::Digest>,
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        BasefoldVerifier::verify(parameters, commitment, point, value, proof, transcript)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use kzg::multilinear::{
+        prover::MultilinearKZGProver, trusted_setup::TrustedSetup, verifier_key::VerifierKey,
+    };
+    use merkle::hasher::Keccak256Hasher;
+    use sha3::Keccak256;
+    use sumcheck::fiat_shamir::FiatShamir;
+
+    // written once over the trait, the way a protocol picks its scheme per deployment
+    fn open_and_verify<C: MultilinearCommitment<Fr>>(
+        prover_parameters: &C::ProverParameters,
+        verifier_parameters: &C::VerifierParameters,
+        poly: &MultilinearPoly<Fr>,
+        point: &[Fr],
+    ) -> Result<Fr, C::Error> {
+        let (commitment, data) = C::commit(prover_parameters, poly)?;
+        let (value, proof) = C::open(
+            prover_parameters,
+            poly,
+            &data,
+            point,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )?;
+        C::verify(
+            verifier_parameters,
+            &commitment,
+            point,
+            value,
+            &proof,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )?;
+
+        Ok(value)
+    }
+
+    #[test]
+    fn test_basefold_and_kzg_behind_one_trait() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let poly = MultilinearPoly::new((0..16).map(|_| Fr::rand(&mut rng)).collect(), 4);
+        let point: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let expected = poly.evaluate(point.clone());
+
+        let parameters = BasefoldParameters::new(Keccak256Hasher, 4, 16).unwrap();
+        assert_eq!(
+            open_and_verify::<BasefoldProver<Fr, Keccak256Hasher>>(
+                &parameters,
+                &parameters,
+                &poly,
+                &point
+            ),
+            Ok(expected)
+        );
+
+        let taus: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let setup = TrustedSetup::<Bls12_381, Fr>::new(&taus);
+        let verifier_key = VerifierKey::from_setup(&setup);
+        assert_eq!(
+            open_and_verify::<MultilinearKZGProver<Fr, Bls12_381>>(
+                &setup,
+                &verifier_key,
+                &poly,
+                &point
+            ),
+            Ok(expected)
+        );
+    }
+}
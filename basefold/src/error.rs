@@ -0,0 +1,64 @@
+use std::fmt;
+
+/// Why a Basefold commitment or opening could not be produced or was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The polynomial does not have `2^n_vars` evaluations, or has no variable at all.
+    InvalidPoly { n_vars: usize, len: usize },
+    /// The point does not have one coordinate per variable.
+    InvalidPointLength { expected: usize, got: usize },
+    /// Some part of the proof does not have the shape the point requires.
+    InvalidProofLength { expected: usize, got: usize },
+    /// The round polynomial of `round` does not sum to the running claim over {0, 1}.
+    RoundSumMismatch { round: usize },
+    /// The sumcheck ends on a claim the final value disagrees with.
+    FinalEvalMismatch,
+    /// Query `query` does not fold down to the final value.
+    FinalValueMismatch { query: usize },
+    /// The committed codeword or one of its folds was rejected.
+    Fri(fri::error::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPoly { n_vars, len } => {
+                write!(
+                    f,
+                    "{len} evaluations can't be a polynomial in {n_vars} variables"
+                )
+            }
+            Self::InvalidPointLength { expected, got } => {
+                write!(f, "expected a point of {expected} coordinates, got {got}")
+            }
+            Self::InvalidProofLength { expected, got } => {
+                write!(f, "expected {expected} elements, got {got}")
+            }
+            Self::RoundSumMismatch { round } => {
+                write!(f, "round {round} polynomial does not match the claimed sum")
+            }
+            Self::FinalEvalMismatch => {
+                write!(f, "final claim does not match the final value")
+            }
+            Self::FinalValueMismatch { query } => {
+                write!(f, "query {query} does not fold into the final value")
+            }
+            Self::Fri(err) => write!(f, "proximity test rejected: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fri(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<fri::error::Error> for Error {
+    fn from(err: fri::error::Error) -> Self {
+        Self::Fri(err)
+    }
+}
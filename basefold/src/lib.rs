@@ -0,0 +1,7 @@
+pub mod commitment;
+pub mod error;
+pub mod parameters;
+pub mod protocol;
+pub mod prover;
+pub mod utils;
+pub mod verifier;
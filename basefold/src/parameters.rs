@@ -0,0 +1,22 @@
+use crate::error::Error;
+use fri::config::FriConfig;
+
+/// Parameters shared by the Basefold prover and verifier: the hasher of the Merkle
+/// trees and the FRI config of the proximity test.
+///
+/// Every sumcheck round binds one variable and folds the codeword once, so the config
+/// always folds by 2 down to a constant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasefoldParameters<H> {
+    pub hasher: H,
+    pub config: FriConfig,
+}
+
+impl<H> BasefoldParameters<H> {
+    pub fn new(hasher: H, blowup_factor: usize, num_queries: usize) -> Result<Self, Error> {
+        Ok(Self {
+            hasher,
+            config: FriConfig::new(blowup_factor, 2, num_queries, 1)?,
+        })
+    }
+}
@@ -0,0 +1,361 @@
+use ark_ff::PrimeField;
+use fri::protocol::FriLayerOpening;
+use merkle::{hasher::MerkleHasher, tree::MerkleTree};
+
+/// Opens every Basefold opening on the transcript.
+pub(crate) const DOMAIN_SEPARATOR: &[u8] = b"basefold";
+
+/// What the prover keeps from a commitment: the Reed-Solomon codeword and the Merkle
+/// tree over it, in FRI's leaf order, whose root is the commitment.
+#[derive(Clone, Debug)]
+pub struct BasefoldCommitmentData<F: PrimeField, H: MerkleHasher<F>> {
+    pub codeword: Vec<F>,
+    pub tree: MerkleTree<F, H>,
+}
+
+impl<F: PrimeField, H: MerkleHasher<F>> BasefoldCommitmentData<F, H> {
+    pub fn new(codeword: Vec<F>, tree: MerkleTree<F, H>) -> Self {
+        Self { codeword, tree }
+    }
+}
+
+/// Opening of a committed multilinear polynomial `f` at a point `z`.
+///
+/// `round_polys` are the sumcheck of `f(b) * eq(b, z)` over the hypercube, in
+/// evaluation form at `0, 1, 2`. Every round's challenge also folds the codeword, and
+/// `layer_roots` commit to every fold but the last one, which is the constant
+/// `final_value`. `layer_openings` open the commitment and every fold at the queries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasefoldProof<F: PrimeField, D> {
+    pub round_polys: Vec<Vec<F>>,
+    pub layer_roots: Vec<D>,
+    pub final_value: F,
+    pub layer_openings: Vec<FriLayerOpening<F, D>>,
+}
+
+impl<F: PrimeField, D> BasefoldProof<F, D> {
+    pub fn new(
+        round_polys: Vec<Vec<F>>,
+        layer_roots: Vec<D>,
+        final_value: F,
+        layer_openings: Vec<FriLayerOpening<F, D>>,
+    ) -> Self {
+        Self {
+            round_polys,
+            layer_roots,
+            final_value,
+            layer_openings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Error, parameters::BasefoldParameters, prover::BasefoldProver,
+        verifier::BasefoldVerifier,
+    };
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use merkle::hasher::{Keccak256Hasher, PoseidonHasher};
+    use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+    use sha3::Keccak256;
+    use sumcheck::{fiat_shamir::FiatShamir, transcript::PoseidonTranscript};
+
+    type Prover = BasefoldProver<Fr, Keccak256Hasher>;
+    type Verifier = BasefoldVerifier<Fr, Keccak256Hasher>;
+
+    fn keccak_transcript() -> FiatShamir<Keccak256, Fr> {
+        FiatShamir::new()
+    }
+
+    fn random_poly(n_vars: usize, rng: &mut StdRng) -> MultilinearPoly<Fr> {
+        MultilinearPoly::new((0..1 << n_vars).map(|_| Fr::rand(rng)).collect(), n_vars)
+    }
+
+    fn random_point(n_vars: usize, rng: &mut StdRng) -> Vec<Fr> {
+        (0..n_vars).map(|_| Fr::rand(rng)).collect()
+    }
+
+    #[test]
+    fn test_basefold_protocol() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for blowup in [2, 4, 8] {
+            let parameters = BasefoldParameters::new(Keccak256Hasher, blowup, 16).unwrap();
+
+            for n_vars in 1..=6 {
+                let poly = random_poly(n_vars, &mut rng);
+                let point = random_point(n_vars, &mut rng);
+
+                let (commitment, data) = Prover::commit(&parameters, &poly).unwrap();
+                let (value, proof) =
+                    Prover::open(&parameters, &poly, &data, &point, &mut keccak_transcript())
+                        .unwrap();
+
+                assert_eq!(value, poly.evaluate(point.clone()));
+                assert_eq!(
+                    Verifier::verify(
+                        &parameters,
+                        &commitment,
+                        &point,
+                        value,
+                        &proof,
+                        &mut keccak_transcript()
+                    ),
+                    Ok(()),
+                    "blowup {blowup}, {n_vars} variables"
+                );
+            }
+        }
+    }
+
+    // one commitment opened at several points, at a hypercube point too
+    #[test]
+    fn test_basefold_opens_one_commitment_many_times() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let parameters = BasefoldParameters::new(Keccak256Hasher, 4, 16).unwrap();
+        let poly = random_poly(4, &mut rng);
+        let (commitment, data) = Prover::commit(&parameters, &poly).unwrap();
+
+        let hypercube_point = [1, 0, 1, 1].map(Fr::from).to_vec();
+        for point in [random_point(4, &mut rng), hypercube_point] {
+            let (value, proof) =
+                Prover::open(&parameters, &poly, &data, &point, &mut keccak_transcript()).unwrap();
+
+            assert_eq!(value, poly.evaluate(point.clone()));
+            assert_eq!(
+                Verifier::verify(
+                    &parameters,
+                    &commitment,
+                    &point,
+                    value,
+                    &proof,
+                    &mut keccak_transcript()
+                ),
+                Ok(())
+            );
+        }
+        assert_eq!(
+            poly.evaluate([1, 0, 1, 1].map(Fr::from).to_vec()),
+            poly.evals[11]
+        );
+    }
+
+    #[test]
+    fn test_basefold_with_poseidon() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let parameters = BasefoldParameters::new(PoseidonHasher::new(), 4, 8).unwrap();
+        let poly = random_poly(3, &mut rng);
+        let point = random_point(3, &mut rng);
+
+        let (commitment, data) = BasefoldProver::commit(&parameters, &poly).unwrap();
+        let (value, proof) = BasefoldProver::open(
+            &parameters,
+            &poly,
+            &data,
+            &point,
+            &mut PoseidonTranscript::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            BasefoldVerifier::verify(
+                &parameters,
+                &commitment,
+                &point,
+                value,
+                &proof,
+                &mut PoseidonTranscript::new()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_basefold_rejects_wrong_claims() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let parameters = BasefoldParameters::new(Keccak256Hasher, 4, 16).unwrap();
+        let poly = random_poly(4, &mut rng);
+        let point = random_point(4, &mut rng);
+
+        let (commitment, data) = Prover::commit(&parameters, &poly).unwrap();
+        let (value, proof) =
+            Prover::open(&parameters, &poly, &data, &point, &mut keccak_transcript()).unwrap();
+
+        let verify = |commitment: &[u8; 32],
+                      point: &[Fr],
+                      value: Fr,
+                      proof: &BasefoldProof<Fr, [u8; 32]>| {
+            Verifier::verify(
+                &parameters,
+                commitment,
+                point,
+                value,
+                proof,
+                &mut keccak_transcript(),
+            )
+        };
+        assert_eq!(verify(&commitment, &point, value, &proof), Ok(()));
+
+        assert_eq!(
+            verify(&commitment, &point, value + Fr::from(1), &proof),
+            Err(Error::RoundSumMismatch { round: 0 })
+        );
+        assert!(verify(&commitment, &random_point(4, &mut rng), value, &proof).is_err());
+        assert_eq!(
+            verify(&commitment, &point[..3], value, &proof),
+            Err(Error::InvalidProofLength {
+                expected: 3,
+                got: 4
+            })
+        );
+
+        // the proof of another polynomial's value against this commitment
+        let other = random_poly(4, &mut rng);
+        let (other_commitment, other_data) = Prover::commit(&parameters, &other).unwrap();
+        let (other_value, other_proof) = Prover::open(
+            &parameters,
+            &other,
+            &other_data,
+            &point,
+            &mut keccak_transcript(),
+        )
+        .unwrap();
+        assert_eq!(
+            verify(&other_commitment, &point, other_value, &other_proof),
+            Ok(())
+        );
+        assert!(verify(&commitment, &point, other_value, &other_proof).is_err());
+    }
+
+    #[test]
+    fn test_basefold_rejects_tampered_proof() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let parameters = BasefoldParameters::new(Keccak256Hasher, 4, 16).unwrap();
+        let poly = random_poly(4, &mut rng);
+        let point = random_point(4, &mut rng);
+
+        let (commitment, data) = Prover::commit(&parameters, &poly).unwrap();
+        let (value, proof) =
+            Prover::open(&parameters, &poly, &data, &point, &mut keccak_transcript()).unwrap();
+
+        let verify = |proof: &BasefoldProof<Fr, [u8; 32]>| {
+            Verifier::verify(
+                &parameters,
+                &commitment,
+                &point,
+                value,
+                proof,
+                &mut keccak_transcript(),
+            )
+        };
+
+        // keeps the round sum but not the final claim
+        let mut tampered = proof.clone();
+        tampered.round_polys[3][0] += Fr::from(1);
+        tampered.round_polys[3][1] -= Fr::from(1);
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.final_value += Fr::from(1);
+        assert_eq!(verify(&tampered), Err(Error::FinalEvalMismatch));
+
+        let mut tampered = proof.clone();
+        tampered.layer_roots[1] = [0; 32];
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.layer_openings[2].values[0] += Fr::from(1);
+        assert!(matches!(
+            verify(&tampered),
+            Err(Error::Fri(fri::error::Error::Merkle { layer: 2, .. }))
+        ));
+
+        let mut tampered = proof.clone();
+        tampered.round_polys[2].push(Fr::from(0));
+        assert!(matches!(
+            verify(&tampered),
+            Err(Error::InvalidProofLength { .. })
+        ));
+    }
+
+    // a prover that commits to the codeword of one polynomial and runs the sumcheck on
+    // another is caught by the folds
+    #[test]
+    fn test_basefold_rejects_mismatched_codeword() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let parameters = BasefoldParameters::new(Keccak256Hasher, 4, 16).unwrap();
+        let poly = random_poly(4, &mut rng);
+        let other = random_poly(4, &mut rng);
+        let point = random_point(4, &mut rng);
+
+        let (commitment, data) = Prover::commit(&parameters, &poly).unwrap();
+        let (value, proof) =
+            Prover::open(&parameters, &other, &data, &point, &mut keccak_transcript()).unwrap();
+
+        assert_eq!(value, other.evaluate(point.clone()));
+        assert!(matches!(
+            Verifier::verify(
+                &parameters,
+                &commitment,
+                &point,
+                value,
+                &proof,
+                &mut keccak_transcript()
+            ),
+            Err(Error::FinalEvalMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_basefold_rejects_invalid_inputs() {
+        let parameters = BasefoldParameters::new(Keccak256Hasher, 4, 16).unwrap();
+        let constant = MultilinearPoly::new(vec![Fr::from(1)], 0);
+        let poly = MultilinearPoly::new(vec![Fr::from(1); 8], 3);
+
+        assert!(matches!(
+            Prover::commit(&parameters, &constant),
+            Err(Error::InvalidPoly { .. })
+        ));
+        assert!(BasefoldParameters::new(Keccak256Hasher, 3, 16).is_err());
+
+        let (_, data) = Prover::commit(&parameters, &poly).unwrap();
+        assert_eq!(
+            Prover::open(
+                &parameters,
+                &poly,
+                &data,
+                &[Fr::from(1)],
+                &mut keccak_transcript()
+            )
+            .map(|(value, _)| value),
+            Err(Error::InvalidPointLength {
+                expected: 3,
+                got: 1
+            })
+        );
+
+        // a point too long for the codeword size to fit in a usize
+        let (commitment, data) = Prover::commit(&parameters, &poly).unwrap();
+        let point = vec![Fr::from(2); 3];
+        let (value, proof) =
+            Prover::open(&parameters, &poly, &data, &point, &mut keccak_transcript()).unwrap();
+        assert_eq!(
+            Verifier::verify(
+                &parameters,
+                &commitment,
+                &[Fr::from(2); 64],
+                value,
+                &proof,
+                &mut keccak_transcript()
+            ),
+            Err(Error::InvalidPointLength {
+                expected: 61,
+                got: 64
+            })
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    error::Error,
+    parameters::BasefoldParameters,
+    protocol::{BasefoldCommitmentData, BasefoldProof, DOMAIN_SEPARATOR},
+    utils::{checked_n_vars, to_univariate},
+};
+use ark_ff::PrimeField;
+use fri::{
+    prover::FriProver,
+    utils::{codeword_domain, encode, fold_domain, fold_layer, query_positions, to_leaf_order},
+};
+use merkle::{hasher::MerkleHasher, tree::MerkleTree};
+use polynomials::multilinear::{multilinear_poly::MultilinearPoly, utils::generate_lagrange_basis};
+use std::marker::PhantomData;
+use sumcheck::{
+    sumcheck_protocol::{fold_top_variable, round_evaluations},
+    transcript::Transcript,
+};
+
+#[derive(Clone, Debug)]
+pub struct BasefoldProver<F: PrimeField, H: MerkleHasher<F>> {
+    _field: PhantomData<F>,
+    _hasher: PhantomData<H>,
+}
+
+impl<F: PrimeField, H: MerkleHasher<F>> BasefoldProver<F, H> {
+    /// Commits to `poly` with the Merkle root of the Reed-Solomon encoding of
+    /// [`to_univariate`].
+    pub fn commit(
+        parameters: &BasefoldParameters<H>,
+        poly: &MultilinearPoly<F>,
+    ) -> Result<(H::Digest, BasefoldCommitmentData<F, H>), Error> {
+        checked_n_vars(poly)?;
+
+        let codeword = encode(&to_univariate(poly), parameters.config.blowup_factor)?;
+        let tree = commit_layer(&parameters.hasher, &codeword, 0)?;
+
+        Ok((tree.root(), BasefoldCommitmentData::new(codeword, tree)))
+    }
+
+    /// Proves `poly(point)`, returned with the proof.
+    ///
+    /// `poly(point)` is the sum of `poly(b) * eq(b, point)` over the hypercube. The
+    /// sumcheck of that sum binds the variables to `r_0, ..., r_{n-1}` one at a time,
+    /// and every challenge also folds the codeword, which is committed again before
+    /// the next one is drawn. The last fold is the constant `poly(r)` the sumcheck ends
+    /// on, and FRI queries check every fold was honest.
+    pub fn open<T: Transcript<F>>(
+        parameters: &BasefoldParameters<H>,
+        poly: &MultilinearPoly<F>,
+        data: &BasefoldCommitmentData<F, H>,
+        point: &[F],
+        transcript: &mut T,
+    ) -> Result<(F, BasefoldProof<F, H::Digest>), Error> {
+        let n_vars = checked_n_vars(poly)?;
+        if point.len() != n_vars {
+            return Err(Error::InvalidPointLength {
+                expected: n_vars,
+                got: point.len(),
+            });
+        }
+
+        let value = poly.evaluate(point.to_vec());
+
+        transcript.domain_separator(DOMAIN_SEPARATOR);
+        transcript.append_point(b"commitment", &data.tree.root());
+        transcript.append_fields(b"point", point);
+        transcript.append_field(b"value", &value);
+
+        // a single product, poly(b) * eq(b, point), of degree 2
+        let mut tables = vec![vec![poly.evals.clone(), generate_lagrange_basis(point)]];
+        let mut round_polys = Vec::with_capacity(n_vars);
+
+        let mut domain = codeword_domain(data.codeword.len())?;
+        let mut layer = data.codeword.clone();
+        let mut trees = Vec::with_capacity(n_vars - 1);
+
+        for round in 0..n_vars {
+            let round_poly = round_evaluations(&tables, 2);
+            transcript.append_fields(b"round_poly", &round_poly);
+            round_polys.push(round_poly);
+
+            let challenge = transcript.challenge_scalar(b"challenge");
+            for table in tables.iter_mut().flatten() {
+                fold_top_variable(table, challenge);
+            }
+
+            layer = fold_layer(&layer, &domain, challenge, 2);
+            domain = fold_domain(&domain, 2);
+
+            // the last fold is a constant, sent as the final value
+            if round + 1 < n_vars {
+                let tree = commit_layer(&parameters.hasher, &layer, round + 1)?;
+                transcript.append_point(b"layer_root", &tree.root());
+                trees.push(tree);
+            }
+        }
+
+        let final_value = layer[0];
+        transcript.append_field(b"final_value", &final_value);
+
+        let positions = query_positions(
+            transcript,
+            parameters.config.num_queries,
+            data.codeword.len(),
+        );
+        let layers: Vec<&MerkleTree<F, H>> = std::iter::once(&data.tree).chain(&trees).collect();
+        let layer_openings = FriProver::open_layers(&layers, positions, 2)?;
+
+        Ok((
+            value,
+            BasefoldProof::new(
+                round_polys,
+                trees.iter().map(|tree| tree.root()).collect(),
+                final_value,
+                layer_openings,
+            ),
+        ))
+    }
+}
+
+fn commit_layer<F: PrimeField, H: MerkleHasher<F>>(
+    hasher: &H,
+    codeword: &[F],
+    layer: usize,
+) -> Result<MerkleTree<F, H>, Error> {
+    MerkleTree::new(hasher.clone(), &to_leaf_order(codeword, 2))
+        .map_err(|source| fri::error::Error::Merkle { layer, source }.into())
+}
@@ -0,0 +1,104 @@
+use crate::error::Error;
+use ark_ff::PrimeField;
+use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+
+/// Number of variables of `poly`, which has to have at least one and `2^n_vars`
+/// evaluations.
+pub fn checked_n_vars<F: PrimeField>(poly: &MultilinearPoly<F>) -> Result<usize, Error> {
+    if poly.n_vars == 0 || poly.evals.len() != 1 << poly.n_vars {
+        return Err(Error::InvalidPoly {
+            n_vars: poly.n_vars,
+            len: poly.evals.len(),
+        });
+    }
+
+    Ok(poly.n_vars)
+}
+
+/// The coefficients of `poly` in the monomial basis, laid out like its evaluations:
+/// bit `n - 1 - j` of an index says whether the monomial has variable `j`.
+pub fn monomial_coefficients<F: PrimeField>(poly: &MultilinearPoly<F>) -> Vec<F> {
+    let mut coeffs = poly.evals.clone();
+
+    for bit in 0..poly.n_vars {
+        let mask = 1 << bit;
+        for i in (0..coeffs.len()).filter(|i| i & mask != 0) {
+            coeffs[i] = coeffs[i] - coeffs[i ^ mask];
+        }
+    }
+
+    coeffs
+}
+
+/// The univariate polynomial Basefold encodes for `poly`: the monomial coefficients in
+/// bit-reversed order, so the coefficient of `X^i` is the monomial whose variable `j`
+/// is bit `j` of `i`.
+///
+/// Folding it as FRI does, `p'(Y) = p_even(Y) + r * p_odd(Y)`, binds the lowest bit
+/// and so variable 0 to `r`, the variable the sumcheck binds first. After `n_vars`
+/// folds with `r_0, ..., r_{n-1}` it is the constant `poly(r)`.
+pub fn to_univariate<F: PrimeField>(poly: &MultilinearPoly<F>) -> Vec<F> {
+    let coeffs = monomial_coefficients(poly);
+    let shift = usize::BITS - poly.n_vars as u32;
+
+    (0..coeffs.len())
+        .map(|i| coeffs[i.reverse_bits() >> shift])
+        .collect()
+}
+
+/// `eq(r, z) = prod_i (r_i * z_i + (1 - r_i) * (1 - z_i))`, which is one when `r = z`
+/// on the hypercube and zero elsewhere on it.
+pub fn eq_eval<F: PrimeField>(r: &[F], z: &[F]) -> F {
+    r.iter()
+        .zip(z)
+        .map(|(r_i, z_i)| *r_i * z_i + (F::one() - r_i) * (F::one() - z_i))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    // f(x0, x1) = a + (c - a) x0 + (b - a) x1 + (d - c - b + a) x0 x1
+    #[test]
+    fn test_to_univariate() {
+        let [a, b, c, d] = [3, 5, 11, 2].map(Fr::from);
+        let poly = MultilinearPoly::new(vec![a, b, c, d], 2);
+
+        assert_eq!(
+            monomial_coefficients(&poly),
+            vec![a, b - a, c - a, d - c - b + a]
+        );
+        assert_eq!(to_univariate(&poly), vec![a, c - a, b - a, d - c - b + a]);
+    }
+
+    #[test]
+    fn test_folding_the_univariate_evaluates_the_poly() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let poly = MultilinearPoly::new((0..32).map(|_| Fr::rand(&mut rng)).collect(), 5);
+        let r: Vec<Fr> = (0..5).map(|_| Fr::rand(&mut rng)).collect();
+
+        let mut coeffs = to_univariate(&poly);
+        for r_j in &r {
+            coeffs = coeffs
+                .chunks(2)
+                .map(|pair| pair[0] + *r_j * pair[1])
+                .collect();
+        }
+
+        assert_eq!(coeffs, vec![poly.evaluate(r)]);
+    }
+
+    #[test]
+    fn test_checked_n_vars() {
+        assert_eq!(
+            checked_n_vars(&MultilinearPoly::new(vec![Fr::from(1); 4], 2)),
+            Ok(2)
+        );
+        assert!(checked_n_vars(&MultilinearPoly::new(vec![Fr::from(1); 3], 2)).is_err());
+        assert!(checked_n_vars(&MultilinearPoly::new(vec![Fr::from(1)], 0)).is_err());
+    }
+}
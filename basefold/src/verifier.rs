@@ -0,0 +1,119 @@
+use crate::{
+    error::Error,
+    parameters::BasefoldParameters,
+    protocol::{BasefoldProof, DOMAIN_SEPARATOR},
+    utils::eq_eval,
+};
+use ark_ff::PrimeField;
+use fri::{
+    utils::{codeword_domain, query_positions},
+    verifier::FriVerifier,
+};
+use merkle::hasher::MerkleHasher;
+use std::marker::PhantomData;
+use sumcheck::{sumcheck_protocol::evaluate_round_poly, transcript::Transcript};
+
+#[derive(Clone, Debug)]
+pub struct BasefoldVerifier<F: PrimeField, H: MerkleHasher<F>> {
+    _field: PhantomData<F>,
+    _hasher: PhantomData<H>,
+}
+
+impl<F: PrimeField, H: MerkleHasher<F>> BasefoldVerifier<F, H> {
+    /// Checks the polynomial behind `commitment` takes `value` at `point`.
+    ///
+    /// The sumcheck reduces `value` to `poly(r) * eq(r, point)` and the final value
+    /// stands for `poly(r)`. The queries then check the committed codeword folds down
+    /// to that final value with the sumcheck's challenges, which holds only if it is
+    /// close to the encoding of a polynomial that really takes it at `r`.
+    pub fn verify<T: Transcript<F>>(
+        parameters: &BasefoldParameters<H>,
+        commitment: &H::Digest,
+        point: &[F],
+        value: F,
+        proof: &BasefoldProof<F, H::Digest>,
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        let n_vars = point.len();
+        if n_vars == 0 {
+            return Err(Error::InvalidPointLength {
+                expected: 1,
+                got: 0,
+            });
+        }
+        // the point comes from the caller, so its length must not overflow the codeword
+        let len = u32::try_from(n_vars)
+            .ok()
+            .and_then(|n_vars| 1usize.checked_shl(n_vars))
+            .and_then(|size| size.checked_mul(parameters.config.blowup_factor))
+            .ok_or(Error::InvalidPointLength {
+                expected: (usize::MAX / parameters.config.blowup_factor).ilog2() as usize,
+                got: n_vars,
+            })?;
+        check_len(n_vars, proof.round_polys.len())?;
+        check_len(n_vars - 1, proof.layer_roots.len())?;
+        check_len(n_vars, proof.layer_openings.len())?;
+
+        transcript.domain_separator(DOMAIN_SEPARATOR);
+        transcript.append_point(b"commitment", commitment);
+        transcript.append_fields(b"point", point);
+        transcript.append_field(b"value", &value);
+
+        let mut claimed_sum = value;
+        let mut challenges = Vec::with_capacity(n_vars);
+
+        for (round, round_poly) in proof.round_polys.iter().enumerate() {
+            check_len(3, round_poly.len())?;
+            if round_poly[0] + round_poly[1] != claimed_sum {
+                return Err(Error::RoundSumMismatch { round });
+            }
+
+            transcript.append_fields(b"round_poly", round_poly);
+
+            let challenge = transcript.challenge_scalar(b"challenge");
+            claimed_sum = evaluate_round_poly(round_poly, challenge);
+            challenges.push(challenge);
+
+            if let Some(root) = proof.layer_roots.get(round) {
+                transcript.append_point(b"layer_root", root);
+            }
+        }
+
+        transcript.append_field(b"final_value", &proof.final_value);
+
+        if claimed_sum != proof.final_value * eq_eval(&challenges, point) {
+            return Err(Error::FinalEvalMismatch);
+        }
+
+        let positions = query_positions(transcript, parameters.config.num_queries, len);
+        let layer_roots: Vec<H::Digest> = std::iter::once(commitment.clone())
+            .chain(proof.layer_roots.iter().cloned())
+            .collect();
+
+        let folded = FriVerifier::verify_layers(
+            &parameters.hasher,
+            2,
+            codeword_domain(len)?,
+            &layer_roots,
+            &proof.layer_openings,
+            &challenges,
+            positions,
+        )?;
+
+        for (query, (_, value)) in folded.into_iter().enumerate() {
+            if value != proof.final_value {
+                return Err(Error::FinalValueMismatch { query });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_len(expected: usize, got: usize) -> Result<(), Error> {
+    if expected != got {
+        return Err(Error::InvalidProofLength { expected, got });
+    }
+
+    Ok(())
+}
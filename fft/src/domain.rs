@@ -0,0 +1,280 @@
+use crate::utils::{distribute_powers, radix2_ntt_in_place};
+use ark_ff::FftField;
+
+/// A multiplicative subgroup `H = {offset * w^i}` of size `2^k` (or a coset of it)
+/// used as the evaluation domain for the number theoretic transform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Radix2Domain<F: FftField> {
+    pub size: usize,
+    pub log_size: u32,
+    pub generator: F,
+    pub generator_inv: F,
+    pub size_inv: F,
+    pub offset: F,
+    pub offset_inv: F,
+}
+
+impl<F: FftField> Radix2Domain<F> {
+    /// Builds the smallest radix-2 domain that can hold `num_coeffs` coefficients.
+    /// Returns `None` if the field does not have a large enough 2-adic subgroup.
+    pub fn new(num_coeffs: usize) -> Option<Self> {
+        let size = num_coeffs.max(1).checked_next_power_of_two()?;
+        let log_size = size.ilog2();
+
+        if log_size > F::TWO_ADICITY {
+            return None;
+        }
+
+        let generator = F::get_root_of_unity(size as u64)?;
+
+        Some(Radix2Domain {
+            size,
+            log_size,
+            generator,
+            generator_inv: generator.inverse()?,
+            size_inv: F::from(size as u64).inverse()?,
+            offset: F::one(),
+            offset_inv: F::one(),
+        })
+    }
+
+    /// Builds a domain of the same size shifted by `offset`, i.e `offset * H`.
+    pub fn get_coset(&self, offset: F) -> Option<Self> {
+        Some(Radix2Domain {
+            offset,
+            offset_inv: offset.inverse()?,
+            ..*self
+        })
+    }
+
+    /// Shorthand for `new(num_coeffs)` followed by a shift by the multiplicative
+    /// generator of the field, which never lies in `H`.
+    pub fn new_coset(num_coeffs: usize) -> Option<Self> {
+        Self::new(num_coeffs)?.get_coset(F::GENERATOR)
+    }
+
+    pub fn is_coset(&self) -> bool {
+        !self.offset.is_one()
+    }
+
+    /// Returns the `i`-th element `offset * w^i` of the domain.
+    pub fn element(&self, i: usize) -> F {
+        self.offset * self.generator.pow([i as u64])
+    }
+
+    /// Returns every element of the domain in order.
+    pub fn elements(&self) -> Vec<F> {
+        let mut elements = Vec::with_capacity(self.size);
+        let mut current = self.offset;
+
+        for _ in 0..self.size {
+            elements.push(current);
+            current *= self.generator;
+        }
+
+        elements
+    }
+
+    /// Evaluates `Z_H(X) = X^n - offset^n` at `x`.
+    pub fn evaluate_vanishing_polynomial(&self, x: F) -> F {
+        x.pow([self.size as u64]) - self.offset.pow([self.size as u64])
+    }
+
+    /// Evaluates the coefficients on the domain, padding with zeros up to its size.
+    pub fn fft(&self, coeffs: &[F]) -> Vec<F> {
+        let mut values = coeffs.to_vec();
+        self.fft_in_place(&mut values);
+
+        values
+    }
+
+    pub fn fft_in_place(&self, coeffs: &mut Vec<F>) {
+        if self.is_coset() {
+            self.coset_fft_in_place(coeffs);
+        } else {
+            self.resize(coeffs);
+            radix2_ntt_in_place(coeffs, self.generator);
+        }
+    }
+
+    /// Interpolates the evaluations over the domain back to coefficient form.
+    pub fn ifft(&self, evals: &[F]) -> Vec<F> {
+        let mut values = evals.to_vec();
+        self.ifft_in_place(&mut values);
+
+        values
+    }
+
+    pub fn ifft_in_place(&self, evals: &mut Vec<F>) {
+        if self.is_coset() {
+            self.coset_ifft_in_place(evals);
+        } else {
+            self.resize(evals);
+            radix2_ntt_in_place(evals, self.generator_inv);
+            evals.iter_mut().for_each(|eval| *eval *= self.size_inv);
+        }
+    }
+
+    /// Evaluates the coefficients on `offset * H`.
+    pub fn coset_fft(&self, coeffs: &[F]) -> Vec<F> {
+        let mut values = coeffs.to_vec();
+        self.coset_fft_in_place(&mut values);
+
+        values
+    }
+
+    pub fn coset_fft_in_place(&self, coeffs: &mut Vec<F>) {
+        self.resize(coeffs);
+        distribute_powers(coeffs, self.offset);
+        radix2_ntt_in_place(coeffs, self.generator);
+    }
+
+    /// Interpolates evaluations over `offset * H` back to coefficient form.
+    pub fn coset_ifft(&self, evals: &[F]) -> Vec<F> {
+        let mut values = evals.to_vec();
+        self.coset_ifft_in_place(&mut values);
+
+        values
+    }
+
+    pub fn coset_ifft_in_place(&self, evals: &mut Vec<F>) {
+        self.resize(evals);
+        radix2_ntt_in_place(evals, self.generator_inv);
+        evals.iter_mut().for_each(|eval| *eval *= self.size_inv);
+        distribute_powers(evals, self.offset_inv);
+    }
+
+    fn resize(&self, values: &mut Vec<F>) {
+        assert!(
+            values.len() <= self.size,
+            "Got {} values for a domain of size {}",
+            values.len(),
+            self.size
+        );
+
+        values.resize(self.size, F::zero());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::{Field, One, UniformRand, Zero};
+    use ark_std::rand::{SeedableRng, rngs::StdRng};
+    use polynomials::univariate::univariate_poly::UnivariatePoly;
+
+    fn random_coeffs(n: usize, rng: &mut StdRng) -> Vec<Fr> {
+        (0..n).map(|_| Fr::rand(rng)).collect()
+    }
+
+    #[test]
+    fn test_domain_size() {
+        let domain = Radix2Domain::<Fr>::new(5).unwrap();
+
+        assert_eq!(domain.size, 8);
+        assert_eq!(domain.log_size, 3);
+        assert_eq!(domain.generator.pow([8]), Fr::one());
+        assert_ne!(domain.generator.pow([4]), Fr::one());
+        assert!(Radix2Domain::<Fr>::new(1 << 29).is_none());
+    }
+
+    #[test]
+    fn test_fft_matches_naive_evaluation() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let coeffs = random_coeffs(13, &mut rng);
+        let domain = Radix2Domain::<Fr>::new(coeffs.len()).unwrap();
+        let poly = UnivariatePoly::new(coeffs.clone());
+
+        let evals = domain.fft(&coeffs);
+        let expected: Vec<Fr> = domain
+            .elements()
+            .into_iter()
+            .map(|x| poly.evaluate(x))
+            .collect();
+
+        assert_eq!(evals, expected);
+    }
+
+    #[test]
+    fn test_fft_ifft_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for log_n in 0..8 {
+            let coeffs = random_coeffs(1 << log_n, &mut rng);
+            let domain = Radix2Domain::<Fr>::new(coeffs.len()).unwrap();
+
+            let mut values = coeffs.clone();
+            domain.fft_in_place(&mut values);
+            domain.ifft_in_place(&mut values);
+
+            assert_eq!(values, coeffs);
+        }
+    }
+
+    #[test]
+    fn test_coset_fft_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let coeffs = random_coeffs(16, &mut rng);
+        let domain = Radix2Domain::<Fr>::new_coset(coeffs.len()).unwrap();
+        let poly = UnivariatePoly::new(coeffs.clone());
+
+        let evals = domain.coset_fft(&coeffs);
+        let expected: Vec<Fr> = domain
+            .elements()
+            .into_iter()
+            .map(|x| poly.evaluate(x))
+            .collect();
+
+        assert_eq!(evals, expected);
+        assert_eq!(domain.coset_ifft(&evals), coeffs);
+        assert_eq!(domain.ifft(&domain.fft(&coeffs)), coeffs);
+    }
+
+    #[test]
+    fn test_fft_mul_matches_naive_mul() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let a = random_coeffs(17, &mut rng);
+        let b = random_coeffs(9, &mut rng);
+        let result_len = a.len() + b.len() - 1;
+        let domain = Radix2Domain::<Fr>::new(result_len).unwrap();
+
+        let a_evals = domain.fft(&a);
+        let b_evals = domain.fft(&b);
+        let product: Vec<Fr> = a_evals
+            .iter()
+            .zip(b_evals.iter())
+            .map(|(x, y)| *x * y)
+            .collect();
+
+        let mut coeffs = domain.ifft(&product);
+        coeffs.truncate(result_len);
+
+        let expected = UnivariatePoly::new(a) * UnivariatePoly::new(b);
+
+        assert_eq!(coeffs, expected.coefficients);
+    }
+
+    #[test]
+    fn test_ifft_matches_naive_interpolation() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let domain = Radix2Domain::<Fr>::new(8).unwrap();
+        let evals = random_coeffs(domain.size, &mut rng);
+
+        let coeffs = domain.ifft(&evals);
+        let expected = UnivariatePoly::interpolate(domain.elements(), evals);
+
+        assert_eq!(coeffs, expected.coefficients);
+    }
+
+    #[test]
+    fn test_vanishing_polynomial() {
+        let domain = Radix2Domain::<Fr>::new(8).unwrap();
+        let coset = Radix2Domain::<Fr>::new_coset(8).unwrap();
+
+        for x in domain.elements() {
+            assert!(domain.evaluate_vanishing_polynomial(x).is_zero());
+            assert!(!coset.evaluate_vanishing_polynomial(x).is_zero());
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use ark_ff::FftField;
+
+/// Reverses the lowest `log_n` bits of `index`.
+pub fn bit_reverse(index: usize, log_n: u32) -> usize {
+    if log_n == 0 {
+        return 0;
+    }
+
+    index.reverse_bits() >> (usize::BITS - log_n)
+}
+
+/// Reorders `values` so that the element at index `i` moves to `bit_reverse(i)`.
+pub fn bit_reverse_permutation<T>(values: &mut [T]) {
+    let n = values.len();
+    assert!(n.is_power_of_two(), "Length must be a power of 2");
+    let log_n = n.ilog2();
+
+    for i in 0..n {
+        let j = bit_reverse(i, log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+/// Iterative radix-2 Cooley-Tukey transform of `values` using `omega` as the
+/// primitive root of unity of order `values.len()`.
+///
+/// Input is in natural order and the output is in natural order, the bit-reversal
+/// is done up front so the butterflies can run in place.
+pub fn radix2_ntt_in_place<F: FftField>(values: &mut [F], omega: F) {
+    let n = values.len();
+    assert!(n.is_power_of_two(), "Length must be a power of 2");
+
+    if n == 1 {
+        return;
+    }
+
+    bit_reverse_permutation(values);
+
+    // twiddles for the last stage, earlier stages use every (n / 2m)-th one
+    let half_n = n / 2;
+    let mut twiddles = Vec::with_capacity(half_n);
+    let mut w = F::one();
+    for _ in 0..half_n {
+        twiddles.push(w);
+        w *= omega;
+    }
+
+    let mut m = 1;
+    while m < n {
+        let stride = half_n / m;
+
+        for chunk in values.chunks_mut(2 * m) {
+            let (lo, hi) = chunk.split_at_mut(m);
+
+            for (j, (a, b)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let t = *b * twiddles[j * stride];
+                *b = *a - t;
+                *a += t;
+            }
+        }
+
+        m *= 2;
+    }
+}
+
+/// Multiplies the `i`-th element of `values` by `g^i`.
+pub fn distribute_powers<F: FftField>(values: &mut [F], g: F) {
+    let mut pow = F::one();
+    for value in values.iter_mut() {
+        *value *= pow;
+        pow *= g;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_bit_reverse() {
+        assert_eq!(bit_reverse(0b001, 3), 0b100);
+        assert_eq!(bit_reverse(0b110, 3), 0b011);
+        assert_eq!(bit_reverse(0b1011, 4), 0b1101);
+        assert_eq!(bit_reverse(0, 0), 0);
+    }
+
+    #[test]
+    fn test_bit_reverse_permutation() {
+        let mut values: Vec<usize> = (0..8).collect();
+        bit_reverse_permutation(&mut values);
+
+        assert_eq!(values, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+    }
+
+    #[test]
+    fn test_distribute_powers() {
+        let mut values = vec![Fr::from(1); 4];
+        distribute_powers(&mut values, Fr::from(3));
+
+        assert_eq!(
+            values,
+            vec![Fr::from(1), Fr::from(3), Fr::from(9), Fr::from(27)]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error::Error;
+
+/// Parameters shared by the FRI prover and verifier.
+///
+/// A codeword of a polynomial of degree below `d` is `d * blowup_factor` evaluations
+/// long. Every round folds it `folding_factor` times shorter, down to a polynomial of
+/// fewer than `final_poly_len` coefficients that is sent in the clear, and each of the
+/// `num_queries` queries checks one path through the folds. A query catches a word far
+/// from the code with probability about `1 - 1 / blowup_factor`, so soundness grows
+/// with both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FriConfig {
+    pub blowup_factor: usize,
+    pub folding_factor: usize,
+    pub num_queries: usize,
+    pub final_poly_len: usize,
+}
+
+impl FriConfig {
+    pub fn new(
+        blowup_factor: usize,
+        folding_factor: usize,
+        num_queries: usize,
+        final_poly_len: usize,
+    ) -> Result<Self, Error> {
+        if blowup_factor < 2 || !blowup_factor.is_power_of_two() {
+            return Err(Error::InvalidConfig {
+                reason: "the blowup factor must be a power of two of at least 2",
+            });
+        }
+        if folding_factor < 2 || !folding_factor.is_power_of_two() {
+            return Err(Error::InvalidConfig {
+                reason: "the folding factor must be a power of two of at least 2",
+            });
+        }
+        if num_queries == 0 {
+            return Err(Error::InvalidConfig {
+                reason: "at least one query is needed",
+            });
+        }
+        if !final_poly_len.is_power_of_two() {
+            return Err(Error::InvalidConfig {
+                reason: "the final polynomial length must be a power of two",
+            });
+        }
+
+        Ok(Self {
+            blowup_factor,
+            folding_factor,
+            num_queries,
+            final_poly_len,
+        })
+    }
+
+    /// Degree bounds of the committed layers, from the codeword's down to the last one
+    /// folded into the final polynomial. Fails unless `degree_bound` is a power of two
+    /// above `final_poly_len`, so there is at least one fold.
+    pub fn layer_degree_bounds(&self, degree_bound: usize) -> Result<Vec<usize>, Error> {
+        if !degree_bound.is_power_of_two() || degree_bound <= self.final_poly_len {
+            return Err(Error::InvalidCodewordLength {
+                len: degree_bound * self.blowup_factor,
+            });
+        }
+
+        let mut bounds = vec![degree_bound];
+        while *bounds.last().unwrap() > self.final_poly_len {
+            let next = bounds.last().unwrap().div_ceil(self.folding_factor);
+            bounds.push(next);
+        }
+        // the last bound is the final polynomial's, it is never committed
+        bounds.pop();
+
+        if bounds.last().unwrap() * self.blowup_factor < self.folding_factor {
+            return Err(Error::InvalidConfig {
+                reason: "the last layer is shorter than the folding factor",
+            });
+        }
+
+        Ok(bounds)
+    }
+
+    /// Degree bound of a codeword of `len` values.
+    pub fn degree_bound(&self, len: usize) -> Result<usize, Error> {
+        if !len.is_multiple_of(self.blowup_factor) {
+            return Err(Error::InvalidCodewordLength { len });
+        }
+
+        Ok(len / self.blowup_factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_validation() {
+        assert!(FriConfig::new(4, 2, 20, 1).is_ok());
+        assert!(FriConfig::new(3, 2, 20, 1).is_err());
+        assert!(FriConfig::new(4, 1, 20, 1).is_err());
+        assert!(FriConfig::new(4, 2, 0, 1).is_err());
+        assert!(FriConfig::new(4, 2, 20, 3).is_err());
+    }
+
+    #[test]
+    fn test_layer_degree_bounds() {
+        let config = FriConfig::new(4, 4, 20, 1).unwrap();
+        assert_eq!(config.layer_degree_bounds(64).unwrap(), vec![64, 16, 4]);
+        // the last fold overshoots, degree below 2 folded by 4 is a constant
+        assert_eq!(config.layer_degree_bounds(32).unwrap(), vec![32, 8, 2]);
+
+        let config = FriConfig::new(2, 2, 20, 4).unwrap();
+        assert_eq!(config.layer_degree_bounds(16).unwrap(), vec![16, 8]);
+        assert!(config.layer_degree_bounds(4).is_err());
+        assert!(config.layer_degree_bounds(12).is_err());
+
+        // a layer of 2 * 2 values can't be folded 8 to 1
+        let config = FriConfig::new(2, 8, 20, 1).unwrap();
+        assert!(config.layer_degree_bounds(16).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Why a FRI proof could not be produced or was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The blowup, folding factor, number of queries or final length can't be used.
+    InvalidConfig { reason: &'static str },
+    /// The codeword is not `degree_bound * blowup_factor` long for a power of two
+    /// degree bound above the final polynomial's length.
+    InvalidCodewordLength { len: usize },
+    /// Some part of the proof does not have the shape the config requires.
+    InvalidProofLength { expected: usize, got: usize },
+    /// The opening of `layer` was rejected by its Merkle root.
+    Merkle {
+        layer: usize,
+        source: merkle::error::Error,
+    },
+    /// Query `query` opened a value in `layer` that is not the fold of the layer below.
+    FoldMismatch { layer: usize, query: usize },
+    /// Query `query` folded down to a value the final polynomial does not take.
+    FinalPolyMismatch { query: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidConfig { reason } => write!(f, "invalid FRI config: {reason}"),
+            Self::InvalidCodewordLength { len } => {
+                write!(f, "a codeword of {len} values does not fit the config")
+            }
+            Self::InvalidProofLength { expected, got } => {
+                write!(f, "expected {expected} elements, got {got}")
+            }
+            Self::Merkle { layer, source } => {
+                write!(f, "opening of layer {layer} rejected: {source}")
+            }
+            Self::FoldMismatch { layer, query } => {
+                write!(f, "query {query} does not fold into layer {layer}")
+            }
+            Self::FinalPolyMismatch { query } => {
+                write!(f, "query {query} does not match the final polynomial")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Merkle { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
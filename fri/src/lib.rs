@@ -0,0 +1,6 @@
+pub mod config;
+pub mod error;
+pub mod protocol;
+pub mod prover;
+pub mod utils;
+pub mod verifier;
@@ -0,0 +1,251 @@
+use ark_ff::PrimeField;
+use merkle::tree::MerkleProof;
+
+/// Opens every FRI proof on the transcript.
+pub(crate) const DOMAIN_SEPARATOR: &[u8] = b"fri";
+
+/// The queried cosets of one committed layer.
+///
+/// `values` holds the `folding_factor` values of every distinct queried coset, in
+/// increasing coset order, and `proof` opens them all against the layer's root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FriLayerOpening<F: PrimeField, D> {
+    pub values: Vec<F>,
+    pub proof: MerkleProof<D>,
+}
+
+impl<F: PrimeField, D> FriLayerOpening<F, D> {
+    pub fn new(values: Vec<F>, proof: MerkleProof<D>) -> Self {
+        Self { values, proof }
+    }
+}
+
+/// Proof that a committed codeword is close to a Reed-Solomon codeword.
+///
+/// `layer_roots[0]` commits to the codeword itself and every following root to its
+/// fold by the previous layer's challenge. The last fold is sent as the coefficients
+/// of `final_poly`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FriProof<F: PrimeField, D> {
+    pub layer_roots: Vec<D>,
+    pub final_poly: Vec<F>,
+    pub layer_openings: Vec<FriLayerOpening<F, D>>,
+}
+
+impl<F: PrimeField, D> FriProof<F, D> {
+    pub fn new(
+        layer_roots: Vec<D>,
+        final_poly: Vec<F>,
+        layer_openings: Vec<FriLayerOpening<F, D>>,
+    ) -> Self {
+        Self {
+            layer_roots,
+            final_poly,
+            layer_openings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::FriConfig, error::Error, prover::FriProver, utils::encode, verifier::FriVerifier,
+    };
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use merkle::hasher::{Keccak256Hasher, MerkleHasher, PoseidonHasher};
+    use sha3::Keccak256;
+    use sumcheck::{
+        fiat_shamir::FiatShamir,
+        transcript::{PoseidonTranscript, Transcript},
+    };
+
+    fn random_poly(degree_bound: usize, rng: &mut StdRng) -> Vec<Fr> {
+        (0..degree_bound).map(|_| Fr::rand(rng)).collect()
+    }
+
+    fn keccak_transcript() -> FiatShamir<Keccak256, Fr> {
+        FiatShamir::new()
+    }
+
+    fn prove_and_verify<H: MerkleHasher<Fr>, T: Transcript<Fr>>(
+        config: &FriConfig,
+        hasher: &H,
+        codeword: &[Fr],
+        new_transcript: impl Fn() -> T,
+    ) -> Result<(), Error> {
+        let proof = FriProver::prove(config, hasher, codeword, &mut new_transcript())?;
+        let degree_bound = codeword.len() / config.blowup_factor;
+
+        FriVerifier::verify(config, hasher, degree_bound, &proof, &mut new_transcript())
+    }
+
+    #[test]
+    fn test_fri_protocol() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for (blowup, k, final_len) in [(2, 2, 1), (4, 2, 4), (4, 4, 1), (2, 8, 2), (8, 4, 4)] {
+            let config = FriConfig::new(blowup, k, 16, final_len).unwrap();
+
+            for degree_bound in [16, 32, 64] {
+                let codeword = encode(&random_poly(degree_bound, &mut rng), blowup).unwrap();
+
+                assert_eq!(
+                    prove_and_verify(&config, &Keccak256Hasher, &codeword, keccak_transcript),
+                    Ok(()),
+                    "blowup {blowup}, folding {k}, final length {final_len}, degree {degree_bound}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fri_protocol_with_poseidon() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let config = FriConfig::new(4, 4, 8, 1).unwrap();
+        let codeword = encode(&random_poly(64, &mut rng), 4).unwrap();
+
+        assert_eq!(
+            prove_and_verify(
+                &config,
+                &PoseidonHasher::new(),
+                &codeword,
+                PoseidonTranscript::new
+            ),
+            Ok(())
+        );
+    }
+
+    // a polynomial of lower degree is still below the bound
+    #[test]
+    fn test_fri_accepts_lower_degree() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let config = FriConfig::new(2, 2, 16, 1).unwrap();
+        let mut coeffs = random_poly(5, &mut rng);
+        coeffs.resize(32, Fr::from(0));
+
+        let codeword = encode(&coeffs, 2).unwrap();
+        assert_eq!(
+            prove_and_verify(&config, &Keccak256Hasher, &codeword, keccak_transcript),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_fri_rejects_high_degree() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let config = FriConfig::new(4, 2, 16, 1).unwrap();
+
+        // degree 127 claimed below 32
+        let codeword = encode(&random_poly(128, &mut rng), 1).unwrap();
+        assert!(prove_and_verify(&config, &Keccak256Hasher, &codeword, keccak_transcript).is_err());
+
+        // a random word of the right length
+        let codeword = random_poly(128, &mut rng);
+        assert!(prove_and_verify(&config, &Keccak256Hasher, &codeword, keccak_transcript).is_err());
+    }
+
+    // half the codeword replaced: far from every codeword, so some query catches it
+    #[test]
+    fn test_fri_rejects_corrupted_codeword() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let config = FriConfig::new(4, 4, 16, 1).unwrap();
+
+        let mut codeword = encode(&random_poly(32, &mut rng), 4).unwrap();
+        for value in codeword.iter_mut().step_by(2) {
+            *value = Fr::rand(&mut rng);
+        }
+
+        assert!(prove_and_verify(&config, &Keccak256Hasher, &codeword, keccak_transcript).is_err());
+    }
+
+    #[test]
+    fn test_fri_rejects_tampered_proof() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let config = FriConfig::new(4, 2, 16, 2).unwrap();
+        let codeword = encode(&random_poly(32, &mut rng), 4).unwrap();
+        let proof = FriProver::prove(
+            &config,
+            &Keccak256Hasher,
+            &codeword,
+            &mut keccak_transcript(),
+        )
+        .unwrap();
+
+        let verify = |proof: &FriProof<Fr, [u8; 32]>| {
+            FriVerifier::verify(
+                &config,
+                &Keccak256Hasher,
+                32,
+                proof,
+                &mut keccak_transcript(),
+            )
+        };
+        assert_eq!(verify(&proof), Ok(()));
+
+        // an opened value no longer matches its root
+        let mut tampered = proof.clone();
+        tampered.layer_openings[1].values[0] += Fr::from(1);
+        assert!(matches!(
+            verify(&tampered),
+            Err(Error::Merkle { layer: 1, .. })
+        ));
+
+        // another final polynomial changes every challenge drawn after it
+        let mut tampered = proof.clone();
+        tampered.final_poly[0] += Fr::from(1);
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.final_poly.push(Fr::from(0));
+        assert_eq!(
+            verify(&tampered),
+            Err(Error::InvalidProofLength {
+                expected: 2,
+                got: 3
+            })
+        );
+
+        let mut tampered = proof.clone();
+        tampered.layer_openings.pop();
+        assert!(matches!(
+            verify(&tampered),
+            Err(Error::InvalidProofLength { .. })
+        ));
+
+        // the proof is for degree below 32, not 16
+        assert!(FriVerifier::verify(
+            &config,
+            &Keccak256Hasher,
+            16,
+            &proof,
+            &mut keccak_transcript()
+        )
+        .is_err());
+    }
+
+    // a prover that commits to honest layers but folds with its own challenge is caught
+    // by the fold checks or the final polynomial
+    #[test]
+    fn test_fri_rejects_another_transcript() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let config = FriConfig::new(4, 2, 16, 1).unwrap();
+        let codeword = encode(&random_poly(32, &mut rng), 4).unwrap();
+
+        let proof = FriProver::prove(
+            &config,
+            &Keccak256Hasher,
+            &codeword,
+            &mut keccak_transcript(),
+        )
+        .unwrap();
+
+        let mut transcript = keccak_transcript();
+        transcript.append_message(b"context", b"another statement");
+        assert!(
+            FriVerifier::verify(&config, &Keccak256Hasher, 32, &proof, &mut transcript).is_err()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    config::FriConfig,
+    error::Error,
+    protocol::{FriLayerOpening, FriProof, DOMAIN_SEPARATOR},
+    utils::{
+        codeword_domain, fold_domain, fold_layer, queried_cosets, query_positions, to_leaf_order,
+    },
+};
+use ark_ff::PrimeField;
+use merkle::{commitment::VectorCommitment, hasher::MerkleHasher, tree::MerkleTree};
+use std::marker::PhantomData;
+use sumcheck::transcript::Transcript;
+
+#[derive(Clone, Debug)]
+pub struct FriProver<F: PrimeField, H: MerkleHasher<F>> {
+    _field: PhantomData<F>,
+    _hasher: PhantomData<H>,
+}
+
+impl<F: PrimeField, H: MerkleHasher<F>> FriProver<F, H> {
+    /// Proves `codeword`, the evaluations of a polynomial over
+    /// [`codeword_domain`], has degree below `codeword.len() / blowup_factor`.
+    ///
+    /// Every layer is committed to before its folding challenge is drawn, the last
+    /// one is interpolated into the final polynomial, and the queries are drawn once
+    /// everything is bound to the transcript.
+    pub fn prove<T: Transcript<F>>(
+        config: &FriConfig,
+        hasher: &H,
+        codeword: &[F],
+        transcript: &mut T,
+    ) -> Result<FriProof<F, H::Digest>, Error> {
+        let degree_bound = config.degree_bound(codeword.len())?;
+        let bounds = config.layer_degree_bounds(degree_bound)?;
+        let k = config.folding_factor;
+
+        transcript.domain_separator(DOMAIN_SEPARATOR);
+        transcript.append_message(b"codeword_len", &(codeword.len() as u64).to_le_bytes());
+
+        let mut domain = codeword_domain(codeword.len())?;
+        let mut layer = codeword.to_vec();
+        let mut trees = Vec::with_capacity(bounds.len());
+
+        for i in 0..bounds.len() {
+            let tree = MerkleTree::new(hasher.clone(), &to_leaf_order(&layer, k))
+                .map_err(|source| Error::Merkle { layer: i, source })?;
+            transcript.append_point(b"layer_root", &tree.root());
+
+            let alpha = transcript.challenge_scalar(b"alpha");
+            layer = fold_layer(&layer, &domain, alpha, k);
+            domain = fold_domain(&domain, k);
+            trees.push(tree);
+        }
+
+        let mut final_poly = domain.coset_ifft(&layer);
+        final_poly.truncate(final_poly_len(&bounds, k));
+        transcript.append_fields(b"final_poly", &final_poly);
+
+        let positions = query_positions(transcript, config.num_queries, codeword.len());
+
+        Ok(FriProof::new(
+            trees.iter().map(|tree| tree.root()).collect(),
+            final_poly,
+            Self::open_layers(&trees.iter().collect::<Vec<_>>(), positions, k)?,
+        ))
+    }
+
+    /// Opens the cosets every query position falls in, layer by layer, for
+    /// [`FriVerifier::verify_layers`](crate::verifier::FriVerifier::verify_layers).
+    /// `trees` commit to the layers in leaf order, each folded `k` times from the
+    /// previous one.
+    pub fn open_layers(
+        trees: &[&MerkleTree<F, H>],
+        mut positions: Vec<usize>,
+        k: usize,
+    ) -> Result<Vec<FriLayerOpening<F, H::Digest>>, Error> {
+        let mut layer_openings = Vec::with_capacity(trees.len());
+
+        for (i, tree) in trees.iter().enumerate() {
+            let (_, leaves) = queried_cosets(&positions, tree.n_leaves(), k);
+            let (values, proof) = tree
+                .open(&leaves)
+                .map_err(|source| Error::Merkle { layer: i, source })?;
+            layer_openings.push(FriLayerOpening::new(values, proof));
+
+            // a position lands on its coset's position in the next layer
+            let m = tree.n_leaves() / k;
+            positions.iter_mut().for_each(|position| *position %= m);
+        }
+
+        Ok(layer_openings)
+    }
+}
+
+/// Number of coefficients of the final polynomial, the last committed layer's degree
+/// bound folded once more.
+pub(crate) fn final_poly_len(bounds: &[usize], k: usize) -> usize {
+    bounds
+        .last()
+        .expect("there is at least one layer")
+        .div_ceil(k)
+}
@@ -0,0 +1,191 @@
+use crate::error::Error;
+use ark_ff::PrimeField;
+use fft::domain::Radix2Domain;
+use sumcheck::transcript::Transcript;
+
+/// Domain a codeword of `len` values is evaluated over: the subgroup of order `len`
+/// shifted by the field's generator, so no evaluation point is ever zero and the
+/// domain never meets the subgroups a protocol uses for its own polynomials.
+pub fn codeword_domain<F: PrimeField>(len: usize) -> Result<Radix2Domain<F>, Error> {
+    if !len.is_power_of_two() {
+        return Err(Error::InvalidCodewordLength { len });
+    }
+
+    Radix2Domain::new_coset(len).ok_or(Error::InvalidCodewordLength { len })
+}
+
+/// Reed-Solomon encodes `coeffs`, evaluating the polynomial over a domain
+/// `blowup_factor` times larger than its (power of two) number of coefficients.
+pub fn encode<F: PrimeField>(coeffs: &[F], blowup_factor: usize) -> Result<Vec<F>, Error> {
+    let len = coeffs.len().max(1).next_power_of_two() * blowup_factor;
+
+    Ok(codeword_domain(len)?.coset_fft(coeffs))
+}
+
+/// Domain of the codeword folded `k` times shorter: `{x^k}` for every `x` of `domain`.
+pub fn fold_domain<F: PrimeField>(domain: &Radix2Domain<F>, k: usize) -> Radix2Domain<F> {
+    Radix2Domain::new(domain.size / k)
+        .and_then(|folded| folded.get_coset(domain.offset.pow([k as u64])))
+        .expect("A subgroup's subgroups are in the field too")
+}
+
+/// Folds the values of `f` over the coset `{x * zeta^m}` of the `k`-th roots of unity
+/// into `f'(x^k)`.
+///
+/// Writing `f(X) = sum_i X^i f_i(X^k)`, the folded polynomial is
+/// `f'(Y) = sum_i alpha^i f_i(Y)`, of degree `k` times lower. An inverse DFT of the
+/// coset's values gives every `x^i f_i(x^k)`, and the fold weighs them with
+/// `(alpha / x)^i`.
+pub fn fold_coset<F: PrimeField>(values: &[F], x_inv: F, alpha: F, zeta_inv: F, k_inv: F) -> F {
+    let ratio = alpha * x_inv;
+
+    let mut folded = F::zero();
+    let mut ratio_pow = F::one();
+    let mut zeta_pow = F::one();
+
+    for _ in 0..values.len() {
+        let mut coefficient = F::zero();
+        let mut twiddle = F::one();
+        for value in values {
+            coefficient += twiddle * value;
+            twiddle *= zeta_pow;
+        }
+
+        folded += ratio_pow * coefficient;
+        ratio_pow *= ratio;
+        zeta_pow *= zeta_inv;
+    }
+
+    folded * k_inv
+}
+
+/// The inverses of a `k`-th root of unity of `domain` and of `k`, which every fold of
+/// a coset of `domain` needs.
+pub fn fold_constants<F: PrimeField>(domain: &Radix2Domain<F>, k: usize) -> (F, F) {
+    let zeta_inv = domain.generator_inv.pow([(domain.size / k) as u64]);
+    let k_inv = F::from(k as u64)
+        .inverse()
+        .expect("k is a power of two below the field order");
+
+    (zeta_inv, k_inv)
+}
+
+/// Folds every coset of `codeword`, evaluated over `domain`, with `alpha`.
+pub fn fold_layer<F: PrimeField>(
+    codeword: &[F],
+    domain: &Radix2Domain<F>,
+    alpha: F,
+    k: usize,
+) -> Vec<F> {
+    let m = codeword.len() / k;
+    let (zeta_inv, k_inv) = fold_constants(domain, k);
+    let mut x_inv = domain.offset_inv;
+
+    (0..m)
+        .map(|c| {
+            let values: Vec<F> = (0..k).map(|j| codeword[c + j * m]).collect();
+            let folded = fold_coset(&values, x_inv, alpha, zeta_inv, k_inv);
+            x_inv *= domain.generator_inv;
+
+            folded
+        })
+        .collect()
+}
+
+/// Orders `codeword` coset by coset: the values at `c, c + m, c + 2m, ...` become the
+/// `k` neighbouring leaves `c * k..(c + 1) * k`, so a query opens one subtree.
+pub fn to_leaf_order<F: PrimeField>(codeword: &[F], k: usize) -> Vec<F> {
+    let m = codeword.len() / k;
+
+    (0..codeword.len())
+        .map(|leaf| codeword[leaf / k + (leaf % k) * m])
+        .collect()
+}
+
+/// The distinct cosets the `positions` of a layer of `len` values fall in, sorted,
+/// and the leaves that open them.
+pub fn queried_cosets(positions: &[usize], len: usize, k: usize) -> (Vec<usize>, Vec<usize>) {
+    let m = len / k;
+
+    let mut cosets: Vec<usize> = positions.iter().map(|p| p % m).collect();
+    cosets.sort_unstable();
+    cosets.dedup();
+
+    let leaves = cosets.iter().flat_map(|c| c * k..(c + 1) * k).collect();
+
+    (cosets, leaves)
+}
+
+/// Draws `n` positions in a codeword of `len` values, `len` a power of two.
+pub fn query_positions<F: PrimeField, T: Transcript<F>>(
+    transcript: &mut T,
+    n: usize,
+    len: usize,
+) -> Vec<usize> {
+    transcript
+        .challenge_scalars(b"query", n)
+        .iter()
+        .map(|challenge| challenge.into_bigint().as_ref()[0] as usize % len)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use polynomials::univariate::univariate_poly::UnivariatePoly;
+
+    #[test]
+    fn test_encode_evaluates_over_the_coset() {
+        let coeffs: Vec<Fr> = (1..=4).map(Fr::from).collect();
+        let codeword = encode(&coeffs, 4).unwrap();
+        let domain = codeword_domain::<Fr>(16).unwrap();
+        let poly = UnivariatePoly::new(coeffs);
+
+        assert_eq!(codeword.len(), 16);
+        for (i, value) in codeword.iter().enumerate() {
+            assert_eq!(*value, poly.evaluate(domain.element(i)));
+        }
+    }
+
+    // f'(Y) = sum_i alpha^i f_i(Y) with f(X) = sum_i X^i f_i(X^k), computed from the
+    // coefficients directly
+    #[test]
+    fn test_fold_layer_folds_the_polynomial() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let coeffs: Vec<Fr> = (0..16).map(|_| Fr::rand(&mut rng)).collect();
+        let alpha = Fr::rand(&mut rng);
+
+        for k in [2, 4, 8] {
+            let domain = codeword_domain::<Fr>(64).unwrap();
+            let folded = fold_layer(&domain.coset_fft(&coeffs), &domain, alpha, k);
+
+            let folded_coeffs: Vec<Fr> = (0..16 / k)
+                .map(|j| {
+                    (0..k)
+                        .rev()
+                        .fold(Fr::from(0), |acc, i| acc * alpha + coeffs[j * k + i])
+                })
+                .collect();
+
+            assert_eq!(folded, fold_domain(&domain, k).coset_fft(&folded_coeffs));
+        }
+    }
+
+    #[test]
+    fn test_leaf_order_groups_cosets() {
+        let codeword: Vec<Fr> = (0..8).map(Fr::from).collect();
+
+        // m = 2: cosets {0, 2, 4, 6} and {1, 3, 5, 7}
+        assert_eq!(
+            to_leaf_order(&codeword, 4),
+            [0, 2, 4, 6, 1, 3, 5, 7].map(Fr::from)
+        );
+        assert_eq!(
+            queried_cosets(&[5, 1, 2], 8, 4),
+            (vec![0, 1], (0..8).collect())
+        );
+    }
+}
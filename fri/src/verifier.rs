@@ -0,0 +1,166 @@
+use crate::{
+    config::FriConfig,
+    error::Error,
+    protocol::{FriLayerOpening, FriProof, DOMAIN_SEPARATOR},
+    prover::final_poly_len,
+    utils::{
+        codeword_domain, fold_constants, fold_coset, fold_domain, queried_cosets, query_positions,
+    },
+};
+use ark_ff::PrimeField;
+use fft::domain::Radix2Domain;
+use merkle::{hasher::MerkleHasher, tree::MerkleTree};
+use polynomials::univariate::univariate_poly::UnivariatePoly;
+use std::marker::PhantomData;
+use sumcheck::transcript::Transcript;
+
+#[derive(Clone, Debug)]
+pub struct FriVerifier<F: PrimeField, H: MerkleHasher<F>> {
+    _field: PhantomData<F>,
+    _hasher: PhantomData<H>,
+}
+
+impl<F: PrimeField, H: MerkleHasher<F>> FriVerifier<F, H> {
+    /// Checks `proof` shows the committed codeword is close to the evaluations of a
+    /// polynomial of degree below `degree_bound`.
+    ///
+    /// Every query is followed down the layers by [`Self::verify_layers`] and has to
+    /// end on the final polynomial.
+    pub fn verify<T: Transcript<F>>(
+        config: &FriConfig,
+        hasher: &H,
+        degree_bound: usize,
+        proof: &FriProof<F, H::Digest>,
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        let bounds = config.layer_degree_bounds(degree_bound)?;
+        let k = config.folding_factor;
+        let len = degree_bound * config.blowup_factor;
+
+        check_len(bounds.len(), proof.layer_roots.len())?;
+        check_len(bounds.len(), proof.layer_openings.len())?;
+        check_len(final_poly_len(&bounds, k), proof.final_poly.len())?;
+
+        transcript.domain_separator(DOMAIN_SEPARATOR);
+        transcript.append_message(b"codeword_len", &(len as u64).to_le_bytes());
+
+        let alphas: Vec<F> = proof
+            .layer_roots
+            .iter()
+            .map(|root| {
+                transcript.append_point(b"layer_root", root);
+                transcript.challenge_scalar(b"alpha")
+            })
+            .collect();
+        transcript.append_fields(b"final_poly", &proof.final_poly);
+
+        let positions = query_positions(transcript, config.num_queries, len);
+        let folded = Self::verify_layers(
+            hasher,
+            k,
+            codeword_domain(len)?,
+            &proof.layer_roots,
+            &proof.layer_openings,
+            &alphas,
+            positions,
+        )?;
+
+        let final_poly = UnivariatePoly::new(proof.final_poly.clone());
+        for (query, (x, value)) in folded.into_iter().enumerate() {
+            if value != final_poly.evaluate(x) {
+                return Err(Error::FinalPolyMismatch { query });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Follows every query position down the committed layers, `domain` being the
+    /// first one's, and returns every query's point and folded value in the domain the
+    /// last layer folds into. There has to be at least one layer.
+    ///
+    /// The coset a position falls in is opened against the layer's root, and folded
+    /// with the layer's challenge into the value the next layer must hold.
+    pub fn verify_layers(
+        hasher: &H,
+        k: usize,
+        mut domain: Radix2Domain<F>,
+        layer_roots: &[H::Digest],
+        layer_openings: &[FriLayerOpening<F, H::Digest>],
+        alphas: &[F],
+        mut positions: Vec<usize>,
+    ) -> Result<Vec<(F, F)>, Error> {
+        if layer_roots.is_empty() {
+            return Err(Error::InvalidProofLength {
+                expected: 1,
+                got: 0,
+            });
+        }
+        check_len(layer_roots.len(), layer_openings.len())?;
+        check_len(layer_roots.len(), alphas.len())?;
+
+        let mut folded: Vec<Option<F>> = vec![None; positions.len()];
+
+        for (layer, ((root, opening), alpha)) in layer_roots
+            .iter()
+            .zip(layer_openings)
+            .zip(alphas)
+            .enumerate()
+        {
+            let n = domain.size;
+            let m = n / k;
+            let (cosets, leaves) = queried_cosets(&positions, n, k);
+
+            check_len(leaves.len(), opening.values.len())?;
+            MerkleTree::<F, H>::verify_batch(
+                hasher,
+                root,
+                n,
+                &leaves,
+                &opening.values,
+                &opening.proof,
+            )
+            .map_err(|source| Error::Merkle { layer, source })?;
+
+            let (zeta_inv, k_inv) = fold_constants(&domain, k);
+
+            for (query, (position, value)) in positions.iter_mut().zip(&mut folded).enumerate() {
+                let (c, j) = (*position % m, *position / m);
+                let slot = cosets
+                    .binary_search(&c)
+                    .expect("every queried coset is opened");
+                let values = &opening.values[slot * k..(slot + 1) * k];
+
+                // the value the previous layer folded into sits at `position` here
+                if value.is_some_and(|value| value != values[j]) {
+                    return Err(Error::FoldMismatch { layer, query });
+                }
+
+                let x_inv = domain.offset_inv * domain.generator_inv.pow([c as u64]);
+                *value = Some(fold_coset(values, x_inv, *alpha, zeta_inv, k_inv));
+                *position = c;
+            }
+
+            domain = fold_domain(&domain, k);
+        }
+
+        Ok(positions
+            .into_iter()
+            .zip(folded)
+            .map(|(position, value)| {
+                (
+                    domain.element(position),
+                    value.expect("every query went through a layer"),
+                )
+            })
+            .collect())
+    }
+}
+
+fn check_len(expected: usize, got: usize) -> Result<(), Error> {
+    if expected != got {
+        return Err(Error::InvalidProofLength { expected, got });
+    }
+
+    Ok(())
+}
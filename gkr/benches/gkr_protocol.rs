@@ -56,7 +56,8 @@ fn benchmark_gkr(c: &mut Criterion, input_size: usize) {
     let (mut prover_circuit, prover_input) = build_circuit(input_size);
     let mut prover_transcript = FiatShamir::<Keccak256, Fq>::new();
 
-    let gkr_proof = GKRProver::prove(&prover_input, &mut prover_circuit, &mut prover_transcript);
+    let gkr_proof =
+        GKRProver::prove(&prover_input, &mut prover_circuit, &mut prover_transcript).unwrap();
 
     // bench prover
     c.bench_function(&format!("gkr_prover/{}_inputs", input_size), |b| {
@@ -75,7 +76,8 @@ fn benchmark_gkr(c: &mut Criterion, input_size: usize) {
         &mut verify_circuit,
         &mut verifier_transcript,
         &gkr_proof,
-    ));
+    )
+    .is_ok());
 
     // bench verifier
     c.bench_function(&format!("gkr_verifier/{}_inputs", input_size), |b| {
@@ -0,0 +1,490 @@
+use crate::circuit::{Circuit, Gate, Op};
+use crate::error::Error;
+use ark_ff::PrimeField;
+use std::collections::HashMap;
+
+/// Handle to a value in a circuit under construction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Wire(usize);
+
+#[derive(Clone, Debug)]
+enum Node<F: PrimeField> {
+    Input(usize),
+    Constant(F),
+    Gate { left: Wire, right: Wire, op: Op },
+}
+
+/// What occupies a slot of a layer between the input and the output.
+#[derive(Clone, Debug)]
+enum Slot {
+    Gate(Wire),
+    Relay(Wire),
+    Zero,
+}
+
+/// Builds a [`Circuit`] from an arbitrary DAG of additions and multiplications.
+///
+/// Every gate is placed on the layer of its longest path from the input. Wires that
+/// skip layers are carried up by relay gates `w + 0`, reading a zero slot the builder
+/// reserves on the layer below. The constants, then the zero relays read on the input
+/// layer, lead the input layer as the circuit's public inputs, so a prover committing
+/// to its input can't change them. The user's inputs follow, and the input is padded
+/// to a power of two wide enough for every layer to fit under the halving widths of
+/// [`Circuit::eval`].
+#[derive(Clone, Debug)]
+pub struct CircuitBuilder<F: PrimeField> {
+    nodes: Vec<Node<F>>,
+    num_inputs: usize,
+    constants: Vec<F>,
+    outputs: Vec<Wire>,
+}
+
+/// A built circuit along with the layout needed to feed it and read it back.
+#[derive(Debug)]
+pub struct BuiltCircuit<F: PrimeField> {
+    pub circuit: Circuit<F>,
+    pub num_inputs: usize,
+    pub constants: Vec<F>,
+    pub input_len: usize,
+    /// Position of every output wire in the output layer, in the order they were marked.
+    pub outputs: Vec<usize>,
+}
+
+impl<F: PrimeField> CircuitBuilder<F> {
+    pub fn new() -> Self {
+        CircuitBuilder {
+            nodes: vec![],
+            num_inputs: 0,
+            constants: vec![],
+            outputs: vec![],
+        }
+    }
+
+    pub fn input(&mut self) -> Wire {
+        self.num_inputs += 1;
+        self.push(Node::Input(self.num_inputs - 1))
+    }
+
+    pub fn inputs(&mut self, n: usize) -> Vec<Wire> {
+        (0..n).map(|_| self.input()).collect()
+    }
+
+    pub fn constant(&mut self, value: F) -> Wire {
+        self.constants.push(value);
+        self.push(Node::Constant(value))
+    }
+
+    pub fn add(&mut self, left: Wire, right: Wire) -> Wire {
+        self.gate(left, right, Op::ADD)
+    }
+
+    pub fn mul(&mut self, left: Wire, right: Wire) -> Wire {
+        self.gate(left, right, Op::MUL)
+    }
+
+    /// Marks `wire` as an output. Outputs are laid out on the output layer in the order
+    /// they are marked.
+    pub fn output(&mut self, wire: Wire) {
+        self.check(wire);
+        self.outputs.push(wire);
+    }
+
+    pub fn build(&self) -> BuiltCircuit<F> {
+        assert!(!self.outputs.is_empty(), "Circuit has no outputs");
+
+        let depths = self.depths();
+        let depth = self
+            .outputs
+            .iter()
+            .map(|w| depths[w.0])
+            .max()
+            .unwrap()
+            .max(1);
+
+        let mut layout = Layout {
+            builder: self,
+            depths: &depths,
+            slots: vec![vec![]; depth + 1],
+            positions: HashMap::new(),
+            zeros: vec![None; depth + 1],
+            input_zero: false,
+            inputs_offset: 0,
+        };
+
+        let outputs: Vec<usize> = self
+            .outputs
+            .iter()
+            .map(|w| layout.position(*w, depth))
+            .collect();
+
+        // the first pass settled whether the input layer needs the shared zero, so the
+        // user's inputs can now be placed after the public slots
+        let mut public_inputs = self.constants.clone();
+        if layout.input_zero {
+            public_inputs.push(F::zero());
+        }
+        layout.inputs_offset = public_inputs.len();
+
+        // the input layer holds the constants, possibly the shared zero and the inputs
+        let input_width = self.num_inputs + self.constants.len() + layout.input_zero as usize;
+        let mut input_len = input_width.next_power_of_two().max(2);
+        for (level, slots) in layout.slots.iter().enumerate().skip(1) {
+            if slots.len() > 2 {
+                input_len = input_len.max(slots.len().next_power_of_two() << level);
+            }
+        }
+
+        let layers = (1..=depth).rev().map(|level| layout.gates(level)).collect();
+
+        BuiltCircuit {
+            circuit: Circuit::new(layers, input_len).with_public_inputs(public_inputs),
+            num_inputs: self.num_inputs,
+            constants: self.constants.clone(),
+            input_len,
+            outputs,
+        }
+    }
+
+    fn gate(&mut self, left: Wire, right: Wire, op: Op) -> Wire {
+        self.check(left);
+        self.check(right);
+        self.push(Node::Gate { left, right, op })
+    }
+
+    fn push(&mut self, node: Node<F>) -> Wire {
+        self.nodes.push(node);
+        Wire(self.nodes.len() - 1)
+    }
+
+    fn check(&self, wire: Wire) {
+        assert!(wire.0 < self.nodes.len(), "Unknown wire {:?}", wire);
+    }
+
+    /// Longest path from the input layer to every node. Wires only point backwards,
+    /// so a single pass in creation order is enough.
+    fn depths(&self) -> Vec<usize> {
+        let mut depths = vec![0; self.nodes.len()];
+        for (i, node) in self.nodes.iter().enumerate() {
+            if let Node::Gate { left, right, .. } = node {
+                depths[i] = depths[left.0].max(depths[right.0]) + 1;
+            }
+        }
+
+        depths
+    }
+}
+
+impl<F: PrimeField> Default for CircuitBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> BuiltCircuit<F> {
+    /// Lays out the full input layer: the public inputs, then the inputs, then zeros
+    /// up to the padded length.
+    pub fn input_layer(&self, inputs: &[F]) -> Result<Vec<F>, Error> {
+        if inputs.len() != self.num_inputs {
+            return Err(Error::InvalidInputLength {
+                expected: self.num_inputs,
+                got: inputs.len(),
+            });
+        }
+
+        let mut layer = self.circuit.public_inputs().to_vec();
+        layer.extend_from_slice(inputs);
+        layer.resize(self.input_len, F::zero());
+
+        Ok(layer)
+    }
+
+    /// Evaluates the circuit and returns the marked outputs in order.
+    pub fn evaluate(&self, inputs: &[F]) -> Result<Vec<F>, Error> {
+        let layers = self.circuit.eval(self.input_layer(inputs)?);
+        let output_layer = layers.last().unwrap();
+
+        Ok(self.outputs.iter().map(|&i| output_layer[i]).collect())
+    }
+}
+
+/// Assigns every wire needed at a level to a slot of that level, top-down from the
+/// outputs, so only gates that feed an output end up in the circuit.
+struct Layout<'a, F: PrimeField> {
+    builder: &'a CircuitBuilder<F>,
+    depths: &'a [usize],
+    slots: Vec<Vec<Slot>>,
+    positions: HashMap<(Wire, usize), usize>,
+    zeros: Vec<Option<usize>>,
+    input_zero: bool,
+    /// Where the user's inputs start on the input layer. Only known once the first
+    /// pass has settled `input_zero`, input positions read before then are not used.
+    inputs_offset: usize,
+}
+
+impl<F: PrimeField> Layout<'_, F> {
+    /// Position of `wire` in the layer at `level`, counted from the input layer.
+    fn position(&mut self, wire: Wire, level: usize) -> usize {
+        if level == 0 {
+            return self.input_position(wire);
+        }
+        if let Some(&position) = self.positions.get(&(wire, level)) {
+            return position;
+        }
+
+        let slot = if self.depths[wire.0] == level {
+            Slot::Gate(wire)
+        } else {
+            Slot::Relay(wire)
+        };
+        self.slots[level].push(slot);
+        let position = self.slots[level].len() - 1;
+        self.positions.insert((wire, level), position);
+
+        match self.builder.nodes[wire.0] {
+            Node::Gate { left, right, .. } if self.depths[wire.0] == level => {
+                self.position(left, level - 1);
+                self.position(right, level - 1);
+            }
+            _ => {
+                self.position(wire, level - 1);
+                self.zero(level - 1);
+            }
+        }
+
+        position
+    }
+
+    fn input_position(&self, wire: Wire) -> usize {
+        match self.builder.nodes[wire.0] {
+            Node::Input(i) => self.inputs_offset + i,
+            Node::Constant(_) => self.builder.nodes[..wire.0]
+                .iter()
+                .filter(|node| matches!(node, Node::Constant(_)))
+                .count(),
+            Node::Gate { .. } => unreachable!("Gates never sit on the input layer"),
+        }
+    }
+
+    /// Position of a slot known to hold zero at `level`, reserving one if needed.
+    fn zero(&mut self, level: usize) -> usize {
+        if level == 0 {
+            self.input_zero = true;
+            return self.builder.constants.len();
+        }
+        if let Some(position) = self.zeros[level] {
+            return position;
+        }
+
+        // a slot without a gate evaluates to zero
+        self.slots[level].push(Slot::Zero);
+        let position = self.slots[level].len() - 1;
+        self.zeros[level] = Some(position);
+
+        position
+    }
+
+    fn gates(&mut self, level: usize) -> Vec<Gate> {
+        let mut gates = vec![];
+
+        for (output, slot) in self.slots[level].clone().into_iter().enumerate() {
+            match slot {
+                Slot::Gate(wire) => {
+                    let Node::Gate { left, right, op } = self.builder.nodes[wire.0].clone() else {
+                        unreachable!()
+                    };
+                    let left = self.position(left, level - 1);
+                    let right = self.position(right, level - 1);
+                    gates.push(Gate::new(left, right, output, op));
+                }
+                Slot::Relay(wire) => {
+                    let left = self.position(wire, level - 1);
+                    let right = self.zero(level - 1);
+                    gates.push(Gate::new(left, right, output, Op::ADD));
+                }
+                Slot::Zero => {}
+            }
+        }
+
+        gates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::GKRProver;
+    use crate::verifier::GKRVerifier;
+    use ark_bn254::{Bn254, Fq, Fr};
+    use kzg::multilinear::{trusted_setup::TrustedSetup, verifier_key::VerifierKey};
+    use sha3::Keccak256;
+    use sumcheck::fiat_shamir::FiatShamir;
+
+    fn prove_and_verify(built: &mut BuiltCircuit<Fq>, inputs: &[Fq]) -> bool {
+        let input = built.input_layer(inputs).unwrap();
+        let mut transcript_p = FiatShamir::<Keccak256, Fq>::new();
+        let mut transcript_v = FiatShamir::<Keccak256, Fq>::new();
+
+        let proof = GKRProver::prove(&input, &mut built.circuit, &mut transcript_p).unwrap();
+        GKRVerifier::verify(&input, &mut built.circuit, &mut transcript_v, &proof).is_ok()
+    }
+
+    #[test]
+    fn test_builds_balanced_circuit() {
+        // the hand-built circuit from the protocol tests: (x0 + x1 + x2 * x3) + x4 * x5 * x6 * x7
+        let mut builder = CircuitBuilder::<Fq>::new();
+        let x = builder.inputs(8);
+        let a = builder.add(x[0], x[1]);
+        let b = builder.mul(x[2], x[3]);
+        let c = builder.mul(x[4], x[5]);
+        let d = builder.mul(x[6], x[7]);
+        let e = builder.add(a, b);
+        let f = builder.mul(c, d);
+        let g = builder.add(e, f);
+        builder.output(g);
+
+        let mut built = builder.build();
+        let inputs: Vec<Fq> = (1..=8).map(Fq::from).collect();
+
+        assert_eq!(built.input_len, 8);
+        assert_eq!(built.circuit.get_layer_count(), 3);
+        assert_eq!(built.evaluate(&inputs).unwrap(), vec![Fq::from(1695)]);
+        assert!(prove_and_verify(&mut built, &inputs));
+    }
+
+    #[test]
+    fn test_relays_wires_that_skip_layers() {
+        // x * y * z + x + 5, where x and the constant have to be carried up two layers
+        let mut builder = CircuitBuilder::<Fq>::new();
+        let x = builder.input();
+        let y = builder.input();
+        let z = builder.input();
+        let five = builder.constant(Fq::from(5));
+        let xy = builder.mul(x, y);
+        let xyz = builder.mul(xy, z);
+        let sum = builder.add(xyz, x);
+        let out = builder.add(sum, five);
+        builder.output(out);
+        builder.output(xy);
+
+        let mut built = builder.build();
+        let inputs = [Fq::from(2), Fq::from(3), Fq::from(4)];
+
+        assert_eq!(built.circuit.get_layer_count(), 4);
+        assert_eq!(
+            built.evaluate(&inputs).unwrap(),
+            vec![Fq::from(31), Fq::from(6)]
+        );
+        assert!(prove_and_verify(&mut built, &inputs));
+    }
+
+    // the constant and the relay zero are public, a prover committing to the input
+    // can't swap them for values of its choosing
+    #[test]
+    fn test_constants_are_public_under_kzg() {
+        let mut builder = CircuitBuilder::<Fr>::new();
+        let x = builder.input();
+        let y = builder.input();
+        let z = builder.input();
+        let five = builder.constant(Fr::from(5));
+        let xy = builder.mul(x, y);
+        let xyz = builder.mul(xy, z);
+        let sum = builder.add(xyz, x);
+        let out = builder.add(sum, five);
+        builder.output(out);
+
+        let mut built = builder.build();
+        assert_eq!(built.circuit.public_inputs(), &[Fr::from(5), Fr::from(0)]);
+
+        let setup = TrustedSetup::<Bn254, Fr>::new(
+            &(0..built.input_len.ilog2())
+                .map(|i| Fr::from(7 + i as u64))
+                .collect::<Vec<_>>(),
+        );
+        let verifier_key = VerifierKey::from_setup(&setup);
+        let input = built
+            .input_layer(&[Fr::from(2), Fr::from(3), Fr::from(4)])
+            .unwrap();
+
+        let proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut built.circuit,
+            &setup.encrypted_lagrange_basis,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+        assert!(GKRVerifier::verify_with_kzg(
+            &mut built.circuit,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+            &proof,
+            &verifier_key,
+        )
+        .is_ok());
+
+        let mut forged = input.clone();
+        forged[0] = Fr::from(6);
+        assert!(matches!(
+            GKRProver::prove_with_kzg::<Bn254, _>(
+                &forged,
+                &mut built.circuit,
+                &setup.encrypted_lagrange_basis,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+            ),
+            Err(Error::PublicInputMismatch { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_pads_input_for_wide_layers() {
+        // squaring every pair product needs a layer as wide as the input
+        let mut builder = CircuitBuilder::<Fq>::new();
+        let x = builder.inputs(4);
+        let products: Vec<Wire> = (0..4)
+            .flat_map(|i| (0..4).map(move |j| (i, j)))
+            .filter(|(i, j)| i < j)
+            .map(|(i, j)| builder.mul(x[i], x[j]))
+            .collect();
+        let mut acc = products[0];
+        for p in &products[1..] {
+            acc = builder.add(acc, *p);
+        }
+        builder.output(acc);
+
+        let mut built = builder.build();
+        let widths = built.circuit.layer_widths();
+        let inputs: Vec<Fq> = (1..=4).map(Fq::from).collect();
+
+        assert!(built.input_len.is_power_of_two());
+        assert!(widths[widths.len() - 2] >= 6);
+        assert_eq!(built.evaluate(&inputs).unwrap(), vec![Fq::from(35)]);
+        assert!(prove_and_verify(&mut built, &inputs));
+    }
+
+    #[test]
+    fn test_input_as_output() {
+        let mut builder = CircuitBuilder::<Fq>::new();
+        let x = builder.inputs(2);
+        builder.output(x[1]);
+
+        let mut built = builder.build();
+        let inputs = [Fq::from(9), Fq::from(10)];
+
+        assert_eq!(built.evaluate(&inputs).unwrap(), vec![Fq::from(10)]);
+        assert!(prove_and_verify(&mut built, &inputs));
+    }
+
+    #[test]
+    fn test_wrong_input_count() {
+        let mut builder = CircuitBuilder::<Fq>::new();
+        let x = builder.inputs(2);
+        let y = builder.add(x[0], x[1]);
+        builder.output(y);
+
+        assert!(matches!(
+            builder.build().input_layer(&[Fq::from(1)]),
+            Err(Error::InvalidInputLength {
+                expected: 2,
+                got: 1
+            })
+        ));
+    }
+}
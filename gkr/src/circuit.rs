@@ -1,18 +1,20 @@
+use crate::error::Error;
 use ark_ff::PrimeField;
 use polynomials::composed::{product_poly::ProductPoly, sum_poly::SumPoly};
 use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+use serde::{Deserialize, Serialize};
 use std::cmp::max;
-use std::marker::PhantomData;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Gate {
-    left: usize,
-    right: usize,
-    output: usize,
-    op: Op,
+    pub(crate) left: usize,
+    pub(crate) right: usize,
+    pub(crate) output: usize,
+    pub(crate) op: Op,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Op {
     ADD,
     MUL,
@@ -20,9 +22,9 @@ pub enum Op {
 
 #[derive(Debug)]
 pub struct Circuit<F: PrimeField> {
-    layers: Vec<Vec<Gate>>,
-    input_len: usize,
-    _phantom: PhantomData<F>,
+    pub(crate) layers: Vec<Vec<Gate>>,
+    pub(crate) input_len: usize,
+    pub(crate) public_inputs: Vec<F>,
 }
 
 impl Gate {
@@ -41,10 +43,39 @@ impl<F: PrimeField> Circuit<F> {
         Circuit {
             layers,
             input_len,
-            _phantom: PhantomData,
+            public_inputs: vec![],
         }
     }
 
+    /// Attaches the values of the first `public_inputs.len()` inputs, which are known
+    /// to both parties and stored alongside the circuit in its file formats. The
+    /// verifier rejects any input layer, or committed input, that starts otherwise.
+    pub fn with_public_inputs(mut self, public_inputs: Vec<F>) -> Self {
+        self.public_inputs = public_inputs;
+        self
+    }
+
+    pub fn public_inputs(&self) -> &[F] {
+        &self.public_inputs
+    }
+
+    /// Checks that `input_layer` starts with the public inputs.
+    pub fn check_public_inputs(&self, input_layer: &[F]) -> Result<(), Error> {
+        match self
+            .public_inputs
+            .iter()
+            .zip(input_layer)
+            .position(|(public, input)| public != input)
+        {
+            Some(index) => Err(Error::PublicInputMismatch { index }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn input_len(&self) -> usize {
+        self.input_len
+    }
+
     pub fn eval(&self, input_layer: Vec<F>) -> Vec<Vec<F>> {
         let mut layer_outputs = vec![input_layer];
 
@@ -72,26 +103,47 @@ impl<F: PrimeField> Circuit<F> {
         &self,
         layer_id: usize,
         input_layer: Vec<F>,
-    ) -> MultilinearPoly<F> {
+    ) -> Result<MultilinearPoly<F>, Error> {
+        if input_layer.len() != self.input_len {
+            return Err(Error::InvalidInputLength {
+                expected: self.input_len,
+                got: input_layer.len(),
+            });
+        }
+
+        // the layers and the input
+        let layer_count = self.layers.len() + 1;
+        if layer_id >= layer_count {
+            return Err(Error::LayerOutOfRange {
+                layer: layer_id,
+                layer_count,
+            });
+        }
+
         let evals: Vec<Vec<F>> = self.eval(input_layer).into_iter().rev().collect();
         let layer_eval = &evals[layer_id];
 
         let len = layer_eval.len();
-        assert!(len.is_power_of_two(), "Length must be a power of 2");
-        let n = (len as f64).log2() as u32;
+        if !len.is_power_of_two() {
+            return Err(Error::LayerNotPowerOfTwo {
+                layer: layer_id,
+                len,
+            });
+        }
 
-        MultilinearPoly::new(layer_eval.to_vec(), n.try_into().unwrap())
+        Ok(MultilinearPoly::new(
+            layer_eval.to_vec(),
+            len.ilog2() as usize,
+        ))
     }
 
     pub fn add_mul_i(&self, layer_id: usize, op: Op) -> MultilinearPoly<F> {
         let layer = &self.layers[layer_id];
-        
-        let l_i_vars = (layer.len() as f64).log2().ceil().max(1.0) as u32;
-        let l_i_plus_1_vars = if layer_id + 1 < self.layers.len() {
-            (self.layers[layer_id + 1].len() as f64).log2().ceil().max(1.0) as u32
-        } else {
-            (self.input_len as f64).log2().ceil().max(1.0) as u32
-        };
+        let widths = self.layer_widths();
+
+        // bits needed to index the outputs of this layer and the values of the next one
+        let l_i_vars = widths[layer_id].next_power_of_two().ilog2().max(1);
+        let l_i_plus_1_vars = widths[layer_id + 1].next_power_of_two().ilog2().max(1);
 
         // Calculate n_vars once (total bits = output + left + right)
         let n_vars = (l_i_vars + 2 * l_i_plus_1_vars) as usize;
@@ -108,7 +160,6 @@ impl<F: PrimeField> Circuit<F> {
             let combined_binary = format!("{}{}{}", output_binary, left_binary, right_binary);
             let eval_true_index: usize = usize::from_str_radix(&combined_binary, 2).unwrap();
 
-
             if gate.op == op {
                 evals[eval_true_index] = F::one();
             }
@@ -164,6 +215,19 @@ impl<F: PrimeField> Circuit<F> {
     pub fn get_layer_count(&self) -> usize {
         self.layers.len()
     }
+
+    /// Number of values in every layer as produced by [`Circuit::eval`], output layer
+    /// first and input layer last. Each layer is half as wide as the one it reads
+    /// from, but never narrower than two.
+    pub fn layer_widths(&self) -> Vec<usize> {
+        let mut widths = vec![self.input_len];
+        for _ in 0..self.layers.len() {
+            widths.push(max(widths.last().unwrap() / 2, 2));
+        }
+        widths.reverse();
+
+        widths
+    }
 }
 
 #[cfg(test)]
@@ -228,13 +292,34 @@ mod tests {
             Fr::from(8u64),
         ];
 
-        let layer_0_poly = circuit.get_layer_poly(0, input.clone());
-        let layer_1_poly = circuit.get_layer_poly(1, input.clone());
-        let layer_2_poly = circuit.get_layer_poly(2, input);
+        let layer_0_poly = circuit.get_layer_poly(0, input.clone()).unwrap();
+        let layer_1_poly = circuit.get_layer_poly(1, input.clone()).unwrap();
+        let layer_2_poly = circuit.get_layer_poly(2, input.clone()).unwrap();
 
         assert_eq!(layer_0_poly.evals.len(), 2);
         assert_eq!(layer_1_poly.evals.len(), 2);
         assert_eq!(layer_2_poly.evals.len(), 4);
+
+        assert!(matches!(
+            circuit.get_layer_poly(0, input[..4].to_vec()),
+            Err(Error::InvalidInputLength {
+                expected: 8,
+                got: 4
+            })
+        ));
+
+        // layer 3 is the input itself, there is nothing past it
+        assert_eq!(
+            circuit.get_layer_poly(3, input.clone()).unwrap().evals,
+            input
+        );
+        assert!(matches!(
+            circuit.get_layer_poly(4, input.clone()),
+            Err(Error::LayerOutOfRange {
+                layer: 4,
+                layer_count: 4
+            })
+        ));
     }
 
     #[test]
@@ -0,0 +1,476 @@
+//! On-disk formats for [`Circuit`].
+//!
+//! Both formats list the layers output first, exactly like [`Circuit::new`], and every
+//! gate as `op left right output`, where `left`/`right` index the layer below and
+//! `output` indexes the gate's own layer. Field elements are written in decimal.
+//!
+//! JSON:
+//!
+//! ```json
+//! {
+//!   "input_len": 4,
+//!   "public_inputs": ["1", "2"],
+//!   "layers": [
+//!     [{ "left": 0, "right": 1, "output": 0, "op": "add" }],
+//!     [{ "left": 0, "right": 1, "output": 0, "op": "mul" },
+//!      { "left": 2, "right": 3, "output": 1, "op": "mul" }]
+//!   ]
+//! }
+//! ```
+//!
+//! Text, one directive per line, with `#` starting a comment:
+//!
+//! ```text
+//! circuit 4
+//! public 1 2
+//! layer
+//! add 0 1 0
+//! layer
+//! mul 0 1 0
+//! mul 2 3 1
+//! ```
+//!
+//! `public_inputs` / `public` is optional and holds the values of the first inputs.
+use crate::circuit::{Circuit, Gate, Op};
+use ark_ff::PrimeField;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+/// Largest input layer a circuit may declare. Validation allocates per wire of a layer,
+/// so an unbounded length read from a file could exhaust memory before any gate is
+/// checked.
+pub const MAX_INPUT_LEN: usize = 1 << 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitFormat {
+    Json,
+    Text,
+}
+
+#[derive(Debug)]
+pub enum CircuitFileError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    InvalidFieldElement(String),
+    NoLayers,
+    InputLengthNotPowerOfTwo(usize),
+    InputLengthTooSmall(usize),
+    InputLengthTooLarge {
+        len: usize,
+        max: usize,
+    },
+    TooManyPublicInputs {
+        public: usize,
+        input_len: usize,
+    },
+    WireOutOfRange {
+        layer: usize,
+        gate: usize,
+        wire: usize,
+        width: usize,
+    },
+    DuplicateOutput {
+        layer: usize,
+        output: usize,
+    },
+}
+
+impl fmt::Display for CircuitFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Json(err) => write!(f, "invalid JSON: {err}"),
+            Self::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            Self::InvalidFieldElement(value) => write!(f, "invalid field element {value:?}"),
+            Self::NoLayers => write!(f, "circuit has no layers"),
+            Self::InputLengthNotPowerOfTwo(len) => {
+                write!(f, "input length {len} is not a power of two")
+            }
+            Self::InputLengthTooSmall(len) => {
+                write!(f, "input length {len} is below the minimum of 2")
+            }
+            Self::InputLengthTooLarge { len, max } => {
+                write!(f, "input length {len} is above the maximum of {max}")
+            }
+            Self::TooManyPublicInputs { public, input_len } => {
+                write!(f, "{public} public inputs for {input_len} inputs")
+            }
+            Self::WireOutOfRange {
+                layer,
+                gate,
+                wire,
+                width,
+            } => write!(
+                f,
+                "layer {layer}, gate {gate}: wire {wire} is out of range for width {width}"
+            ),
+            Self::DuplicateOutput { layer, output } => {
+                write!(
+                    f,
+                    "layer {layer}: output {output} is written by more than one gate"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitFileError {}
+
+impl From<io::Error> for CircuitFileError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CircuitFileError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CircuitJson {
+    input_len: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    public_inputs: Vec<String>,
+    layers: Vec<Vec<Gate>>,
+}
+
+impl<F: PrimeField> Circuit<F> {
+    /// Reads a circuit in the given format and rejects anything [`Circuit::validate`]
+    /// would.
+    pub fn from_reader<R: Read>(
+        reader: R,
+        format: CircuitFormat,
+    ) -> Result<Self, CircuitFileError> {
+        let circuit = match format {
+            CircuitFormat::Json => {
+                let json: CircuitJson = serde_json::from_reader(reader)?;
+                let public_inputs = parse_field_elements(&json.public_inputs)?;
+                Circuit::new(json.layers, json.input_len).with_public_inputs(public_inputs)
+            }
+            CircuitFormat::Text => parse_text(reader)?,
+        };
+        circuit.validate()?;
+
+        Ok(circuit)
+    }
+
+    pub fn to_writer<W: Write>(
+        &self,
+        mut writer: W,
+        format: CircuitFormat,
+    ) -> Result<(), CircuitFileError> {
+        match format {
+            CircuitFormat::Json => {
+                let json = CircuitJson {
+                    input_len: self.input_len,
+                    public_inputs: self.public_inputs.iter().map(|v| v.to_string()).collect(),
+                    layers: self.layers.clone(),
+                };
+                serde_json::to_writer_pretty(&mut writer, &json)?;
+                writeln!(writer)?;
+            }
+            CircuitFormat::Text => {
+                writeln!(writer, "circuit {}", self.input_len)?;
+                if !self.public_inputs.is_empty() {
+                    let values: Vec<String> =
+                        self.public_inputs.iter().map(|v| v.to_string()).collect();
+                    writeln!(writer, "public {}", values.join(" "))?;
+                }
+                for layer in &self.layers {
+                    writeln!(writer, "layer")?;
+                    for gate in layer {
+                        let op = match gate.op {
+                            Op::ADD => "add",
+                            Op::MUL => "mul",
+                        };
+                        writeln!(writer, "{op} {} {} {}", gate.left, gate.right, gate.output)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the input length is a power of two between 2 and [`MAX_INPUT_LEN`],
+    /// so every layer width is too, that every wire indexes into its layer and that no
+    /// two gates of a layer write the same output.
+    ///
+    /// A single input would make the input polynomial a constant, with no variable left
+    /// for the last layer's sumcheck to bind.
+    pub fn validate(&self) -> Result<(), CircuitFileError> {
+        if self.layers.is_empty() {
+            return Err(CircuitFileError::NoLayers);
+        }
+        if self.input_len > MAX_INPUT_LEN {
+            return Err(CircuitFileError::InputLengthTooLarge {
+                len: self.input_len,
+                max: MAX_INPUT_LEN,
+            });
+        }
+        if self.input_len < 2 {
+            return Err(CircuitFileError::InputLengthTooSmall(self.input_len));
+        }
+        if !self.input_len.is_power_of_two() {
+            return Err(CircuitFileError::InputLengthNotPowerOfTwo(self.input_len));
+        }
+        if self.public_inputs.len() > self.input_len {
+            return Err(CircuitFileError::TooManyPublicInputs {
+                public: self.public_inputs.len(),
+                input_len: self.input_len,
+            });
+        }
+
+        let widths = self.layer_widths();
+        for (layer_id, layer) in self.layers.iter().enumerate() {
+            let mut written = vec![false; widths[layer_id]];
+
+            for (gate_id, gate) in layer.iter().enumerate() {
+                let checks = [
+                    (gate.left, widths[layer_id + 1]),
+                    (gate.right, widths[layer_id + 1]),
+                    (gate.output, widths[layer_id]),
+                ];
+                for (wire, width) in checks {
+                    if wire >= width {
+                        return Err(CircuitFileError::WireOutOfRange {
+                            layer: layer_id,
+                            gate: gate_id,
+                            wire,
+                            width,
+                        });
+                    }
+                }
+
+                if written[gate.output] {
+                    return Err(CircuitFileError::DuplicateOutput {
+                        layer: layer_id,
+                        output: gate.output,
+                    });
+                }
+                written[gate.output] = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_field_elements<F: PrimeField, S: AsRef<str>>(
+    values: &[S],
+) -> Result<Vec<F>, CircuitFileError> {
+    values
+        .iter()
+        .map(|value| {
+            F::from_str(value.as_ref())
+                .map_err(|_| CircuitFileError::InvalidFieldElement(value.as_ref().to_string()))
+        })
+        .collect()
+}
+
+fn parse_text<F: PrimeField, R: Read>(reader: R) -> Result<Circuit<F>, CircuitFileError> {
+    let mut input_len = None;
+    let mut public_inputs = vec![];
+    let mut layers: Vec<Vec<Gate>> = vec![];
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let syntax = |message: &str| CircuitFileError::Syntax {
+            line: i + 1,
+            message: message.to_string(),
+        };
+        let parse_index = |token: &str| {
+            token
+                .parse::<usize>()
+                .map_err(|_| syntax("expected a wire index"))
+        };
+
+        let tokens: Vec<&str> = line.split('#').next().unwrap().split_whitespace().collect();
+        match tokens.as_slice() {
+            [] => {}
+            ["circuit", len] if input_len.is_none() => {
+                input_len = Some(
+                    len.parse()
+                        .map_err(|_| syntax("expected an input length"))?,
+                );
+            }
+            _ if input_len.is_none() => return Err(syntax("expected `circuit <input_len>` first")),
+            ["public", values @ ..] if layers.is_empty() => {
+                public_inputs = parse_field_elements(values)?;
+            }
+            ["layer"] => layers.push(vec![]),
+            [op @ ("add" | "mul"), left, right, output] => {
+                let op = if *op == "add" { Op::ADD } else { Op::MUL };
+                let gate = Gate::new(
+                    parse_index(left)?,
+                    parse_index(right)?,
+                    parse_index(output)?,
+                    op,
+                );
+                layers
+                    .last_mut()
+                    .ok_or_else(|| syntax("gate outside of a layer"))?
+                    .push(gate);
+            }
+            _ => return Err(syntax(&format!("unexpected `{}`", line.trim()))),
+        }
+    }
+
+    let input_len = input_len.ok_or(CircuitFileError::Syntax {
+        line: 0,
+        message: "missing `circuit <input_len>`".to_string(),
+    })?;
+
+    Ok(Circuit::new(layers, input_len).with_public_inputs(public_inputs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    fn sample_circuit() -> Circuit<Fr> {
+        let layer_1 = vec![Gate::new(0, 1, 0, Op::MUL), Gate::new(2, 3, 1, Op::MUL)];
+        let layer_0 = vec![Gate::new(0, 1, 0, Op::ADD)];
+
+        Circuit::new(vec![layer_0, layer_1], 4).with_public_inputs(vec![Fr::from(1), Fr::from(2)])
+    }
+
+    fn round_trip(circuit: &Circuit<Fr>, format: CircuitFormat) -> Circuit<Fr> {
+        let mut bytes = vec![];
+        circuit.to_writer(&mut bytes, format).unwrap();
+
+        Circuit::from_reader(bytes.as_slice(), format).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let circuit = sample_circuit();
+        let input = vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)];
+
+        for format in [CircuitFormat::Json, CircuitFormat::Text] {
+            let read = round_trip(&circuit, format);
+
+            assert_eq!(read.layers, circuit.layers);
+            assert_eq!(read.input_len(), 4);
+            assert_eq!(read.public_inputs(), circuit.public_inputs());
+            assert_eq!(read.eval(input.clone()), circuit.eval(input.clone()));
+        }
+    }
+
+    #[test]
+    fn test_parse_text() {
+        let text = "# (x0 * x1) + (x2 * x3)\ncircuit 4\n\nlayer\nadd 0 1 0\nlayer  # products\nmul 0 1 0\nmul 2 3 1\n";
+        let circuit = Circuit::<Fr>::from_reader(text.as_bytes(), CircuitFormat::Text).unwrap();
+
+        assert_eq!(circuit.layers, sample_circuit().layers);
+        assert!(circuit.public_inputs().is_empty());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"{"input_len": 4, "public_inputs": ["1", "2"], "layers": [
+            [{"left": 0, "right": 1, "output": 0, "op": "add"}],
+            [{"left": 0, "right": 1, "output": 0, "op": "mul"}, {"left": 2, "right": 3, "output": 1, "op": "mul"}]
+        ]}"#;
+        let circuit = Circuit::<Fr>::from_reader(json.as_bytes(), CircuitFormat::Json).unwrap();
+
+        assert_eq!(circuit.layers, sample_circuit().layers);
+        assert_eq!(circuit.public_inputs(), sample_circuit().public_inputs());
+    }
+
+    #[test]
+    fn test_rejects_invalid_circuits() {
+        let read = |text: &str| Circuit::<Fr>::from_reader(text.as_bytes(), CircuitFormat::Text);
+
+        assert!(matches!(
+            read("circuit 4\nlayer\nadd 0 1 0\nlayer\nmul 0 4 0\n"),
+            Err(CircuitFileError::WireOutOfRange {
+                layer: 1,
+                gate: 0,
+                wire: 4,
+                width: 4
+            })
+        ));
+        assert!(matches!(
+            read("circuit 4\nlayer\nadd 0 1 2\n"),
+            Err(CircuitFileError::WireOutOfRange {
+                layer: 0,
+                wire: 2,
+                width: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            read("circuit 4\nlayer\nadd 0 1 0\nmul 2 3 0\n"),
+            Err(CircuitFileError::DuplicateOutput {
+                layer: 0,
+                output: 0
+            })
+        ));
+        assert!(matches!(
+            read("circuit 6\nlayer\nadd 0 1 0\n"),
+            Err(CircuitFileError::InputLengthNotPowerOfTwo(6))
+        ));
+        assert!(matches!(
+            read("circuit 4\n"),
+            Err(CircuitFileError::NoLayers)
+        ));
+        assert!(matches!(
+            read("circuit 1\nlayer\nadd 0 0 0\n"),
+            Err(CircuitFileError::InputLengthTooSmall(1))
+        ));
+        assert!(matches!(
+            read("circuit 0\nlayer\n"),
+            Err(CircuitFileError::InputLengthTooSmall(0))
+        ));
+        // a tiny file must not make validation allocate a width of 2^60 wires
+        assert!(matches!(
+            read("circuit 1152921504606846976\nlayer\n"),
+            Err(CircuitFileError::InputLengthTooLarge {
+                len: 1152921504606846976,
+                max: MAX_INPUT_LEN
+            })
+        ));
+        assert!(matches!(
+            read("circuit 2\npublic 1 2 3\nlayer\nadd 0 1 0\n"),
+            Err(CircuitFileError::TooManyPublicInputs {
+                public: 3,
+                input_len: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_malformed_files() {
+        let read = |text: &str| Circuit::<Fr>::from_reader(text.as_bytes(), CircuitFormat::Text);
+
+        assert!(matches!(
+            read("layer\n"),
+            Err(CircuitFileError::Syntax { line: 1, .. })
+        ));
+        assert!(matches!(
+            read("circuit 4\nadd 0 1 0\n"),
+            Err(CircuitFileError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            read("circuit 4\nlayer\nsub 0 1 0\n"),
+            Err(CircuitFileError::Syntax { line: 3, .. })
+        ));
+        assert!(matches!(
+            read("circuit 4\npublic x\nlayer\nadd 0 1 0\n"),
+            Err(CircuitFileError::InvalidFieldElement(_))
+        ));
+        assert!(matches!(
+            Circuit::<Fr>::from_reader(&b"{\"layers\": []}"[..], CircuitFormat::Json),
+            Err(CircuitFileError::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::circuit_file::CircuitFileError;
+use std::fmt;
+
+/// Why a GKR proof could not be produced or was rejected.
+#[derive(Debug)]
+pub enum Error {
+    /// The circuit failed [`crate::circuit::Circuit::validate`].
+    InvalidCircuit(CircuitFileError),
+    /// The input layer does not match the circuit's input length.
+    InvalidInputLength { expected: usize, got: usize },
+    /// There is no layer `layer` in a circuit of `layer_count` layers and an input.
+    LayerOutOfRange { layer: usize, layer_count: usize },
+    /// Input `index` differs from the circuit's public input at that position.
+    PublicInputMismatch { index: usize },
+    /// A layer evaluates to a number of values that is not a power of two.
+    LayerNotPowerOfTwo { layer: usize, len: usize },
+    /// Some part of the proof does not have the shape the circuit requires.
+    InvalidProofLength { expected: usize, got: usize },
+    /// The sumcheck of `layer` does not start from the claim the previous layer left.
+    InitialClaimMismatch { layer: usize },
+    /// A round of the sumcheck of `layer` was rejected.
+    Sumcheck {
+        layer: usize,
+        source: sumcheck::error::Error,
+    },
+    /// The sumcheck of `layer` ends on a claim the gate polynomials disagree with.
+    FinalEvalMismatch { layer: usize },
+    /// The claimed outputs are not what the circuit computes on the input.
+    OutputMismatch,
+    /// The KZG opening of the input layer was rejected.
+    Kzg(kzg::error::Error),
+    /// A polynomial, e.g. the claimed output layer, could not be evaluated.
+    Poly(polynomials::error::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCircuit(err) => write!(f, "invalid circuit: {err}"),
+            Self::InvalidInputLength { expected, got } => {
+                write!(f, "expected {expected} inputs, got {got}")
+            }
+            Self::LayerOutOfRange { layer, layer_count } => {
+                write!(f, "no layer {layer} in a circuit of {layer_count} layers")
+            }
+            Self::PublicInputMismatch { index } => {
+                write!(f, "input {index} differs from the public input")
+            }
+            Self::LayerNotPowerOfTwo { layer, len } => {
+                write!(f, "layer {layer} has {len} values, not a power of two")
+            }
+            Self::InvalidProofLength { expected, got } => {
+                write!(f, "expected {expected} proof elements, got {got}")
+            }
+            Self::InitialClaimMismatch { layer } => {
+                write!(f, "layer {layer}: initial claim does not match")
+            }
+            Self::Sumcheck { layer, source } => write!(f, "layer {layer}: {source}"),
+            Self::FinalEvalMismatch { layer } => {
+                write!(f, "layer {layer}: final claim does not match the gates")
+            }
+            Self::OutputMismatch => write!(f, "output layer does not match the circuit"),
+            Self::Kzg(err) => write!(f, "input opening rejected: {err}"),
+            Self::Poly(err) => write!(f, "polynomial evaluation failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidCircuit(err) => Some(err),
+            Self::Sumcheck { source, .. } => Some(source),
+            Self::Kzg(err) => Some(err),
+            Self::Poly(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<CircuitFileError> for Error {
+    fn from(err: CircuitFileError) -> Self {
+        Self::InvalidCircuit(err)
+    }
+}
+
+impl From<kzg::error::Error> for Error {
+    fn from(err: kzg::error::Error) -> Self {
+        Self::Kzg(err)
+    }
+}
+
+impl From<polynomials::error::Error> for Error {
+    fn from(err: polynomials::error::Error) -> Self {
+        Self::Poly(err)
+    }
+}
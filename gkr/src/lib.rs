@@ -1,4 +1,7 @@
+pub mod builder;
 pub mod circuit;
+pub mod circuit_file;
+pub mod error;
 pub mod protocol;
 pub mod prover;
 pub mod utils;
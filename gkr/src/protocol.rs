@@ -1,23 +1,36 @@
 use ark_ec::pairing::Pairing;
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use kzg::multilinear::protocol::MultilinearKZGBatchProof;
 use polynomials::multilinear::multilinear_poly::MultilinearPoly;
-use sha3::Keccak256;
-use std::marker::PhantomData;
-use sumcheck::fiat_shamir::FiatShamir;
 use sumcheck::sumcheck_protocol::PartialProof;
-use crate::prover::GKRProver;
-use crate::verifier::GKRVerifier;
-use crate::circuit::{Circuit, Op, Gate};
 
+/// Leading byte of every serialized proof, bumped whenever the encoding changes.
+const PROOF_VERSION: u8 = 1;
+
+/// Opens every GKR proof on the transcript.
+pub(crate) const DOMAIN_SEPARATOR: &[u8] = b"gkr";
+/// Opens a GKR proof against a committed input, ahead of [`DOMAIN_SEPARATOR`].
+pub(crate) const KZG_DOMAIN_SEPARATOR: &[u8] = b"gkr-kzg";
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct GKRProof<F: PrimeField> {
     pub output_poly: MultilinearPoly<F>,
     pub w_poly_evals: Vec<(F, F)>,
     pub sumcheck_proofs: Vec<PartialProof<F>>,
 }
 
+/// GKR proof against a KZG commitment to the input layer. `input_opening` opens the
+/// input at the two points the last sumcheck reduces to, its evaluations replace the
+/// values the plain verifier would compute from the witness.
+#[derive(Clone, Debug, PartialEq)]
 pub struct GKRProofWithKZG<F: PrimeField, P: Pairing> {
-    _marker: PhantomData<F>,
-    _marker_p: PhantomData<P>,
+    pub proof: GKRProof<F>,
+    pub input_commitment: P::G1,
+    pub input_opening: MultilinearKZGBatchProof<F, P>,
 }
 
 impl<F: PrimeField> GKRProof<F> {
@@ -34,12 +47,196 @@ impl<F: PrimeField> GKRProof<F> {
     }
 }
 
+impl<F: PrimeField, P: Pairing> GKRProofWithKZG<F, P> {
+    pub fn new(
+        proof: GKRProof<F>,
+        input_commitment: P::G1,
+        input_opening: MultilinearKZGBatchProof<F, P>,
+    ) -> Self {
+        Self {
+            proof,
+            input_commitment,
+            input_opening,
+        }
+    }
+}
+
+// The version byte leads the outermost proof only, a GKRProof nested in a
+// GKRProofWithKZG is written without one.
+impl<F: PrimeField> GKRProof<F> {
+    fn serialize_fields<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.output_poly
+            .n_vars
+            .serialize_with_mode(&mut writer, compress)?;
+        self.output_poly
+            .evals
+            .serialize_with_mode(&mut writer, compress)?;
+        self.w_poly_evals
+            .serialize_with_mode(&mut writer, compress)?;
+        self.sumcheck_proofs
+            .serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn fields_size(&self, compress: Compress) -> usize {
+        self.output_poly.n_vars.serialized_size(compress)
+            + self.output_poly.evals.serialized_size(compress)
+            + self.w_poly_evals.serialized_size(compress)
+            + self.sumcheck_proofs.serialized_size(compress)
+    }
+
+    fn deserialize_fields<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let n_vars = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let evals = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        Ok(Self {
+            output_poly: MultilinearPoly::new(evals, n_vars),
+            w_poly_evals: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            sumcheck_proofs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<F: PrimeField> CanonicalSerialize for GKRProof<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.serialize_fields(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress) + self.fields_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for GKRProof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        // the output polynomial is the only part of the proof the verifier takes as is
+        if self.output_poly.n_vars >= usize::BITS as usize
+            || self.output_poly.evals.len() != 1 << self.output_poly.n_vars
+        {
+            return Err(SerializationError::InvalidData);
+        }
+        self.w_poly_evals.check()?;
+        self.sumcheck_proofs.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for GKRProof<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let proof = Self::deserialize_fields(&mut reader, compress, validate)?;
+        if validate == Validate::Yes {
+            proof.check()?;
+        }
+
+        Ok(proof)
+    }
+}
+
+impl<F: PrimeField, P: Pairing> CanonicalSerialize for GKRProofWithKZG<F, P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.proof.serialize_fields(&mut writer, compress)?;
+        self.input_commitment
+            .serialize_with_mode(&mut writer, compress)?;
+        self.input_opening
+            .serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.proof.fields_size(compress)
+            + self.input_commitment.serialized_size(compress)
+            + self.input_opening.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField, P: Pairing> Valid for GKRProofWithKZG<F, P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.proof.check()?;
+        self.input_commitment.check()?;
+        self.input_opening.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, P: Pairing> CanonicalDeserialize for GKRProofWithKZG<F, P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let proof = Self {
+            proof: GKRProof::deserialize_fields(&mut reader, compress, validate)?,
+            input_commitment: P::G1::deserialize_with_mode(&mut reader, compress, validate)?,
+            input_opening: MultilinearKZGBatchProof::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+        };
+        if validate == Validate::Yes {
+            proof.check()?;
+        }
+
+        Ok(proof)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_bn254::Fq;
+    use crate::circuit::{Circuit, Gate, Op};
+    use crate::circuit_file::CircuitFileError;
+    use crate::error::Error;
+    use crate::prover::GKRProver;
+    use crate::verifier::GKRVerifier;
+    use ark_bn254::{Bn254, Fq, Fr};
+    use ark_ec::PrimeGroup;
+    use kzg::multilinear::{trusted_setup::TrustedSetup, verifier_key::VerifierKey};
+    use sha3::Keccak256;
+    use std::str::FromStr;
+    use sumcheck::{
+        fiat_shamir::FiatShamir,
+        transcript::{
+            Blake3Transcript, KeccakTranscript, PoseidonTranscript, Sha256Transcript, Transcript,
+        },
+    };
 
-    fn init_circuit() -> (Circuit<Fq>, Vec<Fq>) {
+    fn init_circuit<F: PrimeField>() -> (Circuit<F>, Vec<F>) {
         let gate_a = Gate::new(0, 1, 0, Op::ADD);
         let gate_b = Gate::new(2, 3, 1, Op::MUL);
         let gate_c = Gate::new(4, 5, 2, Op::MUL);
@@ -52,17 +249,17 @@ mod tests {
         let layer_1 = vec![gate_e, gate_f];
         let layer_0 = vec![gate_g];
 
-        let circuit = Circuit::<Fq>::new(vec![layer_0, layer_1, layer_2], 8);
+        let circuit = Circuit::<F>::new(vec![layer_0, layer_1, layer_2], 8);
 
         let input = vec![
-            Fq::from(1u64),
-            Fq::from(2u64),
-            Fq::from(3u64),
-            Fq::from(4u64),
-            Fq::from(5u64),
-            Fq::from(6u64),
-            Fq::from(7u64),
-            Fq::from(8u64),
+            F::from(1u64),
+            F::from(2u64),
+            F::from(3u64),
+            F::from(4u64),
+            F::from(5u64),
+            F::from(6u64),
+            F::from(7u64),
+            F::from(8u64),
         ];
 
         (circuit, input)
@@ -74,41 +271,603 @@ mod tests {
         let mut transcript_p = FiatShamir::<Keccak256, Fq>::new();
         let mut transcript_v = FiatShamir::<Keccak256, Fq>::new();
 
-        let gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p);
+        let gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p).unwrap();
         let is_verified = GKRVerifier::verify(&input, &mut circuit, &mut transcript_v, &gkr_proof);
 
-        assert!(is_verified);
+        assert!(is_verified.is_ok());
+
+        // both sides absorbed the same labelled messages, so they finish in sync
+        assert_eq!(
+            transcript_p.challenge_scalar(b"next"),
+            transcript_v.challenge_scalar(b"next")
+        );
+    }
+
+    #[test]
+    fn test_gkr_on_every_transcript() {
+        fn check<T: Transcript<Fr>>(transcript: fn() -> T) {
+            let (mut circuit, input) = init_circuit::<Fr>();
+            let mut transcript_p = transcript();
+            let mut transcript_v = transcript();
+
+            let gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p).unwrap();
+            let is_verified =
+                GKRVerifier::verify(&input, &mut circuit, &mut transcript_v, &gkr_proof);
+
+            assert!(is_verified.is_ok());
+            assert_eq!(
+                transcript_p.challenge_scalar(b"next"),
+                transcript_v.challenge_scalar(b"next")
+            );
+        }
+
+        check(KeccakTranscript::<Fr>::new);
+        check(Sha256Transcript::<Fr>::new);
+        check(Blake3Transcript::<Fr>::new);
+        check(PoseidonTranscript::<Fr>::new);
     }
-    
+
+    // Pinned on the sequential build, so `--features parallel` has to produce the
+    // same transcript byte for byte.
+    #[test]
+    fn test_gkr_transcript_is_pinned() {
+        let (mut circuit, input) = init_circuit();
+        let mut transcript = FiatShamir::<Keccak256, Fq>::new();
+
+        GKRProver::prove(&input, &mut circuit, &mut transcript).unwrap();
+
+        assert_eq!(
+            transcript.challenge_scalar(b"next"),
+            Fq::from_str(
+                "13332767363139811442473572340628260082775870450517045054192422135706106809957"
+            )
+            .unwrap()
+        );
+    }
+
     #[test]
     fn test_malicious_prover_wrong_output() {
         let (mut circuit, input) = init_circuit();
         let mut transcript_p = FiatShamir::<Keccak256, Fq>::new();
         let mut transcript_v = FiatShamir::<Keccak256, Fq>::new();
 
-        let mut gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p);
-        
+        let mut gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p).unwrap();
+
         // Malicious prover tampers with the output polynomial
         gkr_proof.output_poly.evals[0] = Fq::from(999u64);
-        
+
         let is_verified = GKRVerifier::verify(&input, &mut circuit, &mut transcript_v, &gkr_proof);
-        
-        assert!(!is_verified);
+
+        assert!(matches!(
+            is_verified,
+            Err(Error::InitialClaimMismatch { layer: 0 })
+        ));
     }
-    
+
     #[test]
     fn test_malicious_prover_wrong_initial_claim() {
         let (mut circuit, input) = init_circuit();
         let mut transcript_p = FiatShamir::<Keccak256, Fq>::new();
         let mut transcript_v = FiatShamir::<Keccak256, Fq>::new();
 
-        let mut gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p);
-        
+        let mut gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p).unwrap();
+
         // Malicious prover tampers with the initial claim of the first sumcheck
         gkr_proof.sumcheck_proofs[0].initial_claimed_sum = Fq::from(999u64);
-        
+
         let is_verified = GKRVerifier::verify(&input, &mut circuit, &mut transcript_v, &gkr_proof);
-        
-        assert!(!is_verified);
+
+        assert!(matches!(
+            is_verified,
+            Err(Error::InitialClaimMismatch { layer: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_malformed_proof_is_rejected() {
+        let (mut circuit, input) = init_circuit();
+        let mut transcript_p = FiatShamir::<Keccak256, Fq>::new();
+
+        let mut gkr_proof = GKRProver::prove(&input, &mut circuit, &mut transcript_p).unwrap();
+        gkr_proof.sumcheck_proofs[1].round_polys.pop();
+
+        let is_verified = GKRVerifier::verify(
+            &input,
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fq>::new(),
+            &gkr_proof,
+        );
+        assert!(matches!(
+            is_verified,
+            Err(Error::InvalidProofLength {
+                expected: 4,
+                got: 3
+            })
+        ));
+
+        gkr_proof.sumcheck_proofs.pop();
+        let is_verified = GKRVerifier::verify(
+            &input,
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fq>::new(),
+            &gkr_proof,
+        );
+        assert!(matches!(
+            is_verified,
+            Err(Error::InvalidProofLength {
+                expected: 3,
+                got: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_wrong_input_length() {
+        let (mut circuit, input) = init_circuit::<Fq>();
+
+        let result = GKRProver::prove(
+            &input[..4],
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fq>::new(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::InvalidInputLength {
+                expected: 8,
+                got: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_one_input_circuit_is_rejected() {
+        let mut circuit = Circuit::<Fq>::new(vec![vec![Gate::new(0, 0, 0, Op::ADD)]], 1);
+        let input = vec![Fq::from(3u64)];
+
+        assert!(matches!(
+            GKRProver::prove(
+                &input,
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fq>::new()
+            ),
+            Err(Error::InvalidCircuit(
+                CircuitFileError::InputLengthTooSmall(1)
+            ))
+        ));
+
+        let proof = GKRProof::new(
+            MultilinearPoly::new(vec![Fq::from(3u64)], 0),
+            Vec::new(),
+            Vec::new(),
+        );
+        assert!(matches!(
+            GKRVerifier::verify(
+                &input,
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fq>::new(),
+                &proof
+            ),
+            Err(Error::InvalidCircuit(
+                CircuitFileError::InputLengthTooSmall(1)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_public_inputs_are_enforced() {
+        let (circuit, input) = init_circuit::<Fq>();
+        let mut circuit = circuit.with_public_inputs(input[..2].to_vec());
+
+        let proof = GKRProver::prove(
+            &input,
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fq>::new(),
+        )
+        .unwrap();
+        assert!(GKRVerifier::verify(
+            &input,
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fq>::new(),
+            &proof
+        )
+        .is_ok());
+
+        let mut other_input = input.clone();
+        other_input[1] += Fq::from(1u64);
+        assert!(matches!(
+            GKRVerifier::verify(
+                &other_input,
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fq>::new(),
+                &proof
+            ),
+            Err(Error::PublicInputMismatch { index: 1 })
+        ));
+        assert!(matches!(
+            GKRProver::prove(
+                &other_input,
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fq>::new()
+            ),
+            Err(Error::PublicInputMismatch { index: 1 })
+        ));
+    }
+
+    fn kzg_setup() -> TrustedSetup<Bn254, Fr> {
+        TrustedSetup::new(&[Fr::from(11), Fr::from(13), Fr::from(17)])
+    }
+
+    #[test]
+    fn test_gkr_with_kzg() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+        let mut transcript_p = FiatShamir::<Keccak256, Fr>::new();
+        let mut transcript_v = FiatShamir::<Keccak256, Fr>::new();
+
+        let gkr_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut transcript_p,
+        )
+        .unwrap();
+
+        // the verifier only gets the circuit, the proof and the verifier key
+        let is_verified = GKRVerifier::verify_with_kzg(
+            &mut circuit,
+            &mut transcript_v,
+            &gkr_proof,
+            &verifier_key,
+        );
+
+        assert!(is_verified.is_ok());
+        assert_eq!(
+            transcript_p.challenge_scalar(b"next"),
+            transcript_v.challenge_scalar(b"next")
+        );
+    }
+
+    #[test]
+    fn test_gkr_with_kzg_on_poseidon_transcript() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+
+        let gkr_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut PoseidonTranscript::new(),
+        )
+        .unwrap();
+
+        let is_verified = GKRVerifier::verify_with_kzg(
+            &mut circuit,
+            &mut PoseidonTranscript::new(),
+            &gkr_proof,
+            &verifier_key,
+        );
+        assert!(is_verified.is_ok());
+
+        // a Keccak verifier draws other challenges and rejects it
+        let is_verified = GKRVerifier::verify_with_kzg(
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+            &gkr_proof,
+            &verifier_key,
+        );
+        assert!(is_verified.is_err());
+    }
+
+    #[test]
+    fn test_proof_serialization_round_trip() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+
+        let gkr_proof = GKRProver::prove(
+            &input,
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+        let kzg_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            gkr_proof.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), gkr_proof.serialized_size(compress));
+
+            let decoded =
+                GKRProof::<Fr>::deserialize_with_mode(bytes.as_slice(), compress, Validate::Yes)
+                    .unwrap();
+            assert_eq!(decoded, gkr_proof);
+            assert!(GKRVerifier::verify(
+                &input,
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+                &decoded
+            )
+            .is_ok());
+
+            let mut bytes = Vec::new();
+            kzg_proof.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), kzg_proof.serialized_size(compress));
+            // one version byte, the nested proof is written without its own
+            assert_eq!(
+                bytes.len(),
+                kzg_proof.proof.serialized_size(compress)
+                    + kzg_proof.input_commitment.serialized_size(compress)
+                    + kzg_proof.input_opening.serialized_size(compress)
+            );
+
+            let decoded = GKRProofWithKZG::<Fr, Bn254>::deserialize_with_mode(
+                bytes.as_slice(),
+                compress,
+                Validate::Yes,
+            )
+            .unwrap();
+            assert_eq!(decoded, kzg_proof);
+            assert!(GKRVerifier::verify_with_kzg(
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+                &decoded,
+                &verifier_key,
+            )
+            .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_proof_serialization_rejects_bad_encodings() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let mut gkr_proof = GKRProver::prove(
+            &input,
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+
+        let mut bytes = Vec::new();
+        gkr_proof.serialize_compressed(&mut bytes).unwrap();
+        bytes[0] = PROOF_VERSION + 1;
+        assert!(matches!(
+            GKRProof::<Fr>::deserialize_compressed(bytes.as_slice()),
+            Err(SerializationError::InvalidData)
+        ));
+
+        // an output polynomial whose size disagrees with its number of variables
+        gkr_proof.output_poly.n_vars += 1;
+        let mut bytes = Vec::new();
+        gkr_proof.serialize_compressed(&mut bytes).unwrap();
+        assert!(matches!(
+            GKRProof::<Fr>::deserialize_compressed(bytes.as_slice()),
+            Err(SerializationError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_gkr_with_kzg_wrong_input_evaluation() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+        let mut transcript_p = FiatShamir::<Keccak256, Fr>::new();
+        let mut transcript_v = FiatShamir::<Keccak256, Fr>::new();
+
+        let mut gkr_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut transcript_p,
+        )
+        .unwrap();
+
+        gkr_proof.input_opening.evaluations[0][0] += Fr::from(1u64);
+
+        let is_verified = GKRVerifier::verify_with_kzg(
+            &mut circuit,
+            &mut transcript_v,
+            &gkr_proof,
+            &verifier_key,
+        );
+
+        assert!(matches!(
+            is_verified,
+            Err(Error::FinalEvalMismatch { layer: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_gkr_with_kzg_wrong_input_commitment() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+        let mut transcript_p = FiatShamir::<Keccak256, Fr>::new();
+        let mut transcript_v = FiatShamir::<Keccak256, Fr>::new();
+
+        // commit to a different witness than the one the circuit was run on
+        let (_, other_input) = init_circuit::<Fr>();
+        let other_input: Vec<Fr> = other_input.iter().map(|x| *x + Fr::from(1u64)).collect();
+        let other_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &other_input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+
+        let mut gkr_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut transcript_p,
+        )
+        .unwrap();
+        gkr_proof.input_commitment = other_proof.input_commitment;
+
+        let is_verified = GKRVerifier::verify_with_kzg(
+            &mut circuit,
+            &mut transcript_v,
+            &gkr_proof,
+            &verifier_key,
+        );
+
+        assert!(matches!(
+            is_verified,
+            Err(Error::InitialClaimMismatch { layer: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_gkr_with_kzg_public_inputs() {
+        let (circuit, input) = init_circuit::<Fr>();
+        let mut circuit = circuit.with_public_inputs(input[..3].to_vec());
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+
+        let gkr_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+        assert_eq!(gkr_proof.input_opening.evaluations.len(), 2 + 3);
+        assert!(GKRVerifier::verify_with_kzg(
+            &mut circuit,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+            &gkr_proof,
+            &verifier_key,
+        )
+        .is_ok());
+
+        // the opening claims a value the public input disagrees with
+        let mut tampered = gkr_proof.clone();
+        tampered.input_opening.evaluations[4][0] += Fr::from(1u64);
+        assert!(matches!(
+            GKRVerifier::verify_with_kzg(
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+                &tampered,
+                &verifier_key,
+            ),
+            Err(Error::PublicInputMismatch { index: 2 })
+        ));
+
+        // a verifier expecting another public input is not fooled by a claim of that
+        // value: the committed input does not open to it
+        let mut public_inputs = input[..3].to_vec();
+        public_inputs[2] += Fr::from(1u64);
+        let (other_circuit, _) = init_circuit::<Fr>();
+        let mut other_circuit = other_circuit.with_public_inputs(public_inputs);
+        assert!(GKRVerifier::verify_with_kzg(
+            &mut other_circuit,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+            &tampered,
+            &verifier_key,
+        )
+        .is_err());
+
+        // nor does a proof without the public openings pass
+        let mut truncated = gkr_proof.clone();
+        truncated.input_opening.evaluations.truncate(2);
+        truncated.input_opening.q_taus.truncate(2);
+        assert!(matches!(
+            GKRVerifier::verify_with_kzg(
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+                &truncated,
+                &verifier_key,
+            ),
+            Err(Error::InvalidProofLength {
+                expected: 5,
+                got: 2
+            })
+        ));
+    }
+
+    // every sumcheck holds, only the KZG opening of the input is forged
+    #[test]
+    fn test_gkr_with_kzg_forged_opening() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+
+        let mut gkr_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+
+        gkr_proof.input_opening.q_taus[1][0] += <Bn254 as Pairing>::G1::generator();
+
+        assert!(matches!(
+            GKRVerifier::verify_with_kzg(
+                &mut circuit,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+                &gkr_proof,
+                &verifier_key,
+            ),
+            Err(Error::Kzg(kzg::error::Error::PairingCheckFailed))
+        ));
+    }
+
+    #[test]
+    fn test_gkr_with_kzg_rejects_wrong_setup_size() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = TrustedSetup::<Bn254, Fr>::new(&[Fr::from(11), Fr::from(13)]);
+
+        assert!(matches!(
+            GKRProver::prove_with_kzg::<Bn254, _>(
+                &input,
+                &mut circuit,
+                &trusted_setup.encrypted_lagrange_basis,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+            ),
+            Err(Error::Kzg(kzg::error::Error::SetupSizeMismatch {
+                n_vars: 3,
+                basis_len: 4
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_gkr_with_kzg_wrong_output() {
+        let (mut circuit, input) = init_circuit::<Fr>();
+        let trusted_setup = kzg_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+        let mut transcript_p = FiatShamir::<Keccak256, Fr>::new();
+        let mut transcript_v = FiatShamir::<Keccak256, Fr>::new();
+
+        let mut gkr_proof = GKRProver::prove_with_kzg::<Bn254, _>(
+            &input,
+            &mut circuit,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut transcript_p,
+        )
+        .unwrap();
+
+        gkr_proof.proof.output_poly.evals[0] = Fr::from(999u64);
+
+        let is_verified = GKRVerifier::verify_with_kzg(
+            &mut circuit,
+            &mut transcript_v,
+            &gkr_proof,
+            &verifier_key,
+        );
+
+        assert!(matches!(
+            is_verified,
+            Err(Error::InitialClaimMismatch { layer: 0 })
+        ));
     }
 }
@@ -1,41 +1,110 @@
 use crate::circuit::{Circuit, Op};
 use crate::{
-    protocol::{GKRProof, GKRProofWithKZG},
-    utils::{get_evaluated_muli_addi_at_a, get_folded_claim_sum, get_folded_polys},
+    error::Error,
+    protocol::{GKRProof, GKRProofWithKZG, DOMAIN_SEPARATOR, KZG_DOMAIN_SEPARATOR},
+    utils::{get_evaluated_muli_addi_at_a, get_folded_claim_sum, get_folded_polys, opening_points},
 };
 use ark_ec::pairing::Pairing;
-use ark_ff::{BigInteger, PrimeField};
-use polynomials::{
-    composed::{product_poly::ProductPoly, sum_poly::SumPoly},
-    multilinear::multilinear_poly::MultilinearPoly,
-};
-use sha3::Keccak256;
+use ark_ff::PrimeField;
+use kzg::multilinear::prover::MultilinearKZGProver;
+use polynomials::{composed::sum_poly::SumPoly, multilinear::multilinear_poly::MultilinearPoly};
 use std::marker::PhantomData;
-use sumcheck::{fiat_shamir::FiatShamir, sumcheck_protocol::partial_prove};
+use sumcheck::{sumcheck_protocol::partial_prove, transcript::Transcript};
 
 pub struct GKRProver<F: PrimeField> {
     _phantom: PhantomData<F>,
 }
 
 impl<F: PrimeField> GKRProver<F> {
-    pub fn prove(
+    pub fn prove<T: Transcript<F>>(
+        input_layer: &[F],
+        circuit: &mut Circuit<F>,
+        transcript: &mut T,
+    ) -> Result<GKRProof<F>, Error> {
+        Ok(Self::prove_layers(input_layer, circuit, transcript)?.0)
+    }
+
+    /// Proves the circuit against a KZG commitment to the input layer instead of the
+    /// input itself. The commitment is absorbed before anything else, and the two
+    /// claims `W_input(r_b)`, `W_input(r_c)` left by the last sumcheck are opened with
+    /// one batched KZG proof, so the verifier never needs the witness. The same proof
+    /// opens the input at the hypercube point of every public input, which is where it
+    /// has to take the public value. The Lagrange basis must have one element per input.
+    ///
+    /// The commitment is binding but not hiding, and the sumcheck messages are sent in
+    /// the clear, so this mode is succinct but not zero-knowledge. Making it so would take
+    /// masked sumchecks as well as a hiding commitment, which is out of scope here.
+    pub fn prove_with_kzg<P: Pairing, T: Transcript<F>>(
+        input_layer: &[F],
+        circuit: &mut Circuit<F>,
+        encrypted_lagrange_basis: &[P::G1],
+        transcript: &mut T,
+    ) -> Result<GKRProofWithKZG<F, P>, Error> {
+        circuit.validate()?;
+        if input_layer.len() != circuit.input_len() {
+            return Err(Error::InvalidInputLength {
+                expected: circuit.input_len(),
+                got: input_layer.len(),
+            });
+        }
+
+        circuit.check_public_inputs(input_layer)?;
+
+        let n_vars = input_layer.len().ilog2() as usize;
+        if encrypted_lagrange_basis.len() != input_layer.len() {
+            return Err(kzg::error::Error::SetupSizeMismatch {
+                n_vars,
+                basis_len: encrypted_lagrange_basis.len(),
+            }
+            .into());
+        }
+
+        let input_poly = MultilinearPoly::new(input_layer.to_vec(), n_vars);
+        let input_commitment =
+            MultilinearKZGProver::<F, P>::compute_commitment(&input_poly, encrypted_lagrange_basis);
+
+        transcript.domain_separator(KZG_DOMAIN_SEPARATOR);
+        transcript.append_point(b"input_commitment", &input_commitment);
+        transcript.append_fields(b"public_inputs", circuit.public_inputs());
+
+        let (proof, challenges) = Self::prove_layers(input_layer, circuit, transcript)?;
+        let (r_b, r_c) = challenges.split_at(challenges.len() / 2);
+
+        let points = opening_points(r_b, r_c, circuit.public_inputs().len(), n_vars);
+        let input_opening = MultilinearKZGProver::<F, P>::batch_prove(
+            &points,
+            &[input_poly],
+            &[input_commitment],
+            encrypted_lagrange_basis,
+            transcript,
+        );
+
+        Ok(GKRProofWithKZG::new(proof, input_commitment, input_opening))
+    }
+
+    /// Runs the layer by layer sumchecks, returning the proof and the challenges of the
+    /// last sumcheck, which is where the input layer has to be evaluated.
+    fn prove_layers<T: Transcript<F>>(
         input_layer: &[F],
         circuit: &mut Circuit<F>,
-        transcript: &mut FiatShamir<Keccak256, F>,
-    ) -> GKRProof<F> {
+        transcript: &mut T,
+    ) -> Result<(GKRProof<F>, Vec<F>), Error> {
+        circuit.validate()?;
+        circuit.check_public_inputs(input_layer)?;
+
         // get number of layers and initialize vectors for tracking w_poly_evals and sumcheck_proofs
         let layer_count = circuit.get_layer_count();
         let mut w_poly_evals = Vec::with_capacity(layer_count);
         let mut sumcheck_proofs = Vec::with_capacity(layer_count);
 
         // legnth of rs for generating random values for the ouput_poly
-        let length_of_rs = circuit.get_layer_poly(0, input_layer.to_vec()).n_vars;
-
-        let mut running_layer_poly = circuit.get_layer_poly(0, input_layer.to_vec());
+        let mut running_layer_poly = circuit.get_layer_poly(0, input_layer.to_vec())?;
+        let length_of_rs = running_layer_poly.n_vars;
 
-        transcript.absorb(&running_layer_poly.to_bytes());
+        transcript.domain_separator(DOMAIN_SEPARATOR);
+        transcript.append_poly(b"output_poly", &running_layer_poly);
 
-        let mut random_values: Vec<F> = transcript.squeeze_n(length_of_rs);
+        let mut random_values: Vec<F> = transcript.challenge_scalars(b"output_point", length_of_rs);
 
         for layer_i in 0..layer_count {
             let (muli_a_b_c, addi_a_b_c) = (
@@ -49,9 +118,9 @@ impl<F: PrimeField> GKRProver<F> {
                         muli_a_b_c,
                         addi_a_b_c,
                         random_values.to_vec(),
-                    );
+                    )?;
                     (
-                        running_layer_poly.evaluate(random_values.to_vec()),
+                        running_layer_poly.checked_evaluate(&random_values)?,
                         muli_b_c,
                         addi_b_c,
                     )
@@ -66,22 +135,22 @@ impl<F: PrimeField> GKRProver<F> {
                     // dbg!(&r_b);
                     // dbg!(&r_c);
 
-                    let w_i_b_eval = running_layer_poly.evaluate(r_b.to_vec());
-                    let w_i_c_eval = running_layer_poly.evaluate(r_c.to_vec());
+                    let w_i_b_eval = running_layer_poly.checked_evaluate(r_b)?;
+                    let w_i_c_eval = running_layer_poly.checked_evaluate(r_c)?;
 
                     // dbg!(&w_i_b_eval);
                     // dbg!(&w_i_c_eval);
 
-                    transcript.absorb_n(&[
-                        &w_i_b_eval.into_bigint().to_bytes_le(),
-                        &w_i_c_eval.into_bigint().to_bytes_le(),
-                    ]);
+                    transcript.append_fields(b"w_evals", &[w_i_b_eval, w_i_c_eval]);
 
-                    let (alpha, beta) = (transcript.squeeze(), transcript.squeeze());
+                    let (alpha, beta) = (
+                        transcript.challenge_scalar(b"alpha"),
+                        transcript.challenge_scalar(b"beta"),
+                    );
 
                     // get new claim sums, new addi and muli polys, alongside evaluation of current w_i layer poly
                     let (new_muli_b_c, new_addi_b_c) =
-                        get_folded_polys(&alpha, &beta, muli_a_b_c, addi_a_b_c, r_b, r_c);
+                        get_folded_polys(&alpha, &beta, muli_a_b_c, addi_a_b_c, r_b, r_c)?;
 
                     w_poly_evals.push((w_i_b_eval, w_i_c_eval));
 
@@ -93,7 +162,7 @@ impl<F: PrimeField> GKRProver<F> {
                 }
             };
 
-            let next_w_i = circuit.get_layer_poly(layer_i + 1, input_layer.to_vec());
+            let next_w_i = circuit.get_layer_poly(layer_i + 1, input_layer.to_vec())?;
 
             // dbg!(&next_w_i);
             // dbg!(&new_addi_b_c);
@@ -107,20 +176,28 @@ impl<F: PrimeField> GKRProver<F> {
 
             // dbg!(&sumcheck_proof);
 
-            random_values = sumcheck_proof
-                .rand_challenges
-                .iter()
-                .copied()
-                .collect();
+            random_values = sumcheck_proof.rand_challenges.to_vec();
             running_layer_poly = next_w_i;
 
             sumcheck_proofs.push(sumcheck_proof);
         }
 
-        GKRProof {
-            output_poly: circuit.get_layer_poly(0, input_layer.to_vec()),
+        // close the last layer the way the verifier does, with the input evaluations
+        let (r_b, r_c) = random_values.split_at(random_values.len() / 2);
+        transcript.append_fields(
+            b"w_evals",
+            &[
+                running_layer_poly.checked_evaluate(r_b)?,
+                running_layer_poly.checked_evaluate(r_c)?,
+            ],
+        );
+
+        let proof = GKRProof {
+            output_poly: circuit.get_layer_poly(0, input_layer.to_vec())?,
             w_poly_evals,
             sumcheck_proofs,
-        }
+        };
+
+        Ok((proof, random_values))
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::Error;
 use ark_ff::PrimeField;
 use polynomials::multilinear::multilinear_poly::MultilinearPoly;
 
@@ -5,23 +6,11 @@ pub fn get_evaluated_muli_addi_at_a<F: PrimeField>(
     muli_a_b_c: MultilinearPoly<F>,
     addi_a_b_c: MultilinearPoly<F>,
     random_values: Vec<F>,
-) -> (MultilinearPoly<F>, MultilinearPoly<F>) {
-    let mut new_muli_b_c = muli_a_b_c;
-    let mut new_addi_b_c = addi_a_b_c;
-
-    for item in &random_values {
-        if new_muli_b_c.n_vars != 0 {
-            new_muli_b_c =
-                new_muli_b_c.partial_evaluate((new_muli_b_c.n_vars - 1, *item));
-        }
-
-        if new_addi_b_c.n_vars != 0 {
-            new_addi_b_c =
-                new_addi_b_c.partial_evaluate((new_addi_b_c.n_vars - 1, *item));
-        }
-    }
-
-    (new_muli_b_c, new_addi_b_c)
+) -> Result<(MultilinearPoly<F>, MultilinearPoly<F>), Error> {
+    Ok((
+        muli_a_b_c.bind_top_variables(&random_values)?,
+        addi_a_b_c.bind_top_variables(&random_values)?,
+    ))
 }
 
 pub fn get_folded_polys<F: PrimeField>(
@@ -31,48 +20,17 @@ pub fn get_folded_polys<F: PrimeField>(
     addi_a_b_c: MultilinearPoly<F>,
     r_b: &[F],
     r_c: &[F],
-) -> (MultilinearPoly<F>, MultilinearPoly<F>) {
+) -> Result<(MultilinearPoly<F>, MultilinearPoly<F>), Error> {
     // Apply partial evaluation for r_b and scale by alpha
-
-    let muli_b = r_b
-        .iter()
-        .fold(muli_a_b_c.clone(), | acc, &b| {
-            acc.partial_evaluate((acc.n_vars - 1, b))
-        })
-        .scalar_mul(*alpha);
-
-    // dbg!(&muli_b);
-
-    let addi_b = r_b
-        .iter()
-        .fold(addi_a_b_c.clone(), | acc, &b| {
-            acc.partial_evaluate((acc.n_vars - 1, b))
-        })
-        .scalar_mul(*alpha);
+    let muli_b = muli_a_b_c.bind_top_variables(r_b)?.scalar_mul(*alpha);
+    let addi_b = addi_a_b_c.bind_top_variables(r_b)?.scalar_mul(*alpha);
 
     // Apply partial evaluation for r_c and scale by beta
-    let muli_c = r_c
-        .iter()
-        .fold(muli_a_b_c, | acc, &c| {
-            acc.partial_evaluate((acc.n_vars - 1, c))
-        })
-        .scalar_mul(*beta);
-
-    let addi_c = r_c
-        .iter()
-        .fold(addi_a_b_c, |mut acc, &c| {
-            acc.partial_evaluate((acc.n_vars - 1, c))
-        })
-        .scalar_mul(*beta);
-
-    let muli_b_c = muli_b + muli_c;
-    let addi_b_c = addi_b + addi_c;
-
-    // dbg!(&muli_b_c);
-    // dbg!(&addi_b_c);
+    let muli_c = muli_a_b_c.bind_top_variables(r_c)?.scalar_mul(*beta);
+    let addi_c = addi_a_b_c.bind_top_variables(r_c)?.scalar_mul(*beta);
 
     // Sum the results
-    (muli_b_c, addi_b_c)
+    Ok((muli_b + muli_c, addi_b + addi_c))
 }
 
 pub fn get_folded_claim_sum<F: PrimeField>(
@@ -83,3 +41,25 @@ pub fn get_folded_claim_sum<F: PrimeField>(
 ) -> F {
     (*alpha * *w_i_b_eval) + (*beta * *w_i_c_eval)
 }
+
+/// The points the KZG opening of the input covers: the two claims `r_b`, `r_c` left by
+/// the last sumcheck, then the hypercube point of each of the first `public_len`
+/// inputs, its top bit first as [`MultilinearPoly::evaluate`] binds them.
+pub fn opening_points<F: PrimeField>(
+    r_b: &[F],
+    r_c: &[F],
+    public_len: usize,
+    n_vars: usize,
+) -> Vec<Vec<F>> {
+    let hypercube_point = |index: usize| {
+        (0..n_vars)
+            .rev()
+            .map(|bit| F::from((index >> bit) as u64 & 1))
+            .collect()
+    };
+
+    [r_b.to_vec(), r_c.to_vec()]
+        .into_iter()
+        .chain((0..public_len).map(hypercube_point))
+        .collect()
+}
@@ -1,32 +1,125 @@
 use crate::circuit::{Circuit, Op};
 use crate::{
-    protocol::GKRProof,
-    utils::{get_evaluated_muli_addi_at_a, get_folded_polys, get_folded_claim_sum},
+    error::Error,
+    protocol::{GKRProof, GKRProofWithKZG, DOMAIN_SEPARATOR, KZG_DOMAIN_SEPARATOR},
+    utils::{get_evaluated_muli_addi_at_a, get_folded_claim_sum, get_folded_polys, opening_points},
 };
 use ark_ec::pairing::Pairing;
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::PrimeField;
+use kzg::multilinear::{verifier::MultilinearKZGVerifier, verifier_key::VerifierKey};
 use polynomials::multilinear::multilinear_poly::MultilinearPoly;
-use sha3::Keccak256;
 use std::marker::PhantomData;
-use sumcheck::{fiat_shamir::FiatShamir, sumcheck_protocol::partial_verify};
+use sumcheck::{sumcheck_protocol::partial_verify, transcript::Transcript};
 
 pub struct GKRVerifier<F: PrimeField> {
     _phantom: PhantomData<F>,
 }
 
 impl<F: PrimeField> GKRVerifier<F> {
-    pub fn verify(
+    pub fn verify<T: Transcript<F>>(
         input_layer: &[F],
         circuit: &mut Circuit<F>,
-        transcript: &mut FiatShamir<Keccak256, F>,
+        transcript: &mut T,
         proof: &GKRProof<F>,
-    ) -> bool {
+    ) -> Result<(), Error> {
+        circuit.validate()?;
+        if input_layer.len() != circuit.input_len() {
+            return Err(Error::InvalidInputLength {
+                expected: circuit.input_len(),
+                got: input_layer.len(),
+            });
+        }
+        circuit.check_public_inputs(input_layer)?;
+
+        let input_poly =
+            MultilinearPoly::new(input_layer.to_vec(), input_layer.len().ilog2() as usize);
+
+        let input_evals = |r_b: &[F], r_c: &[F]| {
+            Ok((
+                input_poly.checked_evaluate(r_b)?,
+                input_poly.checked_evaluate(r_c)?,
+            ))
+        };
+
+        Self::verify_layers(circuit, transcript, proof, input_evals)?;
+
+        let expected_final_eval = circuit.get_layer_poly(0, input_layer.to_vec())?;
+        if proof.output_poly.evals != expected_final_eval.evals {
+            return Err(Error::OutputMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies a proof made with [`crate::prover::GKRProver::prove_with_kzg`] knowing
+    /// only the input commitment. The input evaluations claimed in the KZG opening are
+    /// used to close the last layer, then the opening itself is checked against the
+    /// commitment at the challenges of the last sumcheck, and at the hypercube point of
+    /// every public input, where it has to claim the public value.
+    pub fn verify_with_kzg<P: Pairing, T: Transcript<F>>(
+        circuit: &mut Circuit<F>,
+        transcript: &mut T,
+        proof: &GKRProofWithKZG<F, P>,
+        verifier_key: &VerifierKey<P>,
+    ) -> Result<(), Error> {
+        circuit.validate()?;
+
+        let public_inputs = circuit.public_inputs().to_vec();
+        let evaluations = &proof.input_opening.evaluations;
+        check_length(2 + public_inputs.len(), evaluations.len())?;
+        for evals in evaluations {
+            check_length(1, evals.len())?;
+        }
+        for (index, (public, evals)) in public_inputs.iter().zip(&evaluations[2..]).enumerate() {
+            if evals[0] != *public {
+                return Err(Error::PublicInputMismatch { index });
+            }
+        }
+
+        transcript.domain_separator(KZG_DOMAIN_SEPARATOR);
+        transcript.append_point(b"input_commitment", &proof.input_commitment);
+        transcript.append_fields(b"public_inputs", &public_inputs);
+
+        let input_evals = |_: &[F], _: &[F]| Ok((evaluations[0][0], evaluations[1][0]));
+
+        let challenges = Self::verify_layers(circuit, transcript, &proof.proof, input_evals)?;
+        let (r_b, r_c) = challenges.split_at(challenges.len() / 2);
+        let points = opening_points(
+            r_b,
+            r_c,
+            public_inputs.len(),
+            circuit.input_len().ilog2() as usize,
+        );
+
+        MultilinearKZGVerifier::<F, P>::batch_verify_with_key(
+            &[proof.input_commitment],
+            &points,
+            &proof.input_opening,
+            verifier_key,
+            transcript,
+        )?;
+
+        Ok(())
+    }
+
+    /// Checks every layer's sumcheck, closing the last one with `input_evals(r_b, r_c)`.
+    /// Returns the challenges of the last sumcheck if everything holds. The circuit must
+    /// already be validated.
+    fn verify_layers<T: Transcript<F>>(
+        circuit: &mut Circuit<F>,
+        transcript: &mut T,
+        proof: &GKRProof<F>,
+        input_evals: impl Fn(&[F], &[F]) -> Result<(F, F), Error>,
+    ) -> Result<Vec<F>, Error> {
+        Self::check_shape(circuit, proof)?;
+
         let layer_count = circuit.get_layer_count();
         let length_of_rs = proof.output_poly.n_vars;
 
-        transcript.absorb(&proof.output_poly.to_bytes());
+        transcript.domain_separator(DOMAIN_SEPARATOR);
+        transcript.append_poly(b"output_poly", &proof.output_poly);
 
-        let mut random_values: Vec<F> = transcript.squeeze_n(length_of_rs);
+        let mut random_values: Vec<F> = transcript.challenge_scalars(b"output_point", length_of_rs);
 
         for layer_i in 0..layer_count {
             let (muli_a_b_c, addi_a_b_c) = (
@@ -34,23 +127,25 @@ impl<F: PrimeField> GKRVerifier<F> {
                 circuit.add_mul_i(layer_i, Op::ADD),
             );
 
-            let (mut new_muli_b_c, mut new_addi_b_c) = match layer_i {
+            let (new_muli_b_c, new_addi_b_c) = match layer_i {
                 0 => {
-                    let expected_output_eval = proof.output_poly.evaluate(random_values.to_vec());
+                    let expected_output_eval =
+                        proof.output_poly.checked_evaluate(&random_values)?;
                     if proof.sumcheck_proofs[layer_i].initial_claimed_sum != expected_output_eval {
-                        // println!("Invalid initial claim for layer 0. Expected {}, got {}", expected_output_eval, proof.sumcheck_proofs[layer_i].initial_claimed_sum);
-                        return false;
+                        return Err(Error::InitialClaimMismatch { layer: layer_i });
                     }
-                    get_evaluated_muli_addi_at_a(muli_a_b_c, addi_a_b_c, random_values.to_vec())
-                },
+                    get_evaluated_muli_addi_at_a(muli_a_b_c, addi_a_b_c, random_values.to_vec())?
+                }
                 _ => {
-                    let (alpha, beta) = (transcript.squeeze(), transcript.squeeze());
+                    let (alpha, beta) = (
+                        transcript.challenge_scalar(b"alpha"),
+                        transcript.challenge_scalar(b"beta"),
+                    );
 
                     let (prev_w_b, prev_w_c) = proof.w_poly_evals[layer_i - 1];
                     let expected_claim = get_folded_claim_sum(&alpha, &beta, &prev_w_b, &prev_w_c);
                     if proof.sumcheck_proofs[layer_i].initial_claimed_sum != expected_claim {
-                        // println!("Invalid initial claim for layer {}. Expected {}, got {}", layer_i, expected_claim, proof.sumcheck_proofs[layer_i].initial_claimed_sum);
-                        return false;
+                        return Err(Error::InitialClaimMismatch { layer: layer_i });
                     }
 
                     let (new_muli_b_c, new_addi_b_c) = get_folded_polys(
@@ -60,14 +155,20 @@ impl<F: PrimeField> GKRVerifier<F> {
                         addi_a_b_c,
                         &random_values[0..random_values.len() / 2],
                         &random_values[random_values.len() / 2..],
-                    );
+                    )?;
 
                     (new_muli_b_c, new_addi_b_c)
                 }
             };
 
+            // f(b, c) = add(b, c) * (W(b) + W(c)) + mul(b, c) * W(b) * W(c) is quadratic
             let (challenges, claimed_sum) =
-                partial_verify(&proof.sumcheck_proofs[layer_i], transcript);
+                partial_verify(&proof.sumcheck_proofs[layer_i], 2, transcript).map_err(
+                    |source| Error::Sumcheck {
+                        layer: layer_i,
+                        source,
+                    },
+                )?;
 
             // dbg!(&challenges);
             // dbg!(&new_muli_b_c);
@@ -76,10 +177,10 @@ impl<F: PrimeField> GKRVerifier<F> {
             // dbg!(&new_muli_b_c.evaluate(challenges.to_vec()));
 
             let (new_muli_b_c_eval, new_addi_b_c_eval) = (
-                new_muli_b_c.evaluate(challenges.to_vec()),
-                new_addi_b_c.evaluate(challenges.to_vec()),
+                new_muli_b_c.checked_evaluate(&challenges)?,
+                new_addi_b_c.checked_evaluate(&challenges)?,
             );
-            
+
             // dbg!(&new_addi_b_c_eval);
             // dbg!(&new_muli_b_c_eval);
 
@@ -88,26 +189,13 @@ impl<F: PrimeField> GKRVerifier<F> {
 
                 // dbg!(&r_b);
                 // dbg!(&r_c);
-                let mut next_w_i = MultilinearPoly::new(
-                    input_layer.to_vec(),
-                    input_layer.len().ilog2().try_into().unwrap(),
-                );
-
-                // dbg!(&next_w_i);
-
-                (
-                    next_w_i.evaluate(r_b.to_vec()),
-                    next_w_i.evaluate(r_c.to_vec()),
-                )
+                input_evals(r_b, r_c)?
             } else {
                 proof.w_poly_evals[layer_i]
             };
 
-            transcript.absorb_n(&[
-                &next_w_i_b_eval.into_bigint().to_bytes_le(),
-                &next_w_i_c_eval.into_bigint().to_bytes_le(),
-            ]);
-            
+            transcript.append_fields(b"w_evals", &[next_w_i_b_eval, next_w_i_c_eval]);
+
             // dbg!(&new_addi_b_c_eval);
             // dbg!(&new_muli_b_c_eval);
 
@@ -115,22 +203,40 @@ impl<F: PrimeField> GKRVerifier<F> {
                 + (new_muli_b_c_eval * (next_w_i_b_eval * next_w_i_c_eval));
 
             if fbc_eval != claimed_sum {
-                // println!("Invalid intermediate claim_sum");
-                // println!("Sums are fbc_eval: {} and claimed_sum: {}", fbc_eval, claimed_sum);
-                // println!("This occured in layer {} of {} layers", layer_i, layer_count);
-
-                return false;
+                return Err(Error::FinalEvalMismatch { layer: layer_i });
             }
 
             random_values = challenges;
         }
-        
-        let expected_final_eval = circuit.get_layer_poly(0, input_layer.to_vec());
-        if proof.output_poly.evals != expected_final_eval.evals {
-            println!("Output polynomial evaluation failed");
-            return false;
+
+        Ok(random_values)
+    }
+
+    /// Checks that the proof has one sumcheck per layer with two rounds per variable of
+    /// the layer below, and an output layer as wide as the circuit's, so nothing later
+    /// evaluates a polynomial at the wrong number of points.
+    fn check_shape(circuit: &Circuit<F>, proof: &GKRProof<F>) -> Result<(), Error> {
+        let widths = circuit.layer_widths();
+        let layer_count = circuit.get_layer_count();
+
+        check_length(layer_count, proof.sumcheck_proofs.len())?;
+        check_length(layer_count - 1, proof.w_poly_evals.len())?;
+        check_length(widths[0], proof.output_poly.evals.len())?;
+        check_length(widths[0].ilog2() as usize, proof.output_poly.n_vars)?;
+
+        for (sumcheck_proof, width) in proof.sumcheck_proofs.iter().zip(&widths[1..]) {
+            let n_vars = width.next_power_of_two().ilog2().max(1) as usize;
+            check_length(2 * n_vars, sumcheck_proof.round_polys.len())?;
         }
 
-        true
+        Ok(())
     }
 }
+
+fn check_length(expected: usize, got: usize) -> Result<(), Error> {
+    if expected != got {
+        return Err(Error::InvalidProofLength { expected, got });
+    }
+
+    Ok(())
+}
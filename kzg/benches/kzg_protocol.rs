@@ -1,9 +1,11 @@
-use ark_bls12_381::{Bls12_381, Fr};
-use ark_ff::UniformRand;
+use ark_bls12_381::{Bls12_381, Fr, G1Projective};
+use ark_ec::PrimeGroup;
+use ark_ff::{PrimeField, UniformRand};
 use ark_std::rand::{rngs::StdRng, SeedableRng};
 use criterion::{criterion_group, criterion_main, Criterion};
 use kzg::multilinear::{
     prover::MultilinearKZGProver, trusted_setup::TrustedSetup, verifier::MultilinearKZGVerifier,
+    verifier_key::VerifierKey,
 };
 use polynomials::multilinear::multilinear_poly::MultilinearPoly;
 use std::hint::black_box;
@@ -69,18 +71,86 @@ fn kzg_benchmark(c: &mut Criterion, n_vars: usize) {
         &trusted_setup.encrypted_taus,
     );
 
-    assert!(valid);
+    assert!(valid.is_ok());
 
     c.bench_function(&format!("kzg_verifier/{} variables", n_vars), |b| {
         b.iter(|| {
-            black_box(MultilinearKZGVerifier::<Fr, Bls12_381>::verify(
+            assert!(black_box(MultilinearKZGVerifier::<Fr, Bls12_381>::verify(
                 &commitment,
                 &openings,
                 &proof,
                 &trusted_setup.encrypted_taus,
-            ));
+            ))
+            .is_ok());
+        })
+    });
+
+    let verifier_key = VerifierKey::from_setup(&trusted_setup);
+
+    c.bench_function(
+        &format!("kzg_verifier_with_key/{} variables", n_vars),
+        |b| {
+            b.iter(|| {
+                assert!(
+                    black_box(MultilinearKZGVerifier::<Fr, Bls12_381>::verify_with_key(
+                        &commitment,
+                        &openings,
+                        &proof,
+                        &verifier_key,
+                    ))
+                    .is_ok()
+                );
+            })
+        },
+    );
+}
+
+// Building a real trusted setup at 20 variables takes minutes, the commitment only
+// cares about the size of the basis so consecutive multiples of a random point do.
+fn commit_setup(n_vars: usize) -> (Vec<G1Projective>, MultilinearPoly<Fr>) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let n_evals = 1usize << n_vars;
+
+    let point = G1Projective::rand(&mut rng);
+    let mut basis = Vec::with_capacity(n_evals);
+    let mut current = point;
+    for _ in 0..n_evals {
+        basis.push(current);
+        current += point;
+    }
+
+    let poly_values: Vec<Fr> = (0..n_evals).map(|_| Fr::rand(&mut rng)).collect();
+
+    (basis, MultilinearPoly::new(poly_values, n_vars))
+}
+
+fn commit_benchmark(c: &mut Criterion, n_vars: usize) {
+    let (basis, poly) = commit_setup(n_vars);
+
+    let mut group = c.benchmark_group(format!("kzg_commit_large/{} variables", n_vars));
+    group.sample_size(10);
+
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            black_box(
+                basis
+                    .iter()
+                    .zip(poly.evals.iter())
+                    .map(|(base, eval)| base.mul_bigint(eval.into_bigint()))
+                    .sum::<G1Projective>(),
+            )
+        })
+    });
+
+    group.bench_function("pippenger", |b| {
+        b.iter(|| {
+            black_box(MultilinearKZGProver::<Fr, Bls12_381>::compute_commitment(
+                &poly, &basis,
+            ))
         })
     });
+
+    group.finish();
 }
 
 fn kzg_benchmarks(c: &mut Criterion) {
@@ -89,5 +159,11 @@ fn kzg_benchmarks(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, kzg_benchmarks);
+fn kzg_commit_benchmarks(c: &mut Criterion) {
+    for n_vars in 16..=20 {
+        commit_benchmark(c, n_vars);
+    }
+}
+
+criterion_group!(benches, kzg_benchmarks, kzg_commit_benchmarks);
 criterion_main!(benches);
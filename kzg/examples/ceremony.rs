@@ -0,0 +1,90 @@
+//! Runs the multilinear setup ceremony with one process per step, passing the
+//! transcript around as a file:
+//!
+//! ```bash
+//! cargo run --example ceremony -- init 4 ceremony.bin
+//! cargo run --example ceremony -- contribute ceremony.bin   # once per participant
+//! cargo run --example ceremony -- verify ceremony.bin
+//! cargo run --example ceremony -- export ceremony.bin setup.srs
+//! ```
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use kzg::multilinear::ceremony::Ceremony;
+use std::{
+    env,
+    fs::File,
+    io::{BufReader, BufWriter},
+    process,
+};
+
+type BlsCeremony = Ceremony<Bls12_381, Fr>;
+
+fn read(path: &str) -> BlsCeremony {
+    let reader = BufReader::new(File::open(path).expect("Could not open the transcript"));
+    BlsCeremony::deserialize_with_mode(reader, Compress::Yes, Validate::Yes)
+        .expect("Could not read the transcript")
+}
+
+fn write(ceremony: &BlsCeremony, path: &str) {
+    let writer = BufWriter::new(File::create(path).expect("Could not create the transcript"));
+    ceremony
+        .serialize_compressed(writer)
+        .expect("Could not write the transcript");
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["init", n_vars, path] => {
+            let n_vars = n_vars.parse().expect("n_vars must be a number");
+            write(&BlsCeremony::new(n_vars), path);
+            println!("Started a ceremony over {n_vars} variables in {path}");
+        }
+        ["contribute", path] => {
+            let mut ceremony = read(path);
+            if let Err(err) = ceremony.verify() {
+                panic!("Refusing to build on an invalid transcript: {err}");
+            }
+
+            ceremony.contribute(&mut StdRng::from_entropy());
+            write(&ceremony, path);
+            println!("Added contribution #{}", ceremony.contributions.len());
+        }
+        ["verify", path] => {
+            let ceremony = read(path);
+            match ceremony.verify() {
+                Ok(()) => println!(
+                    "Transcript with {} contributions is valid",
+                    ceremony.contributions.len()
+                ),
+                Err(err) => {
+                    println!("Transcript is invalid: {err}");
+                    process::exit(1);
+                }
+            }
+        }
+        ["export", path, srs_path] => {
+            let ceremony = read(path);
+            if let Err(err) = ceremony.verify() {
+                panic!("Refusing to export an invalid transcript: {err}");
+            }
+
+            ceremony
+                .setup
+                .save(srs_path, Compress::Yes)
+                .expect("Could not write the setup");
+            println!("Wrote the setup to {srs_path}");
+        }
+        _ => {
+            eprintln!("usage: ceremony init <n_vars> <file> | contribute <file> | verify <file> | export <file> <srs>");
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+/// Why a KZG opening, setup or ceremony was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A proof, point or set of evaluations has the wrong number of elements.
+    InvalidProofLength { expected: usize, got: usize },
+    /// The setup does not hold enough powers for the check.
+    SetupTooSmall { needed: usize, got: usize },
+    /// The Lagrange basis does not have `2^n_vars` elements.
+    SetupSizeMismatch { n_vars: usize, basis_len: usize },
+    /// The final pairing equation does not hold.
+    PairingCheckFailed,
+    /// Contribution `index` of a ceremony does not prove its secrets or does not
+    /// rescale the previous taus.
+    InvalidContribution { index: usize },
+    /// The setup does not match the last contribution or its basis is inconsistent
+    /// with its taus.
+    MalformedSetup,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidProofLength { expected, got } => {
+                write!(f, "expected {expected} elements, got {got}")
+            }
+            Self::SetupTooSmall { needed, got } => {
+                write!(f, "setup has {got} powers, {needed} are needed")
+            }
+            Self::SetupSizeMismatch { n_vars, basis_len } => write!(
+                f,
+                "Lagrange basis has {basis_len} elements for {n_vars} variables"
+            ),
+            Self::PairingCheckFailed => write!(f, "pairing check failed"),
+            Self::InvalidContribution { index } => write!(f, "contribution {index} is invalid"),
+            Self::MalformedSetup => write!(f, "setup is not consistent with the transcript"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -1 +1,5 @@
+pub mod error;
+pub mod msm;
 pub mod multilinear;
+pub mod univariate;
+pub mod utils;
@@ -0,0 +1,157 @@
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Computes `sum scalars[i] * bases[i]` with Pippenger's bucket method.
+///
+/// The scalars only need to share the bit length of the group order, they are
+/// consumed through their canonical integer representation.
+///
+/// The scalars are cut into `c`-bit windows. For every window each base is dropped
+/// into the bucket of its digit, the buckets are folded with a running sum and the
+/// window sums are combined with `c` doublings each. With the `parallel` feature
+/// the windows are processed on separate threads.
+pub fn msm<G: CurveGroup, F: PrimeField>(bases: &[G], scalars: &[F]) -> G {
+    assert!(
+        scalars.len() <= bases.len(),
+        "Got {} scalars for {} bases",
+        scalars.len(),
+        bases.len()
+    );
+
+    let bases = G::normalize_batch(&bases[..scalars.len()]);
+    let scalars: Vec<_> = scalars.iter().map(|s| s.into_bigint()).collect();
+
+    let c = window_size(scalars.len());
+    let num_bits = F::MODULUS_BIT_SIZE as usize;
+    let window_starts: Vec<usize> = (0..num_bits).step_by(c).collect();
+
+    let window_sum = |start: &usize| -> G {
+        let mut buckets = vec![G::zero(); (1 << c) - 1];
+
+        for (base, scalar) in bases.iter().zip(scalars.iter()) {
+            let digit = window_digit(scalar.as_ref(), *start, c);
+            if digit != 0 {
+                buckets[digit - 1] += base;
+            }
+        }
+
+        // sum_{d} d * bucket[d] as a running sum from the top bucket down
+        let mut running_sum = G::zero();
+        let mut sum = G::zero();
+        for bucket in buckets.into_iter().rev() {
+            running_sum += bucket;
+            sum += running_sum;
+        }
+
+        sum
+    };
+
+    #[cfg(feature = "parallel")]
+    let window_sums: Vec<G> = window_starts.par_iter().map(window_sum).collect();
+    #[cfg(not(feature = "parallel"))]
+    let window_sums: Vec<G> = window_starts.iter().map(window_sum).collect();
+
+    window_sums
+        .into_iter()
+        .rev()
+        .fold(G::zero(), |mut acc, sum| {
+            for _ in 0..c {
+                acc.double_in_place();
+            }
+
+            acc + sum
+        })
+}
+
+/// Window size in bits, roughly `ln(n) + 2` which balances the bucket
+/// accumulation against the running sums.
+fn window_size(n: usize) -> usize {
+    if n < 32 {
+        3
+    } else {
+        ((n as f64).ln().ceil() as usize) + 2
+    }
+}
+
+/// Reads the `c`-bit digit starting at bit `start` of a little-endian limb slice.
+fn window_digit(limbs: &[u64], start: usize, c: usize) -> usize {
+    let limb = start / 64;
+    let offset = start % 64;
+
+    let mut digit = limbs[limb] >> offset;
+    if offset + c > 64 && limb + 1 < limbs.len() {
+        digit |= limbs[limb + 1] << (64 - offset);
+    }
+
+    (digit & ((1 << c) - 1)) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use ark_ff::UniformRand;
+    use ark_ff::Zero;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    fn naive_msm(bases: &[G1Projective], scalars: &[Fr]) -> G1Projective {
+        bases
+            .iter()
+            .zip(scalars.iter())
+            .map(|(base, scalar)| base.mul_bigint(scalar.into_bigint()))
+            .sum()
+    }
+
+    #[test]
+    fn test_msm_matches_naive() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for n in [1, 2, 7, 31, 32, 100, 300] {
+            let bases: Vec<G1Projective> = (0..n).map(|_| G1Projective::rand(&mut rng)).collect();
+            let scalars: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+
+            assert_eq!(msm(&bases, &scalars), naive_msm(&bases, &scalars));
+        }
+    }
+
+    #[test]
+    fn test_msm_edge_scalars() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let bases: Vec<G1Projective> = (0..5).map(|_| G1Projective::rand(&mut rng)).collect();
+        let scalars = vec![
+            Fr::from(0),
+            Fr::from(1),
+            -Fr::from(1),
+            Fr::from(0),
+            Fr::from(2),
+        ];
+
+        assert_eq!(msm(&bases, &scalars), naive_msm(&bases, &scalars));
+        assert!(msm(&bases, &[Fr::from(0); 5]).is_zero());
+        assert!(msm::<G1Projective, Fr>(&bases, &[]).is_zero());
+    }
+
+    #[test]
+    fn test_msm_uses_prefix_of_bases() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let bases: Vec<G1Projective> = (0..10).map(|_| G1Projective::rand(&mut rng)).collect();
+        let scalars: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+
+        assert_eq!(msm(&bases, &scalars), naive_msm(&bases[..4], &scalars));
+    }
+
+    #[test]
+    fn test_window_digit() {
+        let limbs = [u64::MAX << 60, 0b1011];
+
+        assert_eq!(window_digit(&limbs, 60, 4), 0b1111);
+        assert_eq!(window_digit(&limbs, 62, 4), 0b1111);
+        assert_eq!(window_digit(&limbs, 63, 4), 0b0111);
+        assert_eq!(window_digit(&limbs, 64, 4), 0b1011);
+        assert_eq!(window_digit(&limbs, 0, 4), 0);
+    }
+}
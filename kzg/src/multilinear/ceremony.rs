@@ -0,0 +1,442 @@
+use crate::error::Error;
+use crate::msm::msm;
+use crate::multilinear::trusted_setup::TrustedSetup;
+use crate::utils::to_bytes;
+use ark_ec::{pairing::Pairing, CurveGroup, PrimeGroup};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::Rng;
+use sha3::Keccak256;
+use sumcheck::fiat_shamir::FiatShamir;
+
+/// Schnorr proof of knowledge of the secret `s` behind `[s]G1`.
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KnowledgeProof<E: Pairing, F: PrimeField> {
+    pub commitment: E::G1,
+    pub response: F,
+}
+
+/// One participant's update `tau_i -> s_i * tau_i`.
+///
+/// `encrypted_taus` are the taus after the update and `secret_commitments[i]` is
+/// `[s_i]G1`, which is what ties them to the previous taus in the pairing check.
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Contribution<E: Pairing, F: PrimeField> {
+    pub encrypted_taus: Vec<E::G2>,
+    pub secret_commitments: Vec<E::G1>,
+    pub proofs: Vec<KnowledgeProof<E, F>>,
+}
+
+/// Powers-of-tau style ceremony for the multilinear SRS.
+///
+/// Every participant multiplies each `tau_i` by a secret of their own, so the final
+/// taus are the products of all the secrets and stay unknown as long as a single
+/// participant throws theirs away. The Lagrange basis can't be rescaled directly
+/// because of its `1 - tau_i` factors, so it is moved to the monomial basis
+/// `prod_{i in S} tau_i`, rescaled there and moved back, using only group additions
+/// and one scalar multiplication per element.
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
+pub struct Ceremony<E: Pairing, F: PrimeField> {
+    pub setup: TrustedSetup<E, F>,
+    pub contributions: Vec<Contribution<E, F>>,
+}
+
+impl<E: Pairing, F: PrimeField> Ceremony<E, F> {
+    /// Starts the ceremony from `tau = (1, ..., 1)`, where every encrypted tau is the
+    /// G2 generator and the only non-zero Lagrange basis element is the all-ones corner.
+    pub fn new(n_vars: usize) -> Self {
+        let mut encrypted_lagrange_basis = vec![E::G1::zero(); 1 << n_vars];
+        encrypted_lagrange_basis[(1 << n_vars) - 1] = E::G1::generator();
+
+        Self {
+            setup: TrustedSetup::from_parts_unchecked(
+                vec![E::G2::generator(); n_vars],
+                encrypted_lagrange_basis,
+            ),
+            contributions: vec![],
+        }
+    }
+
+    pub fn n_vars(&self) -> usize {
+        self.setup.n_vars()
+    }
+
+    /// Adds a contribution with fresh secrets, which are dropped before returning.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> &Contribution<E, F> {
+        let secrets: Vec<F> = (0..self.n_vars())
+            .map(|_| loop {
+                let secret = F::rand(rng);
+                if !secret.is_zero() {
+                    break secret;
+                }
+            })
+            .collect();
+        let nonces: Vec<F> = (0..self.n_vars()).map(|_| F::rand(rng)).collect();
+
+        self.contribute_with(&secrets, &nonces)
+    }
+
+    fn contribute_with(&mut self, secrets: &[F], nonces: &[F]) -> &Contribution<E, F> {
+        let previous_taus = &self.setup.encrypted_taus;
+
+        let secret_commitments: Vec<E::G1> = secrets
+            .iter()
+            .map(|s| E::G1::generator().mul_bigint(s.into_bigint()))
+            .collect();
+
+        let proofs = secrets
+            .iter()
+            .zip(nonces.iter())
+            .enumerate()
+            .map(|(i, (secret, nonce))| {
+                let commitment = E::G1::generator().mul_bigint(nonce.into_bigint());
+                let challenge = Self::knowledge_challenge(
+                    previous_taus,
+                    i,
+                    &secret_commitments[i],
+                    &commitment,
+                );
+
+                KnowledgeProof {
+                    commitment,
+                    response: *nonce + challenge * secret,
+                }
+            })
+            .collect();
+
+        let encrypted_taus: Vec<E::G2> = previous_taus
+            .iter()
+            .zip(secrets.iter())
+            .map(|(tau, s)| tau.mul_bigint(s.into_bigint()))
+            .collect();
+
+        let mut basis = std::mem::take(&mut self.setup.encrypted_lagrange_basis);
+        lagrange_to_monomial(&mut basis);
+        for (element, scale) in basis.iter_mut().zip(monomial_scales(secrets)) {
+            *element = element.mul_bigint(scale.into_bigint());
+        }
+        monomial_to_lagrange(&mut basis);
+
+        self.setup = TrustedSetup::from_parts_unchecked(encrypted_taus.clone(), basis);
+        self.contributions.push(Contribution {
+            encrypted_taus,
+            secret_commitments,
+            proofs,
+        });
+
+        self.contributions.last().unwrap()
+    }
+
+    /// Replays the whole transcript: every contribution must prove knowledge of its
+    /// secrets and rescale the previous taus by them, the last one must match the
+    /// current setup and the setup's Lagrange basis must be consistent with its taus.
+    pub fn verify(&self) -> Result<(), Error> {
+        let mut taus = vec![E::G2::generator(); self.n_vars()];
+
+        for (index, contribution) in self.contributions.iter().enumerate() {
+            if !Self::verify_contribution(&taus, contribution) {
+                return Err(Error::InvalidContribution { index });
+            }
+
+            taus.clone_from(&contribution.encrypted_taus);
+        }
+
+        if taus != self.setup.encrypted_taus || !Self::is_well_formed(&self.setup) {
+            return Err(Error::MalformedSetup);
+        }
+
+        Ok(())
+    }
+
+    /// Checks a single update on top of `previous_taus`.
+    pub fn verify_contribution(previous_taus: &[E::G2], contribution: &Contribution<E, F>) -> bool {
+        let n_vars = previous_taus.len();
+        if contribution.encrypted_taus.len() != n_vars
+            || contribution.secret_commitments.len() != n_vars
+            || contribution.proofs.len() != n_vars
+        {
+            return false;
+        }
+
+        (0..n_vars).all(|i| {
+            let secret_commitment = contribution.secret_commitments[i];
+            let proof = &contribution.proofs[i];
+
+            if secret_commitment.is_zero() {
+                return false;
+            }
+
+            // z * G1 = R + c * [s]G1
+            let challenge =
+                Self::knowledge_challenge(previous_taus, i, &secret_commitment, &proof.commitment);
+            let knows_secret = E::G1::generator().mul_bigint(proof.response.into_bigint())
+                == proof.commitment + secret_commitment.mul_bigint(challenge.into_bigint());
+
+            // e([s]G1, [tau]G2) = e(G1, [s * tau]G2)
+            let rescales_tau = E::multi_pairing(
+                [secret_commitment, -E::G1::generator()],
+                [previous_taus[i], contribution.encrypted_taus[i]],
+            )
+            .is_zero();
+
+            knows_secret && rescales_tau
+        })
+    }
+
+    /// Checks that the basis is the encrypted Lagrange basis of the taus in G2.
+    ///
+    /// In the monomial basis the empty product is G1 and every other monomial is the
+    /// monomial without its lowest variable times that variable, i.e
+    /// `e(m_x, G2) = e(m_{x - bit}, [tau]G2)`. All `2^n` checks are folded with powers
+    /// of a Fiat-Shamir challenge into one multi-pairing with `n + 1` terms.
+    pub fn is_well_formed(setup: &TrustedSetup<E, F>) -> bool {
+        let n_vars = setup.n_vars();
+        if setup.encrypted_lagrange_basis.len() != 1 << n_vars {
+            return false;
+        }
+
+        let mut monomials = setup.encrypted_lagrange_basis.clone();
+        lagrange_to_monomial(&mut monomials);
+
+        if monomials[0] != E::G1::generator() {
+            return false;
+        }
+
+        let mut transcript = FiatShamir::<Keccak256, F>::new();
+        transcript.absorb(&to_bytes(&E::G2::normalize_batch(&setup.encrypted_taus)));
+        transcript.absorb(&to_bytes(&E::G1::normalize_batch(
+            &setup.encrypted_lagrange_basis,
+        )));
+        let rho = transcript.squeeze();
+
+        let mut rho_powers = Vec::with_capacity(monomials.len());
+        let mut rho_pow = F::one();
+        for _ in 0..monomials.len() {
+            rho_powers.push(rho_pow);
+            rho_pow *= rho;
+        }
+
+        let mut g1_terms = vec![msm(&monomials[1..], &rho_powers[1..])];
+        let mut g2_terms = vec![E::G2::generator()];
+
+        for bit in 0..n_vars {
+            let (bases, scalars): (Vec<E::G1>, Vec<F>) = (1..monomials.len())
+                .filter(|x| x.trailing_zeros() as usize == bit)
+                .map(|x| (monomials[x ^ (1 << bit)], rho_powers[x]))
+                .unzip();
+
+            // the lowest bit belongs to the last variable
+            g1_terms.push(-msm(&bases, &scalars));
+            g2_terms.push(setup.encrypted_taus[n_vars - 1 - bit]);
+        }
+
+        E::multi_pairing(g1_terms, g2_terms).is_zero()
+    }
+
+    /// Schnorr challenge bound to the taus being updated, so a proof can't be
+    /// replayed on top of a different transcript.
+    fn knowledge_challenge(
+        previous_taus: &[E::G2],
+        index: usize,
+        secret_commitment: &E::G1,
+        commitment: &E::G1,
+    ) -> F {
+        let mut transcript = FiatShamir::<Keccak256, F>::new();
+        transcript.absorb(&to_bytes(&previous_taus.to_vec()));
+        transcript.absorb(&(index as u64).to_le_bytes());
+        transcript.absorb(&to_bytes(secret_commitment));
+        transcript.absorb(&to_bytes(commitment));
+
+        transcript.squeeze()
+    }
+}
+
+/// `m_S = sum_{B superset of S} L_B`, since the `1 - tau_i` and `tau_i` factors of
+/// the variables outside `S` sum to one.
+fn lagrange_to_monomial<G: CurveGroup>(values: &mut [G]) {
+    let mut half = 1;
+    while half < values.len() {
+        for chunk in values.chunks_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            lo.iter_mut().zip(hi.iter()).for_each(|(l, h)| *l += h);
+        }
+
+        half *= 2;
+    }
+}
+
+/// Inverse of [`lagrange_to_monomial`].
+fn monomial_to_lagrange<G: CurveGroup>(values: &mut [G]) {
+    let mut half = 1;
+    while half < values.len() {
+        for chunk in values.chunks_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            lo.iter_mut().zip(hi.iter()).for_each(|(l, h)| *l -= h);
+        }
+
+        half *= 2;
+    }
+}
+
+/// `scales[x] = prod s_i` over the variables set in `x`, with the first variable on
+/// the highest bit like the Lagrange basis.
+fn monomial_scales<F: PrimeField>(secrets: &[F]) -> Vec<F> {
+    let mut scales = vec![F::one()];
+
+    for secret in secrets {
+        scales = scales
+            .into_iter()
+            .flat_map(|scale| [scale, scale * secret])
+            .collect();
+    }
+
+    scales
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multilinear::{prover::MultilinearKZGProver, verifier::MultilinearKZGVerifier};
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_serialize::{Compress, Validate};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use ark_std::UniformRand;
+    use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+
+    type TestCeremony = Ceremony<Bls12_381, Fr>;
+
+    fn run_ceremony(n_vars: usize, participants: usize) -> TestCeremony {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut ceremony = TestCeremony::new(n_vars);
+
+        for _ in 0..participants {
+            ceremony.contribute(&mut rng);
+        }
+
+        ceremony
+    }
+
+    #[test]
+    fn test_initial_ceremony_is_valid() {
+        let ceremony = TestCeremony::new(3);
+
+        assert_eq!(ceremony.verify(), Ok(()));
+        assert_eq!(
+            ceremony.setup.encrypted_lagrange_basis,
+            TrustedSetup::<Bls12_381, Fr>::new(&[Fr::from(1); 3]).encrypted_lagrange_basis
+        );
+    }
+
+    #[test]
+    fn test_contributions_multiply_taus() {
+        let mut ceremony = TestCeremony::new(3);
+        let first = [Fr::from(2), Fr::from(3), Fr::from(5)];
+        let second = [Fr::from(7), Fr::from(11), Fr::from(13)];
+
+        ceremony.contribute_with(&first, &[Fr::from(1); 3]);
+        ceremony.contribute_with(&second, &[Fr::from(4); 3]);
+
+        let taus: Vec<Fr> = first
+            .iter()
+            .zip(second.iter())
+            .map(|(a, b)| *a * b)
+            .collect();
+        let expected = TrustedSetup::<Bls12_381, Fr>::new(&taus);
+
+        assert_eq!(ceremony.verify(), Ok(()));
+        assert_eq!(ceremony.setup.encrypted_taus, expected.encrypted_taus);
+        assert_eq!(
+            ceremony.setup.encrypted_lagrange_basis,
+            expected.encrypted_lagrange_basis
+        );
+    }
+
+    #[test]
+    fn test_ceremony_setup_verifies_proofs() {
+        let ceremony = run_ceremony(3, 3);
+        assert_eq!(ceremony.verify(), Ok(()));
+
+        let mut rng = StdRng::seed_from_u64(12);
+        let poly = MultilinearPoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        let openings: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let setup = &ceremony.setup;
+
+        let commitment = MultilinearKZGProver::<Fr, Bls12_381>::compute_commitment(
+            &poly,
+            &setup.encrypted_lagrange_basis,
+        );
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::prove(
+            &openings,
+            &poly,
+            &setup.encrypted_lagrange_basis,
+        );
+
+        assert_eq!(
+            MultilinearKZGVerifier::<Fr, Bls12_381>::verify(
+                &commitment,
+                &openings,
+                &proof,
+                &setup.encrypted_taus,
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_tampered_basis_fails() {
+        let mut ceremony = run_ceremony(3, 2);
+        ceremony.setup.encrypted_lagrange_basis[5] += <Bls12_381 as Pairing>::G1::generator();
+
+        assert_eq!(ceremony.verify(), Err(Error::MalformedSetup));
+    }
+
+    #[test]
+    fn test_tampered_taus_fail() {
+        let mut ceremony = run_ceremony(3, 2);
+        let tampered =
+            ceremony.contributions[0].encrypted_taus[1] + <Bls12_381 as Pairing>::G2::generator();
+        ceremony.contributions[0].encrypted_taus[1] = tampered;
+
+        assert_eq!(
+            ceremony.verify(),
+            Err(Error::InvalidContribution { index: 0 })
+        );
+    }
+
+    #[test]
+    fn test_forged_knowledge_proof_fails() {
+        let mut ceremony = run_ceremony(2, 2);
+        ceremony.contributions[1].proofs[0].response += Fr::from(1);
+
+        assert_eq!(
+            ceremony.verify(),
+            Err(Error::InvalidContribution { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_dropped_contribution_fails() {
+        let mut ceremony = run_ceremony(2, 3);
+        ceremony.contributions.remove(1);
+
+        assert_eq!(
+            ceremony.verify(),
+            Err(Error::InvalidContribution { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_ceremony_serialization_roundtrip() {
+        let ceremony = run_ceremony(2, 2);
+
+        let mut bytes = Vec::new();
+        ceremony.serialize_compressed(&mut bytes).unwrap();
+
+        let loaded =
+            TestCeremony::deserialize_with_mode(&bytes[..], Compress::Yes, Validate::Yes).unwrap();
+
+        assert_eq!(loaded.verify(), Ok(()));
+        assert_eq!(loaded.contributions.len(), 2);
+        assert_eq!(loaded.setup.encrypted_taus, ceremony.setup.encrypted_taus);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::error::Error;
+use crate::multilinear::{
+    protocol::MultilinearKZGProof, prover::MultilinearKZGProver, trusted_setup::TrustedSetup,
+    verifier::MultilinearKZGVerifier, verifier_key::VerifierKey,
+};
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+use sumcheck::{commitment::MultilinearCommitment, transcript::Transcript};
+
+/// KZG openings need no challenge, so the transcript is left as it is.
+impl<F: PrimeField, E: Pairing> MultilinearCommitment<F> for MultilinearKZGProver<F, E> {
+    type ProverParameters = TrustedSetup<E, F>;
+    type VerifierParameters = VerifierKey<E>;
+    type Commitment = E::G1;
+    type CommitmentData = ();
+    type Proof = MultilinearKZGProof<F, E>;
+    type Error = Error;
+
+    fn commit(setup: &TrustedSetup<E, F>, poly: &MultilinearPoly<F>) -> Result<(E::G1, ()), Error> {
+        check_setup(setup, poly)?;
+
+        Ok((
+            Self::compute_commitment(poly, &setup.encrypted_lagrange_basis),
+            (),
+        ))
+    }
+
+    fn open<T: Transcript<F>>(
+        setup: &TrustedSetup<E, F>,
+        poly: &MultilinearPoly<F>,
+        _: &(),
+        point: &[F],
+        _: &mut T,
+    ) -> Result<(F, MultilinearKZGProof<F, E>), Error> {
+        check_setup(setup, poly)?;
+        if point.len() != poly.n_vars {
+            return Err(Error::InvalidProofLength {
+                expected: poly.n_vars,
+                got: point.len(),
+            });
+        }
+
+        let proof = Self::prove(point, poly, &setup.encrypted_lagrange_basis);
+
+        Ok((proof.v, proof))
+    }
+
+    fn verify<T: Transcript<F>>(
+        verifier_key: &VerifierKey<E>,
+        commitment: &E::G1,
+        point: &[F],
+        value: F,
+        proof: &MultilinearKZGProof<F, E>,
+        _: &mut T,
+    ) -> Result<(), Error> {
+        // the pairing checks the claimed value, not whichever one the proof carries
+        let proof = MultilinearKZGProof::new(value, proof.q_taus.clone());
+
+        MultilinearKZGVerifier::verify_with_key(commitment, point, &proof, verifier_key)
+    }
+}
+
+fn check_setup<F: PrimeField, E: Pairing>(
+    setup: &TrustedSetup<E, F>,
+    poly: &MultilinearPoly<F>,
+) -> Result<(), Error> {
+    if setup.encrypted_lagrange_basis.len() != poly.evals.len() {
+        return Err(Error::SetupSizeMismatch {
+            n_vars: poly.n_vars,
+            basis_len: setup.encrypted_lagrange_basis.len(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use sha3::Keccak256;
+    use sumcheck::fiat_shamir::FiatShamir;
+
+    type Kzg = MultilinearKZGProver<Fr, Bls12_381>;
+
+    #[test]
+    fn test_kzg_as_multilinear_commitment() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let taus: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let setup = TrustedSetup::<Bls12_381, Fr>::new(&taus);
+        let verifier_key = VerifierKey::from_setup(&setup);
+
+        let poly = MultilinearPoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        let point: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+
+        let (commitment, data) = Kzg::commit(&setup, &poly).unwrap();
+        let (value, proof) = Kzg::open(
+            &setup,
+            &poly,
+            &data,
+            &point,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        )
+        .unwrap();
+        assert_eq!(value, poly.evaluate(point.clone()));
+
+        let verify = |value: Fr, point: &[Fr]| {
+            Kzg::verify(
+                &verifier_key,
+                &commitment,
+                point,
+                value,
+                &proof,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+            )
+        };
+        assert_eq!(verify(value, &point), Ok(()));
+        assert_eq!(
+            verify(value + Fr::from(1), &point),
+            Err(Error::PairingCheckFailed)
+        );
+        assert!(verify(value, &point[..2]).is_err());
+
+        let small_poly = MultilinearPoly::new(vec![Fr::from(1); 4], 2);
+        assert!(Kzg::commit(&setup, &small_poly).is_err());
+    }
+}
@@ -1,5 +1,8 @@
+pub mod ceremony;
+pub mod commitment;
 pub mod protocol;
 pub mod prover;
 pub mod trusted_setup;
 pub mod utils;
 pub mod verifier;
+pub mod verifier_key;
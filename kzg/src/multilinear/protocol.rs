@@ -1,14 +1,12 @@
-use crate::multilinear::{
-    prover::MultilinearKZGProver, trusted_setup::TrustedSetup, verifier::MultilinearKZGVerifier,
+use crate::utils::PROOF_VERSION;
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
 };
-use ark_bls12_381::{Bls12_381, Fr, G1Projective};
-use ark_ec::{pairing::Pairing, PrimeGroup};
-use ark_ff::{PrimeField, UniformRand, Zero};
-use ark_std::rand::{rngs::StdRng, SeedableRng};
-use polynomials::multilinear::multilinear_poly::{BlowUpDirection, MultilinearPoly};
-use std::{cmp::max, marker::PhantomData, ops::Mul};
-
-#[derive(Debug)]
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct MultilinearKZGProof<F: PrimeField, E: Pairing> {
     pub v: F,
     pub q_taus: Vec<E::G1>,
@@ -20,9 +18,206 @@ impl<F: PrimeField, E: Pairing> MultilinearKZGProof<F, E> {
     }
 }
 
+/// Opening of several polynomials at several points.
+///
+/// `evaluations[k][j]` is the claimed value of the `j`-th polynomial at the `k`-th point
+/// and `q_taus[k]` are the quotient commitments of the random combination of all
+/// polynomials opened at that point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultilinearKZGBatchProof<F: PrimeField, E: Pairing> {
+    pub evaluations: Vec<Vec<F>>,
+    pub q_taus: Vec<Vec<E::G1>>,
+}
+
+impl<F: PrimeField, E: Pairing> MultilinearKZGBatchProof<F, E> {
+    pub fn new(evaluations: Vec<Vec<F>>, q_taus: Vec<Vec<E::G1>>) -> Self {
+        Self {
+            evaluations,
+            q_taus,
+        }
+    }
+}
+
+/// Opening of a hiding commitment `C = [f(tau)]G1 + [r]H`.
+///
+/// Every `q_tau` is blinded with `[r_i]H` and `blinding = [r - sum r_i * (tau_i - a_i)]G2`
+/// carries what the blinding factors leave over in the pairing check.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HidingMultilinearKZGProof<F: PrimeField, E: Pairing> {
+    pub v: F,
+    pub q_taus: Vec<E::G1>,
+    pub blinding: E::G2,
+}
+
+impl<F: PrimeField, E: Pairing> HidingMultilinearKZGProof<F, E> {
+    pub fn new(v: F, q_taus: Vec<E::G1>, blinding: E::G2) -> Self {
+        Self {
+            v,
+            q_taus,
+            blinding,
+        }
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalSerialize for MultilinearKZGProof<F, E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.v.serialize_with_mode(&mut writer, compress)?;
+        self.q_taus.serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.v.serialized_size(compress)
+            + self.q_taus.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField, E: Pairing> Valid for MultilinearKZGProof<F, E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.v.check()?;
+        self.q_taus.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalDeserialize for MultilinearKZGProof<F, E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(Self {
+            v: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            q_taus: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalSerialize for MultilinearKZGBatchProof<F, E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.evaluations
+            .serialize_with_mode(&mut writer, compress)?;
+        self.q_taus.serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.evaluations.serialized_size(compress)
+            + self.q_taus.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField, E: Pairing> Valid for MultilinearKZGBatchProof<F, E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.evaluations.check()?;
+        self.q_taus.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalDeserialize for MultilinearKZGBatchProof<F, E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(Self {
+            evaluations: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            q_taus: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalSerialize for HidingMultilinearKZGProof<F, E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.v.serialize_with_mode(&mut writer, compress)?;
+        self.q_taus.serialize_with_mode(&mut writer, compress)?;
+        self.blinding.serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.v.serialized_size(compress)
+            + self.q_taus.serialized_size(compress)
+            + self.blinding.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField, E: Pairing> Valid for HidingMultilinearKZGProof<F, E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.v.check()?;
+        self.q_taus.check()?;
+        self.blinding.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalDeserialize for HidingMultilinearKZGProof<F, E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(Self {
+            v: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            q_taus: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            blinding: E::G2::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Error;
+    use crate::multilinear::{
+        prover::MultilinearKZGProver, trusted_setup::TrustedSetup,
+        utils::generate_hiding_generator, verifier::MultilinearKZGVerifier,
+        verifier_key::VerifierKey,
+    };
+    use ark_bls12_381::{Bls12_381, Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+    use sha3::Keccak256;
+    use sumcheck::{fiat_shamir::FiatShamir, transcript::PoseidonTranscript};
 
     #[test]
     fn test_kzg_protocol() {
@@ -70,6 +265,459 @@ mod tests {
         );
 
         // dbg!(&is_verified);
-        assert!(is_verified, "Proof verification failed");
+        assert_eq!(is_verified, Ok(()), "Proof verification failed");
+    }
+
+    type BatchSetup = (
+        TrustedSetup<Bls12_381, Fr>,
+        Vec<MultilinearPoly<Fr>>,
+        Vec<G1Projective>,
+        Vec<Vec<Fr>>,
+    );
+
+    fn batch_setup() -> BatchSetup {
+        let mut rng = StdRng::seed_from_u64(42);
+        let n_vars = 4;
+
+        let taus: Vec<Fr> = (0..n_vars).map(|_| Fr::rand(&mut rng)).collect();
+        let trusted_setup = TrustedSetup::<Bls12_381, Fr>::new(&taus);
+
+        let polys: Vec<MultilinearPoly<Fr>> = (0..3)
+            .map(|_| {
+                MultilinearPoly::new(
+                    (0..1 << n_vars).map(|_| Fr::rand(&mut rng)).collect(),
+                    n_vars,
+                )
+            })
+            .collect();
+
+        let commitments = polys
+            .iter()
+            .map(|poly| {
+                MultilinearKZGProver::<Fr, Bls12_381>::compute_commitment(
+                    poly,
+                    &trusted_setup.encrypted_lagrange_basis,
+                )
+            })
+            .collect();
+
+        // r_b and r_c style opening points
+        let points: Vec<Vec<Fr>> = (0..2)
+            .map(|_| (0..n_vars).map(|_| Fr::rand(&mut rng)).collect())
+            .collect();
+
+        (trusted_setup, polys, commitments, points)
+    }
+
+    #[test]
+    fn test_kzg_batch_protocol() {
+        let (trusted_setup, polys, commitments, points) = batch_setup();
+
+        let mut transcript_p = FiatShamir::<Keccak256, Fr>::new();
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::batch_prove(
+            &points,
+            &polys,
+            &commitments,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut transcript_p,
+        );
+
+        for (k, point) in points.iter().enumerate() {
+            for (j, poly) in polys.iter().enumerate() {
+                assert_eq!(proof.evaluations[k][j], poly.evaluate(point.to_vec()));
+            }
+        }
+
+        let mut transcript_v = FiatShamir::<Keccak256, Fr>::new();
+        let is_verified = MultilinearKZGVerifier::<Fr, Bls12_381>::batch_verify(
+            &commitments,
+            &points,
+            &proof,
+            &trusted_setup.encrypted_taus,
+            &mut transcript_v,
+        );
+
+        assert_eq!(is_verified, Ok(()), "Batch proof verification failed");
+    }
+
+    // nothing to open: the proof only carries zero quotients, and the verifier agrees
+    #[test]
+    fn test_kzg_batch_protocol_empty_batch() {
+        let (trusted_setup, _, _, points) = batch_setup();
+
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::batch_prove(
+            &points,
+            &[],
+            &[],
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        );
+        assert!(proof.evaluations.iter().all(|evals| evals.is_empty()));
+
+        assert_eq!(
+            MultilinearKZGVerifier::<Fr, Bls12_381>::batch_verify(
+                &[],
+                &points,
+                &proof,
+                &trusted_setup.encrypted_taus,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_kzg_batch_protocol_on_poseidon_transcript() {
+        let (trusted_setup, polys, commitments, points) = batch_setup();
+
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::batch_prove(
+            &points,
+            &polys,
+            &commitments,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut PoseidonTranscript::new(),
+        );
+
+        assert_eq!(
+            MultilinearKZGVerifier::<Fr, Bls12_381>::batch_verify(
+                &commitments,
+                &points,
+                &proof,
+                &trusted_setup.encrypted_taus,
+                &mut PoseidonTranscript::new(),
+            ),
+            Ok(())
+        );
+
+        // the challenges are bound to the hash, a Keccak verifier rejects it
+        assert_eq!(
+            MultilinearKZGVerifier::<Fr, Bls12_381>::batch_verify(
+                &commitments,
+                &points,
+                &proof,
+                &trusted_setup.encrypted_taus,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+            ),
+            Err(Error::PairingCheckFailed)
+        );
+    }
+
+    #[test]
+    fn test_kzg_batch_protocol_wrong_evaluation() {
+        let (trusted_setup, polys, commitments, points) = batch_setup();
+
+        let mut transcript_p = FiatShamir::<Keccak256, Fr>::new();
+        let mut proof = MultilinearKZGProver::<Fr, Bls12_381>::batch_prove(
+            &points,
+            &polys,
+            &commitments,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut transcript_p,
+        );
+
+        proof.evaluations[1][2] += Fr::from(1);
+
+        let mut transcript_v = FiatShamir::<Keccak256, Fr>::new();
+        let is_verified = MultilinearKZGVerifier::<Fr, Bls12_381>::batch_verify(
+            &commitments,
+            &points,
+            &proof,
+            &trusted_setup.encrypted_taus,
+            &mut transcript_v,
+        );
+
+        assert_eq!(
+            is_verified,
+            Err(Error::PairingCheckFailed),
+            "Tampered batch proof should not verify"
+        );
+    }
+
+    #[test]
+    fn test_kzg_batch_protocol_wrong_commitment() {
+        let (trusted_setup, polys, mut commitments, points) = batch_setup();
+
+        let mut transcript_p = FiatShamir::<Keccak256, Fr>::new();
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::batch_prove(
+            &points,
+            &polys,
+            &commitments,
+            &trusted_setup.encrypted_lagrange_basis,
+            &mut transcript_p,
+        );
+
+        commitments.swap(0, 1);
+
+        let mut transcript_v = FiatShamir::<Keccak256, Fr>::new();
+        let is_verified = MultilinearKZGVerifier::<Fr, Bls12_381>::batch_verify(
+            &commitments,
+            &points,
+            &proof,
+            &trusted_setup.encrypted_taus,
+            &mut transcript_v,
+        );
+
+        assert_eq!(
+            is_verified,
+            Err(Error::PairingCheckFailed),
+            "Batch proof against wrong commitments should not verify"
+        );
+    }
+
+    #[test]
+    fn test_kzg_protocol_with_verifier_key() {
+        let (trusted_setup, polys, commitments, points) = batch_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+
+        for (poly, commitment) in polys.iter().zip(commitments.iter()) {
+            for point in &points {
+                let mut proof = MultilinearKZGProver::<Fr, Bls12_381>::prove(
+                    point,
+                    poly,
+                    &trusted_setup.encrypted_lagrange_basis,
+                );
+
+                assert_eq!(
+                    MultilinearKZGVerifier::<Fr, Bls12_381>::verify_with_key(
+                        commitment,
+                        point,
+                        &proof,
+                        &verifier_key,
+                    ),
+                    Ok(())
+                );
+
+                proof.v += Fr::from(1);
+                assert_eq!(
+                    MultilinearKZGVerifier::<Fr, Bls12_381>::verify_with_key(
+                        commitment,
+                        point,
+                        &proof,
+                        &verifier_key,
+                    ),
+                    Err(Error::PairingCheckFailed)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_kzg_protocol_wrong_number_of_openings() {
+        let (trusted_setup, polys, commitments, points) = batch_setup();
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::prove(
+            &points[0],
+            &polys[0],
+            &trusted_setup.encrypted_lagrange_basis,
+        );
+
+        assert_eq!(
+            MultilinearKZGVerifier::<Fr, Bls12_381>::verify(
+                &commitments[0],
+                &points[0][1..],
+                &proof,
+                &trusted_setup.encrypted_taus,
+            ),
+            Err(Error::InvalidProofLength {
+                expected: 4,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_hiding_kzg_protocol() {
+        let (trusted_setup, polys, _, points) = batch_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+        let hiding_generator = generate_hiding_generator::<Bls12_381>();
+        let mut rng = StdRng::seed_from_u64(43);
+
+        let (commitment, r) = MultilinearKZGProver::<Fr, Bls12_381>::compute_hiding_commitment(
+            &polys[0],
+            &trusted_setup.encrypted_lagrange_basis,
+            &hiding_generator,
+            &mut rng,
+        );
+
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::prove_hiding(
+            &points[0],
+            &polys[0],
+            &r,
+            &trusted_setup.encrypted_lagrange_basis,
+            &trusted_setup.encrypted_taus,
+            &hiding_generator,
+            &mut rng,
+        );
+
+        assert_eq!(proof.v, polys[0].evaluate(points[0].clone()));
+        assert_eq!(
+            MultilinearKZGVerifier::<Fr, Bls12_381>::verify_hiding(
+                &commitment,
+                &points[0],
+                &proof,
+                &verifier_key,
+                &hiding_generator,
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_hiding_commitments_are_randomized() {
+        let (trusted_setup, polys, commitments, points) = batch_setup();
+        let hiding_generator = generate_hiding_generator::<Bls12_381>();
+        let mut rng = StdRng::seed_from_u64(44);
+
+        let (first, r_first) = MultilinearKZGProver::<Fr, Bls12_381>::compute_hiding_commitment(
+            &polys[0],
+            &trusted_setup.encrypted_lagrange_basis,
+            &hiding_generator,
+            &mut rng,
+        );
+        let (second, _) = MultilinearKZGProver::<Fr, Bls12_381>::compute_hiding_commitment(
+            &polys[0],
+            &trusted_setup.encrypted_lagrange_basis,
+            &hiding_generator,
+            &mut rng,
+        );
+
+        assert_ne!(first, second);
+        assert_ne!(first, commitments[0]);
+
+        let proof = |rng: &mut StdRng| {
+            MultilinearKZGProver::<Fr, Bls12_381>::prove_hiding(
+                &points[0],
+                &polys[0],
+                &r_first,
+                &trusted_setup.encrypted_lagrange_basis,
+                &trusted_setup.encrypted_taus,
+                &hiding_generator,
+                rng,
+            )
+        };
+        let (proof_a, proof_b) = (proof(&mut rng), proof(&mut rng));
+
+        assert_ne!(proof_a.q_taus, proof_b.q_taus);
+        assert_ne!(proof_a.blinding, proof_b.blinding);
+    }
+
+    #[test]
+    fn test_hiding_kzg_protocol_tampered_proof() {
+        let (trusted_setup, polys, _, points) = batch_setup();
+        let verifier_key = VerifierKey::from_setup(&trusted_setup);
+        let hiding_generator = generate_hiding_generator::<Bls12_381>();
+        let mut rng = StdRng::seed_from_u64(45);
+
+        let (commitment, r) = MultilinearKZGProver::<Fr, Bls12_381>::compute_hiding_commitment(
+            &polys[1],
+            &trusted_setup.encrypted_lagrange_basis,
+            &hiding_generator,
+            &mut rng,
+        );
+        let proof = || {
+            MultilinearKZGProver::<Fr, Bls12_381>::prove_hiding(
+                &points[1],
+                &polys[1],
+                &r,
+                &trusted_setup.encrypted_lagrange_basis,
+                &trusted_setup.encrypted_taus,
+                &hiding_generator,
+                &mut StdRng::seed_from_u64(46),
+            )
+        };
+        let verify = |proof: &HidingMultilinearKZGProof<Fr, Bls12_381>| {
+            MultilinearKZGVerifier::<Fr, Bls12_381>::verify_hiding(
+                &commitment,
+                &points[1],
+                proof,
+                &verifier_key,
+                &hiding_generator,
+            )
+        };
+
+        let mut wrong_value = proof();
+        wrong_value.v += Fr::from(1);
+        assert_eq!(verify(&wrong_value), Err(Error::PairingCheckFailed));
+
+        let mut wrong_blinding = proof();
+        wrong_blinding.blinding += <Bls12_381 as Pairing>::G2::generator();
+        assert_eq!(verify(&wrong_blinding), Err(Error::PairingCheckFailed));
+
+        let mut wrong_quotient = proof();
+        wrong_quotient.q_taus[0] += hiding_generator;
+        assert_eq!(verify(&wrong_quotient), Err(Error::PairingCheckFailed));
+    }
+
+    fn assert_round_trip<T>(value: &T)
+    where
+        T: CanonicalSerialize + CanonicalDeserialize + PartialEq + std::fmt::Debug,
+    {
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            value.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), value.serialized_size(compress));
+            assert_eq!(
+                &T::deserialize_with_mode(bytes.as_slice(), compress, Validate::Yes).unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_proof_serialization_round_trip() {
+        let (trusted_setup, polys, commitments, points) = batch_setup();
+        let basis = &trusted_setup.encrypted_lagrange_basis;
+
+        assert_round_trip(&MultilinearKZGProver::<Fr, Bls12_381>::prove(
+            &points[0], &polys[0], basis,
+        ));
+
+        assert_round_trip(&MultilinearKZGProver::<Fr, Bls12_381>::batch_prove(
+            &points,
+            &polys,
+            &commitments,
+            basis,
+            &mut FiatShamir::<Keccak256, Fr>::new(),
+        ));
+
+        let hiding_generator = generate_hiding_generator::<Bls12_381>();
+        let mut rng = StdRng::seed_from_u64(47);
+        let (_, r) = MultilinearKZGProver::<Fr, Bls12_381>::compute_hiding_commitment(
+            &polys[0],
+            basis,
+            &hiding_generator,
+            &mut rng,
+        );
+        assert_round_trip(&MultilinearKZGProver::<Fr, Bls12_381>::prove_hiding(
+            &points[0],
+            &polys[0],
+            &r,
+            basis,
+            &trusted_setup.encrypted_taus,
+            &hiding_generator,
+            &mut rng,
+        ));
+    }
+
+    #[test]
+    fn test_proof_serialization_rejects_bad_encodings() {
+        let (trusted_setup, polys, _, points) = batch_setup();
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::prove(
+            &points[0],
+            &polys[0],
+            &trusted_setup.encrypted_lagrange_basis,
+        );
+
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = PROOF_VERSION + 1;
+        assert!(matches!(
+            MultilinearKZGProof::<Fr, Bls12_381>::deserialize_compressed(wrong_version.as_slice()),
+            Err(SerializationError::InvalidData)
+        ));
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(MultilinearKZGProof::<Fr, Bls12_381>::deserialize_compressed(truncated).is_err());
     }
 }
@@ -1,13 +1,16 @@
-use crate::multilinear::{
-    protocol::MultilinearKZGProof, trusted_setup::TrustedSetup, verifier::MultilinearKZGVerifier,
+use crate::msm::msm;
+use crate::multilinear::protocol::{
+    HidingMultilinearKZGProof, MultilinearKZGBatchProof, MultilinearKZGProof,
 };
-use ark_bls12_381::{Bls12_381, G1Projective};
-use ark_bn254::{Bn254, Fr};
 use ark_ec::{pairing::Pairing, PrimeGroup};
-use ark_ff::{PrimeField, UniformRand, Zero};
-use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_ff::PrimeField;
+use ark_std::rand::Rng;
 use polynomials::multilinear::multilinear_poly::{BlowUpDirection, MultilinearPoly};
-use std::{cmp::max, marker::PhantomData, ops::Mul};
+use std::{cmp::max, marker::PhantomData};
+use sumcheck::transcript::Transcript;
+
+/// Opens every batch opening on the transcript.
+const BATCH_DOMAIN_SEPARATOR: &[u8] = b"multilinear-kzg-batch";
 
 #[derive(Clone, Debug)]
 pub struct MultilinearKZGProver<F: PrimeField, E: Pairing> {
@@ -22,12 +25,7 @@ impl<F: PrimeField, E: Pairing> MultilinearKZGProver<F, E> {
             "Length mismatch"
         );
 
-        let mut result = E::G1::zero();
-        for (i, eval) in poly.evals.iter().enumerate() {
-            result += &encrypted_lagrange_basis[i].mul_bigint(&eval.into_bigint());
-        }
-
-        result
+        msm(encrypted_lagrange_basis, &poly.evals)
     }
 
     /// Computes the KZG commitment for a given polynomial.
@@ -79,11 +77,147 @@ impl<F: PrimeField, E: Pairing> MultilinearKZGProver<F, E> {
 
         MultilinearKZGProof::new(v, q_taus)
     }
+
+    /// Opens every polynomial at every point. The polynomials are folded into
+    /// `sum gamma^j * f_j` with a Fiat-Shamir challenge `gamma`, so each point
+    /// only needs one set of quotient commitments. An empty batch folds into the zero
+    /// polynomial, whose quotients are all zero.
+    pub fn batch_prove<T: Transcript<F>>(
+        points: &[Vec<F>],
+        polys: &[MultilinearPoly<F>],
+        commitments: &[E::G1],
+        encrypted_lagrange_basis: &[E::G1],
+        transcript: &mut T,
+    ) -> MultilinearKZGBatchProof<F, E> {
+        assert_eq!(
+            polys.len(),
+            commitments.len(),
+            "Every polynomial needs a commitment"
+        );
+
+        let evaluations: Vec<Vec<F>> = points
+            .iter()
+            .map(|point| {
+                polys
+                    .iter()
+                    .map(|poly| poly.evaluate(point.to_vec()))
+                    .collect()
+            })
+            .collect();
+
+        let gamma = Self::batch_challenge(commitments, points, &evaluations, transcript);
+
+        let mut gamma_pow = F::one();
+        let basis_len = encrypted_lagrange_basis.len();
+        let mut combined_poly =
+            MultilinearPoly::new(vec![F::zero(); basis_len], basis_len.ilog2() as usize);
+        for poly in polys {
+            combined_poly = combined_poly + poly.scalar_mul(gamma_pow);
+            gamma_pow *= gamma;
+        }
+
+        let q_taus: Vec<Vec<E::G1>> = points
+            .iter()
+            .map(|point| Self::prove(point, &combined_poly, encrypted_lagrange_basis).q_taus)
+            .collect();
+
+        // bind the quotients to the transcript and draw the same point-combining
+        // challenge as the verifier so both transcripts stay in sync
+        for q_tau in q_taus.iter().flatten() {
+            transcript.append_point(b"q_tau", q_tau);
+        }
+        let _ = transcript.challenge_scalar(b"rho");
+
+        MultilinearKZGBatchProof::new(evaluations, q_taus)
+    }
+
+    /// Hiding commitment `C = [f(tau)]G1 + [r]H`, returned with the blinding factor `r`
+    /// which the prover needs again to open it. `H` should come from
+    /// [`generate_hiding_generator`](crate::multilinear::utils::generate_hiding_generator).
+    pub fn compute_hiding_commitment<R: Rng>(
+        poly: &MultilinearPoly<F>,
+        encrypted_lagrange_basis: &[E::G1],
+        hiding_generator: &E::G1,
+        rng: &mut R,
+    ) -> (E::G1, F) {
+        let r = F::rand(rng);
+        let commitment = Self::evaluate_at_tau(poly, encrypted_lagrange_basis)
+            + hiding_generator.mul_bigint(r.into_bigint());
+
+        (commitment, r)
+    }
+
+    /// Opens a hiding commitment made with blinding factor `r`.
+    ///
+    /// The quotients are computed as in [`Self::prove`] and blinded with fresh `[r_i]H`,
+    /// which turns the check into `e(C - [v]G1, G2) = prod_i e(W_i, [tau_i - a_i]G2) *
+    /// e(H, [r - sum r_i * (tau_i - a_i)]G2)`, so the last G2 element goes in the proof.
+    pub fn prove_hiding<R: Rng>(
+        openings: &[F],
+        poly: &MultilinearPoly<F>,
+        r: &F,
+        encrypted_lagrange_basis: &[E::G1],
+        encrypted_taus: &[E::G2],
+        hiding_generator: &E::G1,
+        rng: &mut R,
+    ) -> HidingMultilinearKZGProof<F, E> {
+        let proof = Self::prove(openings, poly, encrypted_lagrange_basis);
+        let q_blindings: Vec<F> = (0..proof.q_taus.len()).map(|_| F::rand(rng)).collect();
+
+        let q_taus = proof
+            .q_taus
+            .iter()
+            .zip(q_blindings.iter())
+            .map(|(q_tau, r_i)| *q_tau + hiding_generator.mul_bigint(r_i.into_bigint()))
+            .collect();
+
+        // [r + sum r_i * a_i]G2 - sum [r_i]([tau_i]G2)
+        let g2_scalar = *r
+            + q_blindings
+                .iter()
+                .zip(openings.iter())
+                .map(|(r_i, a_i)| *r_i * a_i)
+                .sum::<F>();
+        let blinding = E::G2::generator().mul_bigint(g2_scalar.into_bigint())
+            - msm(encrypted_taus, &q_blindings);
+
+        HidingMultilinearKZGProof::new(proof.v, q_taus, blinding)
+    }
+
+    /// Appends the commitments, opening points and claimed evaluations and draws
+    /// the polynomial-combining challenge. Shared with the verifier.
+    pub fn batch_challenge<T: Transcript<F>>(
+        commitments: &[E::G1],
+        points: &[Vec<F>],
+        evaluations: &[Vec<F>],
+        transcript: &mut T,
+    ) -> F {
+        transcript.domain_separator(BATCH_DOMAIN_SEPARATOR);
+
+        for commitment in commitments {
+            transcript.append_point(b"commitment", commitment);
+        }
+
+        for point in points {
+            transcript.append_fields(b"point", point);
+        }
+
+        for evals in evaluations {
+            transcript.append_fields(b"evaluations", evals);
+        }
+
+        transcript.challenge_scalar(b"gamma")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::multilinear::trusted_setup::TrustedSetup;
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Fr;
+    use ark_ff::{UniformRand, Zero};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_evaluate_at_tau() {
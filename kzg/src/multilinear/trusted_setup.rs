@@ -1,8 +1,29 @@
-use crate::multilinear::utils::{encrypt_lagrange_basis, generate_lagrange_basis};
-use ark_ec::{pairing::Pairing, PrimeGroup};
-use ark_ff::PrimeField;
-use ark_std::UniformRand;
-use std::{marker::PhantomData, ops::Mul};
+use crate::error::Error;
+use crate::multilinear::utils::encrypt_lagrange_basis;
+use crate::utils::to_bytes;
+use ark_ec::{pairing::Pairing, CurveGroup, PrimeGroup};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use polynomials::multilinear::utils::generate_lagrange_basis;
+use sha3::{Digest, Keccak256};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    marker::PhantomData,
+    path::Path,
+};
+
+/// Identifies a serialized multilinear SRS.
+const MAGIC: &[u8; 8] = b"MLKZGSRS";
+const VERSION: u8 = 1;
+/// magic, version, compression flag, curve id, number of variables and checksum.
+const HEADER_LEN: usize = 8 + 1 + 1 + 32 + 4 + 32;
+/// Largest setup a file may declare. The header is read before the checksum can be
+/// checked, so the number of variables it claims must not size the payload unchecked.
+pub const MAX_N_VARS: usize = 28;
 
 pub struct TrustedSetup<E: Pairing, F: PrimeField> {
     pub encrypted_taus: Vec<E::G2>,
@@ -29,13 +50,243 @@ impl<E: Pairing, F: PrimeField> TrustedSetup<E, F> {
             encrypted_lagrange_basis,
         }
     }
+
+    /// Builds a setup from already encrypted values, e.g. the output of a ceremony,
+    /// so the caller never has to know the taus. Fails unless the basis has
+    /// `2^n_vars` elements.
+    pub fn from_parts(
+        encrypted_taus: Vec<E::G2>,
+        encrypted_lagrange_basis: Vec<E::G1>,
+    ) -> Result<Self, Error> {
+        if !basis_matches(encrypted_taus.len(), encrypted_lagrange_basis.len()) {
+            return Err(Error::SetupSizeMismatch {
+                n_vars: encrypted_taus.len(),
+                basis_len: encrypted_lagrange_basis.len(),
+            });
+        }
+
+        Ok(Self::from_parts_unchecked(
+            encrypted_taus,
+            encrypted_lagrange_basis,
+        ))
+    }
+
+    /// [`Self::from_parts`] for callers that built the basis to the right size.
+    pub(crate) fn from_parts_unchecked(
+        encrypted_taus: Vec<E::G2>,
+        encrypted_lagrange_basis: Vec<E::G1>,
+    ) -> Self {
+        Self {
+            _marker: PhantomData,
+            encrypted_taus,
+            encrypted_lagrange_basis,
+        }
+    }
+
+    pub fn n_vars(&self) -> usize {
+        self.encrypted_taus.len()
+    }
+
+    /// Writes the setup to `path`, see [`TrustedSetup::to_writer`].
+    pub fn save<P: AsRef<Path>>(
+        &self,
+        path: P,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.to_writer(&mut writer, compress)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Loads a setup written by [`TrustedSetup::save`].
+    pub fn load<P: AsRef<Path>>(path: P, validate: Validate) -> Result<Self, SerializationError> {
+        Self::from_reader(BufReader::new(File::open(path)?), validate)
+    }
+
+    /// Writes the header followed by the affine points, compressed or not.
+    pub fn to_writer<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.serialize_with_mode(writer, compress)
+    }
+
+    /// Reads a setup in either format, the compression mode is taken from the header.
+    pub fn from_reader<R: Read>(reader: R, validate: Validate) -> Result<Self, SerializationError> {
+        Self::read_with_mode(reader, None, validate)
+    }
+
+    /// Fingerprint of the curve: hash of the compressed G1 and G2 generators.
+    fn curve_id() -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(to_bytes(&E::G1::generator()));
+        hasher.update(to_bytes(&E::G2::generator()));
+
+        hasher.finalize().into()
+    }
+
+    fn payload(&self, compress: Compress) -> Result<Vec<u8>, SerializationError> {
+        let mut payload = Vec::with_capacity(Self::payload_size(self.n_vars(), compress));
+        E::G2::normalize_batch(&self.encrypted_taus).serialize_with_mode(&mut payload, compress)?;
+        E::G1::normalize_batch(&self.encrypted_lagrange_basis)
+            .serialize_with_mode(&mut payload, compress)?;
+
+        Ok(payload)
+    }
+
+    fn payload_size(n_vars: usize, compress: Compress) -> usize {
+        // two length prefixes and fixed size points
+        2 * 0u64.serialized_size(compress)
+            + n_vars * E::G2::zero().serialized_size(compress)
+            + (1 << n_vars) * E::G1::zero().serialized_size(compress)
+    }
+
+    fn read_with_mode<R: Read>(
+        mut reader: R,
+        expected: Option<Compress>,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut header = [0u8; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+
+        if &header[..8] != MAGIC || header[8] != VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let compress = match header[9] {
+            0 => Compress::No,
+            1 => Compress::Yes,
+            _ => return Err(SerializationError::UnexpectedFlags),
+        };
+        if expected.is_some_and(|expected| expected != compress) {
+            return Err(SerializationError::UnexpectedFlags);
+        }
+
+        if header[10..42] != Self::curve_id() {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let n_vars = u32::from_le_bytes(header[42..46].try_into().unwrap()) as usize;
+        let checksum = &header[46..78];
+        if n_vars > MAX_N_VARS {
+            return Err(SerializationError::InvalidData);
+        }
+
+        // read exactly the payload so the setup can be embedded in a larger stream, and
+        // grow the buffer with the bytes actually read rather than the size the header
+        // claims
+        let len = Self::payload_size(n_vars, compress);
+        let mut payload = Vec::new();
+        (&mut reader).take(len as u64).read_to_end(&mut payload)?;
+        if payload.len() != len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        if Keccak256::digest(&payload).as_slice() != checksum {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let mut payload = payload.as_slice();
+        let encrypted_taus =
+            Vec::<E::G2Affine>::deserialize_with_mode(&mut payload, compress, validate)?;
+        let encrypted_lagrange_basis =
+            Vec::<E::G1Affine>::deserialize_with_mode(&mut payload, compress, validate)?;
+
+        if !payload.is_empty()
+            || encrypted_taus.len() != n_vars
+            || encrypted_lagrange_basis.len() != 1 << n_vars
+        {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(Self::from_parts_unchecked(
+            encrypted_taus.into_iter().map(Into::into).collect(),
+            encrypted_lagrange_basis
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        ))
+    }
+}
+
+/// Whether a basis of `basis_len` elements has the `2^n_vars` a setup needs, without
+/// overflowing the shift when a caller hands over 64 or more taus.
+fn basis_matches(n_vars: usize, basis_len: usize) -> bool {
+    u32::try_from(n_vars)
+        .ok()
+        .and_then(|n_vars| 1usize.checked_shl(n_vars))
+        == Some(basis_len)
+}
+
+impl<E: Pairing, F: PrimeField> CanonicalSerialize for TrustedSetup<E, F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let payload = self.payload(compress)?;
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, matches!(compress, Compress::Yes) as u8])?;
+        writer.write_all(&Self::curve_id())?;
+        writer.write_all(&(self.n_vars() as u32).to_le_bytes())?;
+        writer.write_all(&Keccak256::digest(&payload))?;
+        writer.write_all(&payload)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        HEADER_LEN + Self::payload_size(self.n_vars(), compress)
+    }
+}
+
+impl<E: Pairing, F: PrimeField> Valid for TrustedSetup<E, F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        if !basis_matches(self.n_vars(), self.encrypted_lagrange_basis.len()) {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: Pairing, F: PrimeField> CanonicalDeserialize for TrustedSetup<E, F> {
+    /// Fails with `UnexpectedFlags` if the header was written in the other mode.
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Self::read_with_mode(reader, Some(compress), validate)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::multilinear::{prover::MultilinearKZGProver, verifier::MultilinearKZGVerifier};
     use ark_bls12_381::{Bls12_381, Fr};
+    use ark_bn254::Bn254;
     use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use ark_std::UniformRand;
+    use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+
+    fn random_setup(n_vars: usize) -> TrustedSetup<Bls12_381, Fr> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let taus: Vec<Fr> = (0..n_vars).map(|_| Fr::rand(&mut rng)).collect();
+
+        TrustedSetup::new(&taus)
+    }
+
+    fn serialize(setup: &TrustedSetup<Bls12_381, Fr>, compress: Compress) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        setup.to_writer(&mut bytes, compress).unwrap();
+
+        bytes
+    }
 
     #[test]
     fn test_trusted_setup() {
@@ -58,4 +309,181 @@ mod tests {
         // Check if the encrypted lagrange basis is the expected size
         assert_eq!(setup.encrypted_lagrange_basis.len(), 1 << taus.len());
     }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let setup = random_setup(3);
+
+        for compress in [Compress::Yes, Compress::No] {
+            let bytes = serialize(&setup, compress);
+            assert_eq!(bytes.len(), setup.serialized_size(compress));
+
+            let loaded =
+                TrustedSetup::<Bls12_381, Fr>::from_reader(&bytes[..], Validate::Yes).unwrap();
+            assert_eq!(loaded.encrypted_taus, setup.encrypted_taus);
+            assert_eq!(
+                loaded.encrypted_lagrange_basis,
+                setup.encrypted_lagrange_basis
+            );
+
+            let loaded = TrustedSetup::<Bls12_381, Fr>::deserialize_with_mode(
+                &bytes[..],
+                compress,
+                Validate::Yes,
+            )
+            .unwrap();
+            assert_eq!(loaded.n_vars(), 3);
+        }
+
+        assert!(
+            serialize(&setup, Compress::Yes).len() < serialize(&setup, Compress::No).len(),
+            "Compressed points should be smaller"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_wrong_mode() {
+        let bytes = serialize(&random_setup(2), Compress::Yes);
+
+        let result = TrustedSetup::<Bls12_381, Fr>::deserialize_with_mode(
+            &bytes[..],
+            Compress::No,
+            Validate::Yes,
+        );
+        assert!(matches!(result, Err(SerializationError::UnexpectedFlags)));
+    }
+
+    #[test]
+    fn test_load_rejects_corrupted_data() {
+        let bytes = serialize(&random_setup(2), Compress::Yes);
+
+        // flipping a payload byte breaks the checksum
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(TrustedSetup::<Bls12_381, Fr>::from_reader(&corrupted[..], Validate::Yes).is_err());
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] ^= 1;
+        assert!(TrustedSetup::<Bls12_381, Fr>::from_reader(&bad_magic[..], Validate::Yes).is_err());
+
+        let truncated = &bytes[..bytes.len() - 10];
+        assert!(matches!(
+            TrustedSetup::<Bls12_381, Fr>::from_reader(truncated, Validate::Yes),
+            Err(SerializationError::IoError(err)) if err.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    // a header claiming a huge setup must fail before a payload of that size is allocated
+    #[test]
+    fn test_load_rejects_inflated_header() {
+        let bytes = serialize(&random_setup(2), Compress::Yes);
+
+        let mut too_large = bytes.clone();
+        too_large[42..46].copy_from_slice(&(MAX_N_VARS as u32 + 1).to_le_bytes());
+        assert!(matches!(
+            TrustedSetup::<Bls12_381, Fr>::from_reader(&too_large[..], Validate::Yes),
+            Err(SerializationError::InvalidData)
+        ));
+
+        // within the limit, but the stream holds far fewer bytes than it claims
+        let mut inflated = bytes.clone();
+        inflated[42..46].copy_from_slice(&(MAX_N_VARS as u32).to_le_bytes());
+        assert!(matches!(
+            TrustedSetup::<Bls12_381, Fr>::from_reader(&inflated[..], Validate::Yes),
+            Err(SerializationError::IoError(err)) if err.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_other_curve() {
+        let bytes = serialize(&random_setup(2), Compress::Yes);
+
+        let result = TrustedSetup::<Bn254, ark_bn254::Fr>::from_reader(&bytes[..], Validate::Yes);
+        assert!(matches!(result, Err(SerializationError::InvalidData)));
+    }
+
+    #[test]
+    fn test_loaded_setup_verifies_proofs() {
+        let path = std::env::temp_dir().join("kzg_test_loaded_setup_verifies_proofs.srs");
+        random_setup(3).save(&path, Compress::Yes).unwrap();
+
+        // the prover and verifier only see the file, never the taus
+        let setup = TrustedSetup::<Bls12_381, Fr>::load(&path, Validate::Yes).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut rng = StdRng::seed_from_u64(8);
+        let poly = MultilinearPoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        let openings: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+
+        let commitment = MultilinearKZGProver::<Fr, Bls12_381>::compute_commitment(
+            &poly,
+            &setup.encrypted_lagrange_basis,
+        );
+        let proof = MultilinearKZGProver::<Fr, Bls12_381>::prove(
+            &openings,
+            &poly,
+            &setup.encrypted_lagrange_basis,
+        );
+
+        assert_eq!(
+            MultilinearKZGVerifier::<Fr, Bls12_381>::verify(
+                &commitment,
+                &openings,
+                &proof,
+                &setup.encrypted_taus,
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_from_parts_checks_basis_size() {
+        let setup = random_setup(2);
+
+        let result = TrustedSetup::<Bls12_381, Fr>::from_parts(
+            setup.encrypted_taus.clone(),
+            setup.encrypted_lagrange_basis[..3].to_vec(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::SetupSizeMismatch {
+                n_vars: 2,
+                basis_len: 3
+            })
+        ));
+
+        let rebuilt = TrustedSetup::<Bls12_381, Fr>::from_parts(
+            setup.encrypted_taus.clone(),
+            setup.encrypted_lagrange_basis.clone(),
+        )
+        .unwrap();
+        assert_eq!(rebuilt.n_vars(), 2);
+    }
+
+    #[test]
+    fn test_too_many_taus_do_not_overflow() {
+        let setup = random_setup(1);
+        let encrypted_taus = vec![setup.encrypted_taus[0]; 64];
+
+        let result = TrustedSetup::<Bls12_381, Fr>::from_parts(
+            encrypted_taus.clone(),
+            setup.encrypted_lagrange_basis.clone(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::SetupSizeMismatch {
+                n_vars: 64,
+                basis_len: 2
+            })
+        ));
+
+        let unchecked = TrustedSetup::<Bls12_381, Fr>::from_parts_unchecked(
+            encrypted_taus,
+            setup.encrypted_lagrange_basis,
+        );
+        assert!(matches!(
+            unchecked.check(),
+            Err(SerializationError::InvalidData)
+        ));
+    }
 }
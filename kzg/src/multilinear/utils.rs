@@ -1,32 +1,10 @@
 use ark_ec::pairing::Pairing;
-use ark_ec::PrimeGroup;
-use ark_ff::{PrimeField, UniformRand};
-use ark_std::rand::rngs::StdRng;
-use ark_std::rand::SeedableRng;
-use std::ops::Mul;
+use ark_ec::{AffineRepr, PrimeGroup};
+use ark_ff::PrimeField;
+use sha3::{Digest, Keccak256};
 
-/// Generates an array of Lagrange basis polynomials evaluated over the boolean hypercube
-/// for a given set of `taus`.
-pub fn generate_lagrange_basis<F: PrimeField>(taus: &[F]) -> Vec<F> {
-    let n = taus.len();
-    let mut lagrange_basis = Vec::new();
-
-    let dim = 1 << n; // 2^n for the boolean hypercube
-    for i in 0..dim {
-        let mut product = F::one();
-        for j in 0..n {
-            let bit = (i >> (n - 1 - j)) & 1;
-            if bit == 1 {
-                product *= taus[j];
-            } else {
-                product *= F::one() - taus[j];
-            }
-        }
-        lagrange_basis.push(product);
-    }
-
-    lagrange_basis
-}
+/// Hashed into every candidate for the hiding generator.
+const HIDING_GENERATOR_DOMAIN: &[u8] = b"multilinear-kzg-hiding-generator";
 
 /// Encrypts the generated Lagrange basis polynomials using generator points G1.
 pub fn encrypt_lagrange_basis<E: Pairing, F: PrimeField>(lagrange_basis: &[F]) -> Vec<E::G1> {
@@ -36,23 +14,42 @@ pub fn encrypt_lagrange_basis<E: Pairing, F: PrimeField>(lagrange_basis: &[F]) -
         .collect()
 }
 
+/// Second G1 generator `H` for hiding commitments, hashed to the curve so nobody
+/// knows its discrete logarithm to G1: whoever did could open a hiding commitment to
+/// any value. Every party derives the same `H`, so it needs no setup.
+///
+/// Hashes a counter until the digest is the x coordinate of a curve point, then
+/// clears the cofactor to land in the prime order subgroup.
+pub fn generate_hiding_generator<E: Pairing>() -> E::G1 {
+    (0u64..)
+        .find_map(|counter| {
+            let bytes: Vec<u8> = (0u8..2)
+                .flat_map(|half| {
+                    let mut hasher = Keccak256::new();
+                    hasher.update(HIDING_GENERATOR_DOMAIN);
+                    hasher.update(counter.to_le_bytes());
+                    hasher.update([half]);
+                    hasher.finalize()
+                })
+                .collect();
+
+            E::G1Affine::from_random_bytes(&bytes)
+                .map(|point| point.clear_cofactor())
+                .filter(|point| !point.is_zero())
+        })
+        .unwrap()
+        .into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use ark_bls12_381::Bls12_381;
     use ark_bn254::Fr;
-
-    #[test]
-    fn test_generate_lagrange_basis() {
-        // Generate random taus for testing
-        let mut rng = StdRng::from_entropy();
-        let taus: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
-
-        let lagrange_basis = generate_lagrange_basis(&taus);
-
-        // Basic assertions to ensure the function works as expected
-        assert_eq!(lagrange_basis.len(), 1 << taus.len());
-    }
+    use ark_ec::CurveGroup;
+    use ark_ff::{UniformRand, Zero};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use polynomials::multilinear::utils::generate_lagrange_basis;
 
     #[test]
     fn test_encrypt_lagrange_basis() {
@@ -66,4 +63,19 @@ mod tests {
         // Basic assertions to ensure the function works as expected
         assert_eq!(encrypted_basis.len(), lagrange_basis.len());
     }
+
+    #[test]
+    fn test_hiding_generator() {
+        let h = generate_hiding_generator::<Bls12_381>();
+        let h_affine = h.into_affine();
+
+        assert_eq!(h, generate_hiding_generator::<Bls12_381>());
+        assert!(!h.is_zero());
+        assert_ne!(h, <Bls12_381 as Pairing>::G1::generator());
+        assert!(h_affine.is_on_curve() && h_affine.is_in_correct_subgroup_assuming_on_curve());
+
+        let h = generate_hiding_generator::<ark_bn254::Bn254>();
+        assert!(!h.is_zero());
+        assert_ne!(h, <ark_bn254::Bn254 as Pairing>::G1::generator());
+    }
 }
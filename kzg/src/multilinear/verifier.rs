@@ -1,12 +1,14 @@
+use crate::error::Error;
+use crate::msm::msm;
 use crate::multilinear::{
-    protocol::MultilinearKZGProof, prover::MultilinearKZGProver, trusted_setup::TrustedSetup,
+    protocol::{HidingMultilinearKZGProof, MultilinearKZGBatchProof, MultilinearKZGProof},
+    prover::MultilinearKZGProver,
+    verifier_key::VerifierKey,
 };
-use ark_bls12_381::{Bls12_381, G1Projective};
 use ark_ec::{pairing::Pairing, PrimeGroup};
-use ark_ff::{PrimeField, UniformRand, Zero};
-use ark_std::rand::{rngs::StdRng, SeedableRng};
-use polynomials::multilinear::multilinear_poly::{BlowUpDirection, MultilinearPoly};
-use std::{cmp::max, marker::PhantomData, ops::Mul};
+use ark_ff::{PrimeField, Zero};
+use std::marker::PhantomData;
+use sumcheck::transcript::Transcript;
 
 #[derive(Clone, Debug)]
 pub struct MultilinearKZGVerifier<F: PrimeField, E: Pairing> {
@@ -15,32 +17,194 @@ pub struct MultilinearKZGVerifier<F: PrimeField, E: Pairing> {
 }
 
 impl<F: PrimeField, E: Pairing> MultilinearKZGVerifier<F, E> {
+    /// Prepares a [`VerifierKey`] from `encrypted_taus` on every call, keep a key
+    /// around and use [`Self::verify_with_key`] when checking many proofs.
     pub fn verify(
         commitment: &E::G1,
         openings: &[F],
         proof: &MultilinearKZGProof<F, E>,
         encrypted_taus: &[E::G2],
-    ) -> bool {
-        let g1_v = E::G1::generator().mul_bigint(proof.v.into_bigint());
-        let f_tau_minus_v = *commitment - g1_v;
-        let g2_1 = E::G2::generator().mul_bigint(F::one().into_bigint());
+    ) -> Result<(), Error> {
+        Self::verify_with_key(
+            commitment,
+            openings,
+            proof,
+            &VerifierKey::new(encrypted_taus),
+        )
+    }
+
+    /// Checks `e(C - [v]G1, G2) = prod_i e(q_i, [tau_i - a_i]G2)`.
+    ///
+    /// The openings are moved to the G1 side, `e(q_i, [a_i]G2) = e([a_i]q_i, G2)`, so
+    /// the check becomes `e(C - [v]G1 + sum [a_i]q_i, G2) = prod_i e(q_i, [tau_i]G2)`
+    /// and only pairs against the prepared points of the key, in one multi-pairing.
+    pub fn verify_with_key(
+        commitment: &E::G1,
+        openings: &[F],
+        proof: &MultilinearKZGProof<F, E>,
+        verifier_key: &VerifierKey<E>,
+    ) -> Result<(), Error> {
+        Self::check_lengths(verifier_key.n_vars(), openings.len())?;
+        Self::check_lengths(verifier_key.n_vars(), proof.q_taus.len())?;
+
+        let lhs = *commitment - E::G1::generator().mul_bigint(proof.v.into_bigint())
+            + msm(&proof.q_taus, openings);
+
+        let g1_terms = std::iter::once(lhs).chain(proof.q_taus.iter().map(|q_tau| -*q_tau));
+
+        Self::check(g1_terms, verifier_key)
+    }
+
+    /// See [`Self::batch_verify_with_key`].
+    pub fn batch_verify<T: Transcript<F>>(
+        commitments: &[E::G1],
+        points: &[Vec<F>],
+        proof: &MultilinearKZGBatchProof<F, E>,
+        encrypted_taus: &[E::G2],
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        Self::batch_verify_with_key(
+            commitments,
+            points,
+            proof,
+            &VerifierKey::new(encrypted_taus),
+            transcript,
+        )
+    }
+
+    /// Verifies a batch opening with one multi-pairing. Each point `k` contributes
+    /// `e(C - [v_k]G1 + sum_i [a_ki]q_ki, G2) = prod_i e(q_ki, [tau_i]G2)` where `C` and
+    /// `v_k` are the `gamma`-combinations of the commitments and claimed evaluations.
+    /// The points are folded together with powers of a second challenge `rho`, so the
+    /// pairing count stays at `n_vars + 1` however many points are opened.
+    pub fn batch_verify_with_key<T: Transcript<F>>(
+        commitments: &[E::G1],
+        points: &[Vec<F>],
+        proof: &MultilinearKZGBatchProof<F, E>,
+        verifier_key: &VerifierKey<E>,
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        let n_vars = verifier_key.n_vars();
+
+        Self::check_lengths(points.len(), proof.evaluations.len())?;
+        Self::check_lengths(points.len(), proof.q_taus.len())?;
+        for evals in &proof.evaluations {
+            Self::check_lengths(commitments.len(), evals.len())?;
+        }
+        for (point, q_taus) in points.iter().zip(proof.q_taus.iter()) {
+            Self::check_lengths(n_vars, point.len())?;
+            Self::check_lengths(n_vars, q_taus.len())?;
+        }
+
+        let gamma = MultilinearKZGProver::<F, E>::batch_challenge(
+            commitments,
+            points,
+            &proof.evaluations,
+            transcript,
+        );
+
+        for q_tau in proof.q_taus.iter().flatten() {
+            transcript.append_point(b"q_tau", q_tau);
+        }
+        let rho = transcript.challenge_scalar(b"rho");
+
+        let gamma_powers: Vec<F> = std::iter::successors(Some(F::one()), |pow| Some(*pow * gamma))
+            .take(commitments.len())
+            .collect();
+
+        // sum_k rho^k * (C - [v_k]G1 + sum_i [a_ki]q_ki) as a single msm
+        let mut bases: Vec<E::G1> =
+            Vec::with_capacity(commitments.len() + 1 + points.len() * n_vars);
+        let mut scalars: Vec<F> = Vec::with_capacity(bases.capacity());
+
+        // rho^k * q_ki summed over k, paired against [tau_i]G2
+        let mut q_sums = vec![E::G1::zero(); n_vars];
 
-        let lhs = E::pairing(f_tau_minus_v, g2_1);
+        let mut rho_pow = F::one();
+        let mut rho_sum = F::zero();
+        let mut rho_v_sum = F::zero();
 
-        let rhs_calc = proof
-            .q_taus
-            .iter()
-            .enumerate()
-            .map(|(i, q_tau)| {
-                let tau_i = encrypted_taus[i];
-                let g2_a = E::G2::generator().mul_bigint(openings[i].into_bigint());
+        for (k, point) in points.iter().enumerate() {
+            let v_k: F = proof.evaluations[k]
+                .iter()
+                .zip(gamma_powers.iter())
+                .map(|(v, gamma_pow)| *v * gamma_pow)
+                .sum();
 
-                E::pairing(*q_tau, tau_i - g2_a)
-            })
-            .collect::<Vec<_>>();
+            rho_sum += rho_pow;
+            rho_v_sum += rho_pow * v_k;
 
-        let rhs = rhs_calc.iter().sum();
+            for (i, q_tau) in proof.q_taus[k].iter().enumerate() {
+                bases.push(*q_tau);
+                scalars.push(rho_pow * point[i]);
+                q_sums[i] += q_tau.mul_bigint(rho_pow.into_bigint());
+            }
+
+            rho_pow *= rho;
+        }
+
+        bases.extend_from_slice(commitments);
+        scalars.extend(gamma_powers.iter().map(|gamma_pow| rho_sum * gamma_pow));
+        bases.push(E::G1::generator());
+        scalars.push(-rho_v_sum);
+
+        let g1_terms = std::iter::once(msm(&bases, &scalars)).chain(q_sums.into_iter().map(|q| -q));
+
+        Self::check(g1_terms, verifier_key)
+    }
+
+    /// Verifies the opening of a hiding commitment, which is the check of
+    /// [`Self::verify_with_key`] with an extra `e(H, blinding)` term on the right.
+    pub fn verify_hiding(
+        commitment: &E::G1,
+        openings: &[F],
+        proof: &HidingMultilinearKZGProof<F, E>,
+        verifier_key: &VerifierKey<E>,
+        hiding_generator: &E::G1,
+    ) -> Result<(), Error> {
+        Self::check_lengths(verifier_key.n_vars(), openings.len())?;
+        Self::check_lengths(verifier_key.n_vars(), proof.q_taus.len())?;
+
+        let lhs = *commitment - E::G1::generator().mul_bigint(proof.v.into_bigint())
+            + msm(&proof.q_taus, openings);
+
+        let g1_terms = std::iter::once(lhs)
+            .chain(proof.q_taus.iter().map(|q_tau| -*q_tau))
+            .chain(std::iter::once(-*hiding_generator))
+            .map(E::G1Prepared::from);
+        let g2_terms = std::iter::once(verifier_key.g2.clone())
+            .chain(verifier_key.encrypted_taus.iter().cloned())
+            .chain(std::iter::once(E::G2Prepared::from(proof.blinding)));
+
+        Self::pairing_result(E::multi_pairing(g1_terms, g2_terms).is_zero())
+    }
+
+    /// `e(g1_terms[0], G2) * prod_i e(g1_terms[i + 1], [tau_i]G2) == 1`, with a single
+    /// final exponentiation.
+    fn check(
+        g1_terms: impl Iterator<Item = E::G1>,
+        verifier_key: &VerifierKey<E>,
+    ) -> Result<(), Error> {
+        let g2_terms = std::iter::once(&verifier_key.g2)
+            .chain(verifier_key.encrypted_taus.iter())
+            .cloned();
+
+        Self::pairing_result(E::multi_pairing(g1_terms, g2_terms).is_zero())
+    }
+
+    fn check_lengths(expected: usize, got: usize) -> Result<(), Error> {
+        if expected != got {
+            return Err(Error::InvalidProofLength { expected, got });
+        }
+
+        Ok(())
+    }
 
-        lhs == rhs
+    fn pairing_result(holds: bool) -> Result<(), Error> {
+        if holds {
+            Ok(())
+        } else {
+            Err(Error::PairingCheckFailed)
+        }
     }
 }
@@ -0,0 +1,44 @@
+use crate::multilinear::trusted_setup::TrustedSetup;
+use ark_ec::{pairing::Pairing, PrimeGroup};
+use ark_ff::PrimeField;
+
+/// The verifier's half of the SRS with every G2 point already prepared for the
+/// Miller loop. Since the openings are moved to the G1 side of the check, these are
+/// the only G2 points a verification ever pairs with.
+#[derive(Clone, Debug)]
+pub struct VerifierKey<E: Pairing> {
+    pub g2: E::G2Prepared,
+    pub encrypted_taus: Vec<E::G2Prepared>,
+}
+
+impl<E: Pairing> VerifierKey<E> {
+    pub fn new(encrypted_taus: &[E::G2]) -> Self {
+        Self {
+            g2: E::G2Prepared::from(E::G2::generator()),
+            encrypted_taus: encrypted_taus.iter().map(E::G2Prepared::from).collect(),
+        }
+    }
+
+    pub fn from_setup<F: PrimeField>(setup: &TrustedSetup<E, F>) -> Self {
+        Self::new(&setup.encrypted_taus)
+    }
+
+    pub fn n_vars(&self) -> usize {
+        self.encrypted_taus.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr};
+
+    #[test]
+    fn test_verifier_key_from_setup() {
+        let setup = TrustedSetup::<Bls12_381, Fr>::new(&[Fr::from(2), Fr::from(3)]);
+        let key = VerifierKey::from_setup(&setup);
+
+        assert_eq!(key.n_vars(), 2);
+        assert_eq!(key.encrypted_taus.len(), setup.encrypted_taus.len());
+    }
+}
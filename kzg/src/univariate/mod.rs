@@ -0,0 +1,4 @@
+pub mod protocol;
+pub mod prover;
+pub mod trusted_setup;
+pub mod verifier;
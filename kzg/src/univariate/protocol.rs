@@ -0,0 +1,280 @@
+use crate::utils::PROOF_VERSION;
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnivariateKZGProof<F: PrimeField, E: Pairing> {
+    pub v: F,
+    pub q_tau: E::G1,
+}
+
+impl<F: PrimeField, E: Pairing> UnivariateKZGProof<F, E> {
+    pub fn new(v: F, q_tau: E::G1) -> Self {
+        Self { v, q_tau }
+    }
+}
+
+/// Opening of several polynomials at one point: one claimed evaluation per
+/// polynomial and a single quotient commitment for their random combination.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnivariateKZGBatchProof<F: PrimeField, E: Pairing> {
+    pub vs: Vec<F>,
+    pub q_tau: E::G1,
+}
+
+impl<F: PrimeField, E: Pairing> UnivariateKZGBatchProof<F, E> {
+    pub fn new(vs: Vec<F>, q_tau: E::G1) -> Self {
+        Self { vs, q_tau }
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalSerialize for UnivariateKZGProof<F, E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.v.serialize_with_mode(&mut writer, compress)?;
+        self.q_tau.serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.v.serialized_size(compress)
+            + self.q_tau.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField, E: Pairing> Valid for UnivariateKZGProof<F, E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.v.check()?;
+        self.q_tau.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalDeserialize for UnivariateKZGProof<F, E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(Self {
+            v: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            q_tau: E::G1::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalSerialize for UnivariateKZGBatchProof<F, E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.vs.serialize_with_mode(&mut writer, compress)?;
+        self.q_tau.serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.vs.serialized_size(compress)
+            + self.q_tau.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField, E: Pairing> Valid for UnivariateKZGBatchProof<F, E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.vs.check()?;
+        self.q_tau.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, E: Pairing> CanonicalDeserialize for UnivariateKZGBatchProof<F, E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let vs = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+        let q_tau = E::G1::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        Ok(Self::new(vs, q_tau))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::univariate::{
+        prover::UnivariateKZGProver, trusted_setup::TrustedSetup, verifier::UnivariateKZGVerifier,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use polynomials::univariate::univariate_poly::UnivariatePoly;
+    use sha3::Keccak256;
+    use sumcheck::fiat_shamir::FiatShamir;
+
+    fn assert_round_trip<T>(value: &T)
+    where
+        T: CanonicalSerialize + CanonicalDeserialize + PartialEq + std::fmt::Debug,
+    {
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            value.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), value.serialized_size(compress));
+            assert_eq!(
+                &T::deserialize_with_mode(bytes.as_slice(), compress, Validate::Yes).unwrap(),
+                value
+            );
+        }
+    }
+
+    fn run_kzg_protocol<E: Pairing>() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let tau = E::ScalarField::rand(&mut rng);
+        let trusted_setup = TrustedSetup::<E, E::ScalarField>::new(&tau, 16);
+
+        let poly = UnivariatePoly::new((0..17).map(|_| E::ScalarField::rand(&mut rng)).collect());
+        let opening = E::ScalarField::rand(&mut rng);
+
+        let commitment = UnivariateKZGProver::<E::ScalarField, E>::compute_commitment(
+            &poly,
+            &trusted_setup.powers_of_tau_g1,
+        );
+
+        let mut proof = UnivariateKZGProver::<E::ScalarField, E>::prove(
+            &opening,
+            &poly,
+            &trusted_setup.powers_of_tau_g1,
+        );
+
+        assert_eq!(proof.v, poly.evaluate(opening));
+        assert_round_trip(&proof);
+        assert_eq!(
+            UnivariateKZGVerifier::<E::ScalarField, E>::verify(
+                &commitment,
+                &opening,
+                &proof,
+                &trusted_setup.powers_of_tau_g2,
+            ),
+            Ok(())
+        );
+
+        // a wrong evaluation must be rejected
+        proof.v += E::ScalarField::from(1u64);
+        assert_eq!(
+            UnivariateKZGVerifier::<E::ScalarField, E>::verify(
+                &commitment,
+                &opening,
+                &proof,
+                &trusted_setup.powers_of_tau_g2,
+            ),
+            Err(Error::PairingCheckFailed)
+        );
+    }
+
+    fn run_kzg_batch_protocol<E: Pairing>() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let tau = E::ScalarField::rand(&mut rng);
+        let trusted_setup = TrustedSetup::<E, E::ScalarField>::new(&tau, 8);
+
+        let polys: Vec<UnivariatePoly<E::ScalarField>> = (1..5)
+            .map(|n| {
+                UnivariatePoly::new((0..2 * n).map(|_| E::ScalarField::rand(&mut rng)).collect())
+            })
+            .collect();
+        let commitments: Vec<E::G1> = polys
+            .iter()
+            .map(|poly| {
+                UnivariateKZGProver::<E::ScalarField, E>::compute_commitment(
+                    poly,
+                    &trusted_setup.powers_of_tau_g1,
+                )
+            })
+            .collect();
+        let opening = E::ScalarField::rand(&mut rng);
+
+        let mut transcript_p = FiatShamir::<Keccak256, E::ScalarField>::new();
+        let mut proof = UnivariateKZGProver::<E::ScalarField, E>::batch_prove(
+            &opening,
+            &polys,
+            &commitments,
+            &trusted_setup.powers_of_tau_g1,
+            &mut transcript_p,
+        );
+
+        assert_round_trip(&proof);
+
+        let mut transcript_v = FiatShamir::<Keccak256, E::ScalarField>::new();
+        assert_eq!(
+            UnivariateKZGVerifier::<E::ScalarField, E>::batch_verify(
+                &commitments,
+                &opening,
+                &proof,
+                &trusted_setup.powers_of_tau_g2,
+                &mut transcript_v,
+            ),
+            Ok(())
+        );
+
+        proof.vs[2] += E::ScalarField::from(1u64);
+        let mut transcript_v = FiatShamir::<Keccak256, E::ScalarField>::new();
+        assert_eq!(
+            UnivariateKZGVerifier::<E::ScalarField, E>::batch_verify(
+                &commitments,
+                &opening,
+                &proof,
+                &trusted_setup.powers_of_tau_g2,
+                &mut transcript_v,
+            ),
+            Err(Error::PairingCheckFailed)
+        );
+    }
+
+    #[test]
+    fn test_kzg_protocol_bls12_381() {
+        run_kzg_protocol::<Bls12_381>();
+    }
+
+    #[test]
+    fn test_kzg_protocol_bn254() {
+        run_kzg_protocol::<Bn254>();
+    }
+
+    #[test]
+    fn test_kzg_batch_protocol_bls12_381() {
+        run_kzg_batch_protocol::<Bls12_381>();
+    }
+
+    #[test]
+    fn test_kzg_batch_protocol_bn254() {
+        run_kzg_batch_protocol::<Bn254>();
+    }
+}
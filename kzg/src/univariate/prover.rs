@@ -0,0 +1,158 @@
+use crate::msm::msm;
+use crate::univariate::protocol::{UnivariateKZGBatchProof, UnivariateKZGProof};
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use polynomials::univariate::univariate_poly::UnivariatePoly;
+use std::marker::PhantomData;
+use sumcheck::transcript::Transcript;
+
+/// Opens every batch opening on the transcript.
+const BATCH_DOMAIN_SEPARATOR: &[u8] = b"univariate-kzg-batch";
+
+#[derive(Clone, Debug)]
+pub struct UnivariateKZGProver<F: PrimeField, E: Pairing> {
+    _field: PhantomData<F>,
+    _pairing: PhantomData<E>,
+}
+
+impl<F: PrimeField, E: Pairing> UnivariateKZGProver<F, E> {
+    fn evaluate_at_tau(poly: &UnivariatePoly<F>, powers_of_tau_g1: &[E::G1]) -> E::G1 {
+        assert!(
+            poly.coefficients.len() <= powers_of_tau_g1.len(),
+            "Polynomial degree exceeds the trusted setup"
+        );
+
+        msm(powers_of_tau_g1, &poly.coefficients)
+    }
+
+    /// Computes the KZG commitment `[f(tau)]G1` for a given polynomial.
+    pub fn compute_commitment(poly: &UnivariatePoly<F>, powers_of_tau_g1: &[E::G1]) -> E::G1 {
+        Self::evaluate_at_tau(poly, powers_of_tau_g1)
+    }
+
+    /// Opens the polynomial at `opening` with the quotient `(f(X) - f(z)) / (X - z)`.
+    pub fn prove(
+        opening: &F,
+        poly: &UnivariatePoly<F>,
+        powers_of_tau_g1: &[E::G1],
+    ) -> UnivariateKZGProof<F, E> {
+        // synthetic division by (X - z) leaves f(z) as the remainder,
+        // so the quotient is exactly (f(X) - f(z)) / (X - z)
+        let (quotient, v) = poly.div_by_linear(*opening);
+        let q_tau = Self::evaluate_at_tau(&quotient, powers_of_tau_g1);
+
+        UnivariateKZGProof::new(v, q_tau)
+    }
+
+    /// Opens every polynomial at the same point with a single quotient commitment,
+    /// combining them with powers of a Fiat-Shamir challenge `gamma`.
+    pub fn batch_prove<T: Transcript<F>>(
+        opening: &F,
+        polys: &[UnivariatePoly<F>],
+        commitments: &[E::G1],
+        powers_of_tau_g1: &[E::G1],
+        transcript: &mut T,
+    ) -> UnivariateKZGBatchProof<F, E> {
+        assert_eq!(
+            polys.len(),
+            commitments.len(),
+            "Every polynomial needs a commitment"
+        );
+
+        let vs: Vec<F> = polys.iter().map(|poly| poly.evaluate(*opening)).collect();
+
+        let gamma = Self::batch_challenge(opening, commitments, &vs, transcript);
+
+        let mut gamma_pow = F::one();
+        let mut combined_poly = UnivariatePoly::zero();
+        for poly in polys {
+            combined_poly = combined_poly + poly.scalar_mul(&gamma_pow);
+            gamma_pow *= gamma;
+        }
+
+        let (quotient, _) = combined_poly.div_by_linear(*opening);
+        let q_tau = Self::evaluate_at_tau(&quotient, powers_of_tau_g1);
+
+        UnivariateKZGBatchProof::new(vs, q_tau)
+    }
+
+    /// Appends the opening point, the commitments and the claimed evaluations and
+    /// draws the batching challenge. Shared with the verifier.
+    pub fn batch_challenge<T: Transcript<F>>(
+        opening: &F,
+        commitments: &[E::G1],
+        vs: &[F],
+        transcript: &mut T,
+    ) -> F {
+        transcript.domain_separator(BATCH_DOMAIN_SEPARATOR);
+        transcript.append_field(b"opening", opening);
+
+        for commitment in commitments {
+            transcript.append_point(b"commitment", commitment);
+        }
+
+        transcript.append_fields(b"evaluations", vs);
+
+        transcript.challenge_scalar(b"gamma")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::univariate::trusted_setup::TrustedSetup;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ec::PrimeGroup;
+
+    #[test]
+    fn test_compute_commitment() {
+        let tau = Fr::from(5);
+        let trusted_setup = TrustedSetup::<Bls12_381, Fr>::new(&tau, 4);
+
+        // f(X) = 3 + 2X + X^2, f(5) = 38
+        let poly = UnivariatePoly::new(vec![Fr::from(3), Fr::from(2), Fr::from(1)]);
+
+        let commitment = UnivariateKZGProver::<Fr, Bls12_381>::compute_commitment(
+            &poly,
+            &trusted_setup.powers_of_tau_g1,
+        );
+
+        assert_eq!(
+            commitment,
+            <Bls12_381 as Pairing>::G1::generator().mul_bigint(Fr::from(38).into_bigint())
+        );
+    }
+
+    #[test]
+    fn test_prove() {
+        let tau = Fr::from(5);
+        let trusted_setup = TrustedSetup::<Bls12_381, Fr>::new(&tau, 4);
+
+        // f(X) = 3 + 2X + X^2, f(2) = 11 and q(X) = (f(X) - 11) / (X - 2) = X + 4
+        let poly = UnivariatePoly::new(vec![Fr::from(3), Fr::from(2), Fr::from(1)]);
+
+        let proof = UnivariateKZGProver::<Fr, Bls12_381>::prove(
+            &Fr::from(2),
+            &poly,
+            &trusted_setup.powers_of_tau_g1,
+        );
+
+        assert_eq!(proof.v, Fr::from(11));
+        assert_eq!(
+            proof.q_tau,
+            <Bls12_381 as Pairing>::G1::generator().mul_bigint(Fr::from(9).into_bigint())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Polynomial degree exceeds the trusted setup")]
+    fn test_commitment_degree_too_large() {
+        let trusted_setup = TrustedSetup::<Bls12_381, Fr>::new(&Fr::from(5), 1);
+        let poly = UnivariatePoly::new(vec![Fr::from(3), Fr::from(2), Fr::from(1)]);
+
+        let _ = UnivariateKZGProver::<Fr, Bls12_381>::compute_commitment(
+            &poly,
+            &trusted_setup.powers_of_tau_g1,
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use ark_ec::{pairing::Pairing, PrimeGroup};
+use ark_ff::PrimeField;
+use std::marker::PhantomData;
+
+/// Powers-of-tau structured reference string for univariate KZG.
+///
+/// `powers_of_tau_g1` holds `[tau^i]G1` for `i = 0..=max_degree` and
+/// `powers_of_tau_g2` holds `[1]G2, [tau]G2`, which is all a single point opening needs.
+pub struct TrustedSetup<E: Pairing, F: PrimeField> {
+    pub powers_of_tau_g1: Vec<E::G1>,
+    pub powers_of_tau_g2: Vec<E::G2>,
+    _marker: PhantomData<F>,
+}
+
+impl<E: Pairing, F: PrimeField> TrustedSetup<E, F> {
+    pub fn new(tau: &F, max_degree: usize) -> Self {
+        let mut powers_of_tau_g1 = Vec::with_capacity(max_degree + 1);
+        let mut power = F::one();
+
+        for _ in 0..=max_degree {
+            powers_of_tau_g1.push(E::G1::generator().mul_bigint(power.into_bigint()));
+            power *= tau;
+        }
+
+        let powers_of_tau_g2 = vec![
+            E::G2::generator(),
+            E::G2::generator().mul_bigint(tau.into_bigint()),
+        ];
+
+        Self {
+            powers_of_tau_g1,
+            powers_of_tau_g2,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.powers_of_tau_g1.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+
+    #[test]
+    fn test_trusted_setup() {
+        let mut rng = StdRng::from_entropy();
+        let tau = Fr::rand(&mut rng);
+
+        let setup = TrustedSetup::<Bls12_381, Fr>::new(&tau, 7);
+
+        assert_eq!(setup.max_degree(), 7);
+        assert_eq!(setup.powers_of_tau_g1.len(), 8);
+        assert_eq!(setup.powers_of_tau_g2.len(), 2);
+        assert_eq!(
+            setup.powers_of_tau_g1[3],
+            setup.powers_of_tau_g1[2].mul_bigint(tau.into_bigint())
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::Error;
+use crate::univariate::{
+    protocol::{UnivariateKZGBatchProof, UnivariateKZGProof},
+    prover::UnivariateKZGProver,
+};
+use ark_ec::{pairing::Pairing, PrimeGroup};
+use ark_ff::{PrimeField, Zero};
+use std::marker::PhantomData;
+use sumcheck::transcript::Transcript;
+
+#[derive(Clone, Debug)]
+pub struct UnivariateKZGVerifier<F: PrimeField, E: Pairing> {
+    _field: PhantomData<F>,
+    _pairing: PhantomData<E>,
+}
+
+impl<F: PrimeField, E: Pairing> UnivariateKZGVerifier<F, E> {
+    /// Checks `e(C - [v]G1, G2) == e(q_tau, [tau]G2 - [z]G2)`.
+    pub fn verify(
+        commitment: &E::G1,
+        opening: &F,
+        proof: &UnivariateKZGProof<F, E>,
+        powers_of_tau_g2: &[E::G2],
+    ) -> Result<(), Error> {
+        Self::check(
+            commitment,
+            opening,
+            &proof.v,
+            &proof.q_tau,
+            powers_of_tau_g2,
+        )
+    }
+
+    /// Recombines the commitments and evaluations with the same challenge as the
+    /// prover and runs a single opening check on the result.
+    pub fn batch_verify<T: Transcript<F>>(
+        commitments: &[E::G1],
+        opening: &F,
+        proof: &UnivariateKZGBatchProof<F, E>,
+        powers_of_tau_g2: &[E::G2],
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        if commitments.len() != proof.vs.len() {
+            return Err(Error::InvalidProofLength {
+                expected: commitments.len(),
+                got: proof.vs.len(),
+            });
+        }
+
+        let gamma = UnivariateKZGProver::<F, E>::batch_challenge(
+            opening,
+            commitments,
+            &proof.vs,
+            transcript,
+        );
+
+        let mut gamma_pow = F::one();
+        let mut combined_commitment = E::G1::zero();
+        let mut combined_v = F::zero();
+
+        for (commitment, v) in commitments.iter().zip(proof.vs.iter()) {
+            combined_commitment += commitment.mul_bigint(gamma_pow.into_bigint());
+            combined_v += gamma_pow * v;
+            gamma_pow *= gamma;
+        }
+
+        Self::check(
+            &combined_commitment,
+            opening,
+            &combined_v,
+            &proof.q_tau,
+            powers_of_tau_g2,
+        )
+    }
+
+    fn check(
+        commitment: &E::G1,
+        opening: &F,
+        v: &F,
+        q_tau: &E::G1,
+        powers_of_tau_g2: &[E::G2],
+    ) -> Result<(), Error> {
+        if powers_of_tau_g2.len() < 2 {
+            return Err(Error::SetupTooSmall {
+                needed: 2,
+                got: powers_of_tau_g2.len(),
+            });
+        }
+
+        let g1_v = E::G1::generator().mul_bigint(v.into_bigint());
+        let g2_z = E::G2::generator().mul_bigint(opening.into_bigint());
+
+        let lhs = E::pairing(*commitment - g1_v, powers_of_tau_g2[0]);
+        let rhs = E::pairing(*q_tau, powers_of_tau_g2[1] - g2_z);
+
+        if lhs != rhs {
+            return Err(Error::PairingCheckFailed);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+use ark_serialize::CanonicalSerialize;
+
+/// Leading byte of every serialized proof, bumped whenever an encoding changes.
+pub(crate) const PROOF_VERSION: u8 = 1;
+
+/// Compressed canonical encoding of a curve point (or any serializable value),
+/// used to feed group elements into the Fiat-Shamir transcript.
+pub fn to_bytes<T: CanonicalSerialize>(item: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(item.compressed_size());
+    item.serialize_compressed(&mut bytes)
+        .expect("Serializing into a Vec cannot fail");
+
+    bytes
+}
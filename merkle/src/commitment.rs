@@ -0,0 +1,140 @@
+use crate::{
+    error::Error,
+    hasher::MerkleHasher,
+    tree::{MerkleProof, MerkleTree},
+};
+use ark_ff::PrimeField;
+use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+use std::fmt::Debug;
+
+/// A binding commitment to a vector of field elements, opened at any set of positions
+/// at once.
+///
+/// Anything that is a vector of evaluations can be committed: the `evals` of a
+/// [`MultilinearPoly`] over the hypercube, or a Reed-Solomon codeword, the evaluations
+/// of a low degree univariate polynomial over a larger domain.
+pub trait VectorCommitment<F: PrimeField>: Sized {
+    /// What the prover and the verifier share, the hasher of a Merkle tree.
+    type Parameters;
+    type Commitment: Clone + Debug + PartialEq;
+    type Proof: Clone + Debug;
+
+    /// Commits to `values`, keeping what the prover needs to open them.
+    fn commit(parameters: &Self::Parameters, values: &[F]) -> Result<Self, Error>;
+
+    /// Commits to the evaluations of `poly` over the boolean hypercube.
+    fn commit_poly(
+        parameters: &Self::Parameters,
+        poly: &MultilinearPoly<F>,
+    ) -> Result<Self, Error> {
+        Self::commit(parameters, &poly.evals)
+    }
+
+    fn commitment(&self) -> Self::Commitment;
+
+    /// The values at `indices`, in the same order, and one proof for all of them.
+    fn open(&self, indices: &[usize]) -> Result<(Vec<F>, Self::Proof), Error>;
+
+    /// Checks `values[k]` sits at `indices[k]` of the committed vector of `len` values.
+    fn verify(
+        parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        len: usize,
+        indices: &[usize],
+        values: &[F],
+        proof: &Self::Proof,
+    ) -> Result<(), Error>;
+}
+
+impl<F: PrimeField, H: MerkleHasher<F>> VectorCommitment<F> for MerkleTree<F, H> {
+    type Parameters = H;
+    type Commitment = H::Digest;
+    type Proof = MerkleProof<H::Digest>;
+
+    fn commit(hasher: &H, values: &[F]) -> Result<Self, Error> {
+        MerkleTree::new(hasher.clone(), values)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        self.root()
+    }
+
+    fn open(&self, indices: &[usize]) -> Result<(Vec<F>, MerkleProof<H::Digest>), Error> {
+        let proof = self.prove_batch(indices)?;
+        let values = indices.iter().map(|&index| self.leaves()[index]).collect();
+
+        Ok((values, proof))
+    }
+
+    fn verify(
+        hasher: &H,
+        root: &H::Digest,
+        len: usize,
+        indices: &[usize],
+        values: &[F],
+        proof: &MerkleProof<H::Digest>,
+    ) -> Result<(), Error> {
+        MerkleTree::verify_batch(hasher, root, len, indices, values, proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hasher::{Keccak256Hasher, PoseidonHasher};
+    use ark_bn254::Fr;
+    use fft::domain::Radix2Domain;
+
+    fn open_and_verify<C: VectorCommitment<Fr>>(parameters: &C::Parameters, values: &[Fr]) {
+        let committed = C::commit(parameters, values).unwrap();
+        let indices = [3, 0, values.len() - 1];
+
+        let (opened, proof) = committed.open(&indices).unwrap();
+        assert_eq!(opened, indices.map(|i| values[i]));
+        assert_eq!(
+            C::verify(
+                parameters,
+                &committed.commitment(),
+                values.len(),
+                &indices,
+                &opened,
+                &proof
+            ),
+            Ok(())
+        );
+
+        let mut wrong = opened.clone();
+        wrong[1] += Fr::from(1);
+        assert!(C::verify(
+            parameters,
+            &committed.commitment(),
+            values.len(),
+            &indices,
+            &wrong,
+            &proof
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_commit_to_multilinear_poly() {
+        let poly = MultilinearPoly::new((0..16).map(Fr::from).collect(), 4);
+
+        let tree = MerkleTree::commit_poly(&Keccak256Hasher, &poly).unwrap();
+        assert_eq!(tree.leaves(), poly.evals.as_slice());
+
+        open_and_verify::<MerkleTree<Fr, Keccak256Hasher>>(&Keccak256Hasher, &poly.evals);
+        open_and_verify::<MerkleTree<Fr, PoseidonHasher<Fr>>>(&PoseidonHasher::new(), &poly.evals);
+    }
+
+    #[test]
+    fn test_commit_to_reed_solomon_codeword() {
+        // a degree 3 polynomial evaluated over 16 points, rate 1/4
+        let mut coeffs: Vec<Fr> = (1..=4).map(Fr::from).collect();
+        coeffs.resize(16, Fr::from(0));
+        let codeword = Radix2Domain::<Fr>::new(16).unwrap().fft(&coeffs);
+
+        open_and_verify::<MerkleTree<Fr, Keccak256Hasher>>(&Keccak256Hasher, &codeword);
+        open_and_verify::<MerkleTree<Fr, PoseidonHasher<Fr>>>(&PoseidonHasher::new(), &codeword);
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// Why a Merkle tree could not be built or an inclusion proof was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Trees are built over a non-zero power of two leaves.
+    InvalidLeafCount { got: usize },
+    /// A leaf index is not below the number of leaves.
+    IndexOutOfRange { index: usize, n_leaves: usize },
+    /// The indices and the leaves claimed at them differ in number.
+    LeafCountMismatch { indices: usize, leaves: usize },
+    /// The same index is claimed with two different leaves.
+    ConflictingLeaves { index: usize },
+    /// The proof does not hold the number of siblings the indices need.
+    InvalidProofLength { expected: usize, got: usize },
+    /// The leaves and siblings hash to another root.
+    RootMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLeafCount { got } => {
+                write!(f, "expected a non-zero power of two leaves, got {got}")
+            }
+            Self::IndexOutOfRange { index, n_leaves } => {
+                write!(f, "index {index} is out of range for {n_leaves} leaves")
+            }
+            Self::LeafCountMismatch { indices, leaves } => {
+                write!(f, "{indices} indices but {leaves} leaves")
+            }
+            Self::ConflictingLeaves { index } => {
+                write!(f, "index {index} is claimed with two different leaves")
+            }
+            Self::InvalidProofLength { expected, got } => {
+                write!(f, "expected {expected} siblings, got {got}")
+            }
+            Self::RootMismatch => write!(f, "leaves do not hash to the root"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,82 @@
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use poseidon::parameters::PoseidonParameters;
+use sha3::{Digest, Keccak256};
+use std::fmt::Debug;
+
+/// How a Merkle tree hashes its leaves and nodes.
+///
+/// Leaves and nodes are hashed apart, so no leaf can ever pass for an inner node and a
+/// proof can't stop early at one. Digests serialize, so roots can go on a transcript.
+pub trait MerkleHasher<F: PrimeField>: Clone {
+    type Digest: Clone + Debug + PartialEq + CanonicalSerialize;
+
+    fn hash_leaf(&self, leaf: &F) -> Self::Digest;
+
+    fn hash_nodes(&self, left: &Self::Digest, right: &Self::Digest) -> Self::Digest;
+}
+
+/// Leaf and node prefixes of [`Keccak256Hasher`], as in RFC 6962.
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Keccak256 over `0x00 || leaf` (little-endian) and `0x01 || left || right`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Keccak256Hasher;
+
+impl<F: PrimeField> MerkleHasher<F> for Keccak256Hasher {
+    type Digest = [u8; 32];
+
+    fn hash_leaf(&self, leaf: &F) -> Self::Digest {
+        let mut hasher = Keccak256::new();
+        hasher.update([LEAF_PREFIX]);
+        hasher.update(leaf.into_bigint().to_bytes_le());
+
+        hasher.finalize().into()
+    }
+
+    fn hash_nodes(&self, left: &Self::Digest, right: &Self::Digest) -> Self::Digest {
+        let mut hasher = Keccak256::new();
+        hasher.update([NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+
+        hasher.finalize().into()
+    }
+}
+
+/// circomlib's Poseidon: `poseidon([leaf])` for leaves and `poseidon([left, right])` for
+/// nodes. The two have different widths and so different constants, which keeps them
+/// apart, and the tree is the one a circomlib or Noir circuit recomputes.
+#[derive(Clone, Debug)]
+pub struct PoseidonHasher<F: PrimeField> {
+    leaf: PoseidonParameters<F>,
+    node: PoseidonParameters<F>,
+}
+
+impl<F: PrimeField> PoseidonHasher<F> {
+    pub fn new() -> Self {
+        Self {
+            leaf: PoseidonParameters::circom(2),
+            node: PoseidonParameters::circom(3),
+        }
+    }
+}
+
+impl<F: PrimeField> Default for PoseidonHasher<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> MerkleHasher<F> for PoseidonHasher<F> {
+    type Digest = F;
+
+    fn hash_leaf(&self, leaf: &F) -> F {
+        self.leaf.hash(&[*leaf])
+    }
+
+    fn hash_nodes(&self, left: &F, right: &F) -> F {
+        self.node.hash(&[*left, *right])
+    }
+}
@@ -0,0 +1,4 @@
+pub mod commitment;
+pub mod error;
+pub mod hasher;
+pub mod tree;
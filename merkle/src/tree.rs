@@ -0,0 +1,399 @@
+use crate::{error::Error, hasher::MerkleHasher};
+use ark_ff::PrimeField;
+
+/// Siblings needed to hash a set of leaves up to the root, listed level by level from
+/// the leaves up and left to right within a level.
+///
+/// A sibling that can be computed from the opened leaves is left out, so opening `k`
+/// leaves of a tree of depth `d` costs at most `k * d` digests and usually far fewer:
+/// two neighbouring leaves share all but one of their siblings. A single leaf's proof
+/// is its authentication path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleProof<D> {
+    pub siblings: Vec<D>,
+}
+
+/// Binary Merkle tree over field elements.
+///
+/// `layers[0]` holds the leaf hashes and every layer above hashes pairs of the one
+/// below, up to the root alone in the last layer. The leaves are kept too, so the tree
+/// is all a prover needs to open them.
+#[derive(Clone, Debug)]
+pub struct MerkleTree<F: PrimeField, H: MerkleHasher<F>> {
+    hasher: H,
+    leaves: Vec<F>,
+    layers: Vec<Vec<H::Digest>>,
+}
+
+impl<F: PrimeField, H: MerkleHasher<F>> MerkleTree<F, H> {
+    pub fn new(hasher: H, leaves: &[F]) -> Result<Self, Error> {
+        if !leaves.len().is_power_of_two() {
+            return Err(Error::InvalidLeafCount { got: leaves.len() });
+        }
+
+        let mut layers = vec![leaves
+            .iter()
+            .map(|leaf| hasher.hash_leaf(leaf))
+            .collect::<Vec<_>>()];
+
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hasher.hash_nodes(&pair[0], &pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+
+        Ok(Self {
+            hasher,
+            leaves: leaves.to_vec(),
+            layers,
+        })
+    }
+
+    pub fn root(&self) -> H::Digest {
+        self.layers.last().unwrap()[0].clone()
+    }
+
+    pub fn leaves(&self) -> &[F] {
+        &self.leaves
+    }
+
+    pub fn n_leaves(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Authentication path of the leaf at `index`.
+    pub fn prove(&self, index: usize) -> Result<MerkleProof<H::Digest>, Error> {
+        self.prove_batch(&[index])
+    }
+
+    /// One proof for the leaves at every index, in any order and with repeats.
+    pub fn prove_batch(&self, indices: &[usize]) -> Result<MerkleProof<H::Digest>, Error> {
+        let mut known = sorted_indices(indices, self.n_leaves())?;
+        let mut siblings = Vec::with_capacity(sibling_count(&known, self.depth()));
+
+        for layer in &self.layers[..self.depth()] {
+            let mut i = 0;
+            let mut parents = Vec::with_capacity(known.len());
+
+            while i < known.len() {
+                let index = known[i];
+                if index % 2 == 0 && known.get(i + 1) == Some(&(index + 1)) {
+                    i += 1;
+                } else {
+                    siblings.push(layer[index ^ 1].clone());
+                }
+
+                parents.push(index / 2);
+                i += 1;
+            }
+
+            known = parents;
+        }
+
+        Ok(MerkleProof { siblings })
+    }
+
+    /// Checks the leaf at `index` of a tree of `n_leaves` leaves against `root`.
+    pub fn verify(
+        hasher: &H,
+        root: &H::Digest,
+        n_leaves: usize,
+        index: usize,
+        leaf: &F,
+        proof: &MerkleProof<H::Digest>,
+    ) -> Result<(), Error> {
+        Self::verify_batch(
+            hasher,
+            root,
+            n_leaves,
+            &[index],
+            std::slice::from_ref(leaf),
+            proof,
+        )
+    }
+
+    /// Checks `leaves[k]` is at `indices[k]` for every `k` in a tree of `n_leaves`
+    /// leaves, by hashing them up to the root with the siblings of a
+    /// [`Self::prove_batch`] proof.
+    pub fn verify_batch(
+        hasher: &H,
+        root: &H::Digest,
+        n_leaves: usize,
+        indices: &[usize],
+        leaves: &[F],
+        proof: &MerkleProof<H::Digest>,
+    ) -> Result<(), Error> {
+        if !n_leaves.is_power_of_two() {
+            return Err(Error::InvalidLeafCount { got: n_leaves });
+        }
+        if indices.len() != leaves.len() {
+            return Err(Error::LeafCountMismatch {
+                indices: indices.len(),
+                leaves: leaves.len(),
+            });
+        }
+
+        let mut nodes: Vec<(usize, F)> = indices
+            .iter()
+            .copied()
+            .zip(leaves.iter().copied())
+            .collect();
+        nodes.sort_by_key(|(index, _)| *index);
+        nodes.dedup();
+        if let Some(pair) = nodes.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(Error::ConflictingLeaves { index: pair[0].0 });
+        }
+
+        let known = sorted_indices(indices, n_leaves)?;
+        let depth = n_leaves.ilog2() as usize;
+        let expected = sibling_count(&known, depth);
+        if proof.siblings.len() != expected {
+            return Err(Error::InvalidProofLength {
+                expected,
+                got: proof.siblings.len(),
+            });
+        }
+
+        let mut nodes: Vec<(usize, H::Digest)> = nodes
+            .iter()
+            .map(|(index, leaf)| (*index, hasher.hash_leaf(leaf)))
+            .collect();
+        let mut siblings = proof.siblings.iter();
+
+        for _ in 0..depth {
+            let mut i = 0;
+            let mut parents = Vec::with_capacity(nodes.len());
+
+            while i < nodes.len() {
+                let (index, node) = &nodes[i];
+                let parent = if index % 2 == 1 {
+                    hasher.hash_nodes(siblings.next().unwrap(), node)
+                } else if nodes.get(i + 1).map(|(next, _)| *next) == Some(index + 1) {
+                    i += 1;
+                    hasher.hash_nodes(node, &nodes[i].1)
+                } else {
+                    hasher.hash_nodes(node, siblings.next().unwrap())
+                };
+
+                parents.push((index / 2, parent));
+                i += 1;
+            }
+
+            nodes = parents;
+        }
+
+        // opening no leaf at all proves nothing and needs no siblings
+        match nodes.first() {
+            Some((_, node)) if node != root => Err(Error::RootMismatch),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `indices` sorted, without repeats, and checked against the number of leaves.
+fn sorted_indices(indices: &[usize], n_leaves: usize) -> Result<Vec<usize>, Error> {
+    if let Some(&index) = indices.iter().find(|&&index| index >= n_leaves) {
+        return Err(Error::IndexOutOfRange { index, n_leaves });
+    }
+
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    Ok(sorted)
+}
+
+/// Number of siblings a proof for the sorted, distinct `indices` holds.
+fn sibling_count(indices: &[usize], depth: usize) -> usize {
+    let mut known = indices.to_vec();
+    let mut count = 0;
+
+    for _ in 0..depth {
+        let before = known.len();
+        known.dedup_by_key(|index| *index / 2);
+        // every pair that collapsed had both children known, the rest need a sibling
+        count += 2 * known.len() - before;
+        known.iter_mut().for_each(|index| *index /= 2);
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hasher::{Keccak256Hasher, PoseidonHasher};
+    use ark_bn254::Fr;
+
+    fn leaves(n: u64) -> Vec<Fr> {
+        (0..n).map(|i| Fr::from(i * i + 1)).collect()
+    }
+
+    fn check_every_leaf<H: MerkleHasher<Fr>>(hasher: H) {
+        let leaves = leaves(8);
+        let tree = MerkleTree::new(hasher.clone(), &leaves).unwrap();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.prove(index).unwrap();
+            assert_eq!(proof.siblings.len(), 3);
+            assert_eq!(
+                MerkleTree::verify(&hasher, &tree.root(), 8, index, leaf, &proof),
+                Ok(())
+            );
+
+            // the same path does not open another leaf or another position
+            assert_eq!(
+                MerkleTree::verify(
+                    &hasher,
+                    &tree.root(),
+                    8,
+                    index,
+                    &(*leaf + Fr::from(1)),
+                    &proof
+                ),
+                Err(Error::RootMismatch)
+            );
+            assert_eq!(
+                MerkleTree::verify(&hasher, &tree.root(), 8, index ^ 1, leaf, &proof),
+                Err(Error::RootMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proofs() {
+        check_every_leaf(Keccak256Hasher);
+        check_every_leaf(PoseidonHasher::new());
+    }
+
+    #[test]
+    fn test_single_leaf_tree() {
+        let tree = MerkleTree::new(Keccak256Hasher, &[Fr::from(7)]).unwrap();
+        let proof = tree.prove(0).unwrap();
+
+        assert_eq!(tree.depth(), 0);
+        assert!(proof.siblings.is_empty());
+        assert_eq!(
+            MerkleTree::verify(&Keccak256Hasher, &tree.root(), 1, 0, &Fr::from(7), &proof),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_poseidon_root_matches_circomlib() {
+        let hasher = PoseidonHasher::<Fr>::new();
+        let tree = MerkleTree::new(hasher.clone(), &[Fr::from(1), Fr::from(2)]).unwrap();
+
+        let (l, r) = (
+            hasher.hash_leaf(&Fr::from(1)),
+            hasher.hash_leaf(&Fr::from(2)),
+        );
+        assert_eq!(
+            tree.root(),
+            poseidon::parameters::PoseidonParameters::circom(3).hash(&[l, r])
+        );
+    }
+
+    #[test]
+    fn test_batch_proofs_compress_shared_paths() {
+        let leaves = leaves(16);
+        let tree = MerkleTree::new(Keccak256Hasher, &leaves).unwrap();
+        let root = tree.root();
+
+        for (indices, expected) in [
+            (vec![0, 1], 3),        // neighbours share everything but each other
+            (vec![0, 1, 2, 3], 2),  // a whole subtree of four
+            (vec![0, 15], 6),       // opposite ends share only the root
+            (vec![5, 3, 5, 12], 7), // any order, repeats dropped
+            ((0..16).collect(), 0), // every leaf, the root is computed outright
+        ] {
+            let proof = tree.prove_batch(&indices).unwrap();
+            assert_eq!(proof.siblings.len(), expected, "{indices:?}");
+
+            let claimed: Vec<Fr> = indices.iter().map(|&i| leaves[i]).collect();
+            assert_eq!(
+                MerkleTree::verify_batch(&Keccak256Hasher, &root, 16, &indices, &claimed, &proof),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_proof_rejections() {
+        let leaves = leaves(16);
+        let tree = MerkleTree::new(Keccak256Hasher, &leaves).unwrap();
+        let root = tree.root();
+        let indices = [2, 9];
+        let claimed = [leaves[2], leaves[9]];
+        let proof = tree.prove_batch(&indices).unwrap();
+
+        let verify = |indices: &[usize], claimed: &[Fr], proof: &MerkleProof<[u8; 32]>| {
+            MerkleTree::verify_batch(&Keccak256Hasher, &root, 16, indices, claimed, proof)
+        };
+
+        assert_eq!(verify(&indices, &claimed, &proof), Ok(()));
+        assert_eq!(
+            verify(&indices, &[leaves[2], leaves[10]], &proof),
+            Err(Error::RootMismatch)
+        );
+        assert_eq!(
+            verify(&[2, 9, 2], &[leaves[2], leaves[9], leaves[3]], &proof),
+            Err(Error::ConflictingLeaves { index: 2 })
+        );
+        assert_eq!(
+            verify(&indices, &claimed[..1], &proof),
+            Err(Error::LeafCountMismatch {
+                indices: 2,
+                leaves: 1
+            })
+        );
+        assert_eq!(
+            verify(&[2, 16], &claimed, &proof),
+            Err(Error::IndexOutOfRange {
+                index: 16,
+                n_leaves: 16
+            })
+        );
+
+        let mut short = proof.clone();
+        short.siblings.pop();
+        assert_eq!(
+            verify(&indices, &claimed, &short),
+            Err(Error::InvalidProofLength {
+                expected: 6,
+                got: 5
+            })
+        );
+
+        let mut tampered = proof;
+        tampered.siblings[0][0] ^= 1;
+        assert_eq!(
+            verify(&indices, &claimed, &tampered),
+            Err(Error::RootMismatch)
+        );
+    }
+
+    #[test]
+    fn test_rejects_trees_that_are_not_a_power_of_two() {
+        assert_eq!(
+            MerkleTree::new(Keccak256Hasher, &leaves(6)).unwrap_err(),
+            Error::InvalidLeafCount { got: 6 }
+        );
+        assert_eq!(
+            MerkleTree::new(Keccak256Hasher, &[] as &[Fr]).unwrap_err(),
+            Error::InvalidLeafCount { got: 0 }
+        );
+    }
+}
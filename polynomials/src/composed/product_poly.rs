@@ -1,6 +1,8 @@
 use crate::multilinear::multilinear_poly::MultilinearPoly;
 use ark_ff::PrimeField;
-use std::iter;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ProductPoly<F: PrimeField> {
@@ -39,22 +41,25 @@ impl<F: PrimeField> ProductPoly<F> {
     }
 
     pub fn reduce(&self) -> Vec<F> {
-        // perform element-wise product on each multilinear polynomial
         let general_poly_length = self.length();
 
-        let res = iter::repeat(())
-            .enumerate()
-            .map(|(index, _)| {
-                let mut running_idx_prod = F::one();
+        let product_at = |index: usize| -> F {
+            let mut running_idx_prod = F::one();
+
+            self.polys.iter().for_each(|poly| {
+                running_idx_prod *= poly.evals[index];
+            });
 
-                self.polys.iter().for_each(|poly| {
-                    running_idx_prod *= poly.evals[index];
-                });
+            running_idx_prod
+        };
 
-                running_idx_prod
-            })
-            .take(general_poly_length)
+        #[cfg(feature = "parallel")]
+        let res = (0..general_poly_length)
+            .into_par_iter()
+            .map(product_at)
             .collect();
+        #[cfg(not(feature = "parallel"))]
+        let res = (0..general_poly_length).map(product_at).collect();
 
         res
     }
@@ -1,6 +1,8 @@
 use crate::composed::product_poly::ProductPoly;
 use ark_ff::PrimeField;
-use std::iter;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SumPoly<F: PrimeField> {
@@ -42,19 +44,20 @@ impl<F: PrimeField> SumPoly<F> {
         let reduced_product_polys: Vec<Vec<F>> =
             self.polys.iter().map(|poly| poly.reduce()).collect();
 
-        let res = iter::repeat(())
-            .enumerate()
-            .map(|(index, _)| {
-                let mut running_idx_sum = F::zero();
+        let sum_at = |index: usize| -> F {
+            let mut running_idx_sum = F::zero();
 
-                reduced_product_polys.iter().for_each(|poly| {
-                    running_idx_sum += poly[index];
-                });
+            reduced_product_polys.iter().for_each(|poly| {
+                running_idx_sum += poly[index];
+            });
 
-                running_idx_sum
-            })
-            .take(general_poly_length)
-            .collect();
+            running_idx_sum
+        };
+
+        #[cfg(feature = "parallel")]
+        let res = (0..general_poly_length).into_par_iter().map(sum_at).collect();
+        #[cfg(not(feature = "parallel"))]
+        let res = (0..general_poly_length).map(sum_at).collect();
 
         res
     }
@@ -74,6 +77,15 @@ impl<F: PrimeField> SumPoly<F> {
     pub fn n_vars(&self) -> u32 {
         self.length().ilog2()
     }
+
+    /// Degree of the sum in each variable, i.e. the most factors of any product.
+    pub fn max_degree(&self) -> usize {
+        self.polys
+            .iter()
+            .map(|poly| poly.degree() as usize)
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 #[cfg(test)]
@@ -157,4 +169,14 @@ mod tests {
         // When x=1: 2 + 4 + 6 = 12
         assert_eq!(result, Fr::from(12));
     }
+
+    #[test]
+    fn test_max_degree() {
+        let linear = ProductPoly::new(vec![MultilinearPoly::new(vec![Fr::from(1), Fr::from(2)], 1)]);
+        let cubic = ProductPoly::new(vec![linear.polys[0].clone(); 3]);
+
+        assert_eq!(SumPoly::new(vec![linear.clone(), cubic]).max_degree(), 3);
+        assert_eq!(SumPoly::new(vec![linear]).max_degree(), 1);
+        assert_eq!(SumPoly::<Fr>::new(vec![]).max_degree(), 0);
+    }
 }
@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Why a polynomial could not be evaluated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The polynomial does not have `2^n_vars` evaluations.
+    InvalidEvalsLength { n_vars: usize, len: usize },
+    /// There is no variable `pos` to bind in a polynomial of `n_vars` variables.
+    VariableOutOfRange { pos: usize, n_vars: usize },
+    /// The point does not have one coordinate per variable, or has more values than
+    /// there are variables left to bind.
+    InvalidPointLength { expected: usize, got: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidEvalsLength { n_vars, len } => {
+                write!(
+                    f,
+                    "{len} evaluations can't be a polynomial in {n_vars} variables"
+                )
+            }
+            Self::VariableOutOfRange { pos, n_vars } => {
+                write!(f, "no variable {pos} in a polynomial of {n_vars} variables")
+            }
+            Self::InvalidPointLength { expected, got } => {
+                write!(f, "expected {expected} values, got {got}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
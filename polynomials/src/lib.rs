@@ -1,4 +1,5 @@
 pub mod univariate;
 pub mod multilinear;
 pub mod shamir_secret;
-pub mod composed;
\ No newline at end of file
+pub mod composed;
+pub mod error;
@@ -1 +1,2 @@
-pub mod multilinear_poly;
\ No newline at end of file
+pub mod multilinear_poly;
+pub mod utils;
\ No newline at end of file
@@ -1,6 +1,10 @@
+use crate::error::Error;
 use ark_ff::{BigInteger, PrimeField};
 use std::ops::Add;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MultilinearPoly<F: PrimeField> {
     pub n_vars: usize,
@@ -31,17 +35,20 @@ impl<F: PrimeField> MultilinearPoly<F> {
             );
         }
 
-        let mut new_evals = Vec::with_capacity(length / 2);
-
-        let unique_pairs_evals = Self::get_unique_pairs_evals(&self.evals, pos);
-        // dbg!(&unique_pairs_evals);
-        // dbg!(&val);
+        // the k-th pair is the k-th index with bit `pos` cleared and its partner, the
+        // same order `get_unique_pairs_evals` walks them in
+        let mask = 1 << pos;
+        let fold = |k: usize| {
+            let i = ((k >> pos) << (pos + 1)) | (k & (mask - 1));
+            let (c_i, c_pair) = (self.evals[i], self.evals[i | mask]);
 
-        new_evals.extend(unique_pairs_evals.iter().map(|(c_i, c_pair)| {
-            let res = *c_i + val * (*c_pair - c_i);
+            c_i + val * (c_pair - c_i)
+        };
 
-            res
-        }));
+        #[cfg(feature = "parallel")]
+        let new_evals: Vec<F> = (0..length / 2).into_par_iter().map(fold).collect();
+        #[cfg(not(feature = "parallel"))]
+        let new_evals: Vec<F> = (0..length / 2).map(fold).collect();
 
         MultilinearPoly::new(new_evals, self.n_vars - 1)
     }
@@ -56,6 +63,61 @@ impl<F: PrimeField> MultilinearPoly<F> {
         poly.evals[0]
     }
 
+    /// [`Self::partial_evaluate`] for polynomials that may be malformed, e.g. read from
+    /// a proof: fails instead of panicking unless the polynomial has `2^n_vars`
+    /// evaluations and a variable `pos`.
+    pub fn checked_partial_evaluate(&self, (pos, val): (usize, F)) -> Result<Self, Error> {
+        self.check_evals_len()?;
+        if pos >= self.n_vars {
+            return Err(Error::VariableOutOfRange {
+                pos,
+                n_vars: self.n_vars,
+            });
+        }
+
+        Ok(self.partial_evaluate((pos, val)))
+    }
+
+    /// Binds the top variables to `values`, `values[0]` to the top one, the way
+    /// [`Self::evaluate`] does, and returns the polynomial in the variables left.
+    pub fn bind_top_variables(&self, values: &[F]) -> Result<Self, Error> {
+        self.check_evals_len()?;
+        if values.len() > self.n_vars {
+            return Err(Error::InvalidPointLength {
+                expected: self.n_vars,
+                got: values.len(),
+            });
+        }
+
+        Ok(values.iter().fold(self.clone(), |poly, value| {
+            poly.partial_evaluate((poly.n_vars - 1, *value))
+        }))
+    }
+
+    /// [`Self::evaluate`] for polynomials that may be malformed: fails unless there is
+    /// exactly one value per variable and `2^n_vars` evaluations.
+    pub fn checked_evaluate(&self, values: &[F]) -> Result<F, Error> {
+        if values.len() != self.n_vars {
+            return Err(Error::InvalidPointLength {
+                expected: self.n_vars,
+                got: values.len(),
+            });
+        }
+
+        Ok(self.bind_top_variables(values)?.evals[0])
+    }
+
+    fn check_evals_len(&self) -> Result<(), Error> {
+        if self.n_vars >= usize::BITS as usize || self.evals.len() != 1 << self.n_vars {
+            return Err(Error::InvalidEvalsLength {
+                n_vars: self.n_vars,
+                len: self.evals.len(),
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn scalar_mul(&self, scalar: F) -> Self {
         let new_evals = self.evals.iter().map(|e| scalar * *e).collect();
 
@@ -284,6 +346,50 @@ pub mod tests {
         assert_eq!(result, Fq::from(55));
     }
 
+    #[test]
+    fn test_checked_evaluate() {
+        let poly = MultilinearPoly::new(to_field(vec![0, 0, 0, 3, 0, 0, 2, 5]), 3);
+        let point = to_field(vec![1, 5, 3]);
+
+        assert_eq!(poly.checked_evaluate(&point), Ok(poly.evaluate(point.clone())));
+        assert_eq!(
+            poly.bind_top_variables(&point[..1]),
+            Ok(poly.partial_evaluate((2, point[0])))
+        );
+
+        assert_eq!(
+            poly.checked_evaluate(&to_field(vec![1, 5, 3, 7])),
+            Err(Error::InvalidPointLength {
+                expected: 3,
+                got: 4
+            })
+        );
+        assert_eq!(
+            poly.checked_evaluate(&point[..2]),
+            Err(Error::InvalidPointLength {
+                expected: 3,
+                got: 2
+            })
+        );
+        assert_eq!(
+            poly.checked_partial_evaluate((3, point[0])),
+            Err(Error::VariableOutOfRange { pos: 3, n_vars: 3 })
+        );
+
+        let constant = MultilinearPoly::new(to_field(vec![4]), 0);
+        assert_eq!(constant.checked_evaluate(&[]), Ok(Fr::from(4)));
+        assert_eq!(
+            constant.checked_partial_evaluate((0, point[0])),
+            Err(Error::VariableOutOfRange { pos: 0, n_vars: 0 })
+        );
+
+        let malformed = MultilinearPoly::new(to_field(vec![1, 2, 3]), 2);
+        assert_eq!(
+            malformed.checked_evaluate(&point[..2]),
+            Err(Error::InvalidEvalsLength { n_vars: 2, len: 3 })
+        );
+    }
+
     #[test]
     fn test_scalar_mul() {
         let poly = MultilinearPoly::new(
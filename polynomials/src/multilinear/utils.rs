@@ -0,0 +1,49 @@
+use ark_ff::PrimeField;
+
+/// Generates an array of Lagrange basis polynomials evaluated over the boolean hypercube
+/// for a given set of `taus`, i.e. the table of `eq(b, taus)` with `taus[0]` binding the
+/// top variable.
+pub fn generate_lagrange_basis<F: PrimeField>(taus: &[F]) -> Vec<F> {
+    let n = taus.len();
+    let mut lagrange_basis = Vec::new();
+
+    let dim = 1 << n; // 2^n for the boolean hypercube
+    for i in 0..dim {
+        let mut product = F::one();
+        for (j, tau) in taus.iter().enumerate() {
+            let bit = (i >> (n - 1 - j)) & 1;
+            if bit == 1 {
+                product *= tau;
+            } else {
+                product *= F::one() - tau;
+            }
+        }
+        lagrange_basis.push(product);
+    }
+
+    lagrange_basis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multilinear::multilinear_poly::MultilinearPoly;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_generate_lagrange_basis() {
+        let taus = vec![Fr::from(3), Fr::from(5), Fr::from(7)];
+        let lagrange_basis = generate_lagrange_basis(&taus);
+        assert_eq!(lagrange_basis.len(), 1 << taus.len());
+
+        // eq(b, taus) summed against the evaluations is the value at taus
+        let poly = MultilinearPoly::new((0..8).map(Fr::from).collect(), 3);
+        let value: Fr = poly
+            .evals
+            .iter()
+            .zip(lagrange_basis.iter())
+            .map(|(eval, basis)| *eval * basis)
+            .sum();
+        assert_eq!(value, poly.evaluate(taus));
+    }
+}
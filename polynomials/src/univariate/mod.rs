@@ -1,2 +1,3 @@
+pub mod subproduct_tree;
 pub mod univariate_poly;
-pub mod univariate_poly_sparse;
\ No newline at end of file
+pub mod univariate_poly_sparse;
@@ -0,0 +1,162 @@
+use crate::univariate::univariate_poly::UnivariatePoly;
+use ark_ff::{batch_inversion, PrimeField};
+
+/// Binary tree of the products `M(X) = prod (X - x_i)` over ranges of points.
+///
+/// `layers[0]` holds the linear factors `X - x_i`, every node in `layers[k + 1]`
+/// is the product of (at most) two neighbouring nodes of `layers[k]` and the last
+/// layer holds the single root `M(X)`.
+#[derive(Clone, Debug)]
+pub struct SubproductTree<F: PrimeField> {
+    pub points: Vec<F>,
+    pub layers: Vec<Vec<UnivariatePoly<F>>>,
+}
+
+impl<F: PrimeField> SubproductTree<F> {
+    pub fn new(points: &[F]) -> Self {
+        assert!(
+            !points.is_empty(),
+            "Cannot build a subproduct tree over no points"
+        );
+
+        let leaves: Vec<UnivariatePoly<F>> = points
+            .iter()
+            .map(|x| UnivariatePoly::new(vec![-*x, F::one()]))
+            .collect();
+
+        let mut layers = vec![leaves];
+
+        while layers.last().unwrap().len() > 1 {
+            let next_layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => left.clone() * right.clone(),
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+
+            layers.push(next_layer);
+        }
+
+        SubproductTree {
+            points: points.to_vec(),
+            layers,
+        }
+    }
+
+    /// The vanishing polynomial `M(X)` of all the points.
+    pub fn root(&self) -> &UnivariatePoly<F> {
+        &self.layers.last().unwrap()[0]
+    }
+
+    /// Evaluates `poly` at every point by reducing it modulo each node, top-down.
+    pub fn evaluate(&self, poly: &UnivariatePoly<F>) -> Vec<F> {
+        let mut remainders = vec![poly.div_rem(self.root()).1];
+
+        for layer in self.layers.iter().rev().skip(1) {
+            remainders = layer
+                .iter()
+                .enumerate()
+                .map(|(j, node)| remainders[j / 2].div_rem(node).1)
+                .collect();
+        }
+
+        remainders.iter().map(|rem| rem.coefficients[0]).collect()
+    }
+
+    /// Interpolates `ys` over the tree's points using
+    /// `f(X) = sum y_i / M'(x_i) * M(X) / (X - x_i)`, combined bottom-up.
+    pub fn interpolate(&self, ys: &[F]) -> UnivariatePoly<F> {
+        assert_eq!(
+            ys.len(),
+            self.points.len(),
+            "Number of points and evaluations must match"
+        );
+
+        let mut weights = self.evaluate(&self.root().derivative());
+
+        assert!(
+            weights.iter().all(|w| !w.is_zero()),
+            "Interpolation points must be distinct"
+        );
+        batch_inversion(&mut weights);
+
+        let mut combined: Vec<UnivariatePoly<F>> = weights
+            .iter()
+            .zip(ys.iter())
+            .map(|(w, y)| UnivariatePoly::new(vec![*w * y]))
+            .collect();
+
+        for layer in self.layers.iter().take(self.layers.len() - 1) {
+            combined = combined
+                .chunks(2)
+                .zip(layer.chunks(2))
+                .map(|(values, nodes)| match (values, nodes) {
+                    ([left, right], [left_node, right_node]) => {
+                        left.clone() * right_node.clone() + right.clone() * left_node.clone()
+                    }
+                    ([single], [_]) => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+
+        let mut coefficients = combined.remove(0).coefficients;
+        coefficients.resize(self.points.len(), F::zero());
+
+        UnivariatePoly::new(coefficients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn random_vec(n: usize, rng: &mut StdRng) -> Vec<Fr> {
+        (0..n).map(|_| Fr::rand(rng)).collect()
+    }
+
+    #[test]
+    fn test_root_vanishes_on_points() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let points = random_vec(13, &mut rng);
+        let tree = SubproductTree::new(&points);
+
+        assert_eq!(tree.root().degree(), 13);
+        for x in points {
+            assert_eq!(tree.root().evaluate(x), Fr::from(0));
+        }
+    }
+
+    #[test]
+    fn test_multipoint_evaluation() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let points = random_vec(37, &mut rng);
+        let poly = UnivariatePoly::new(random_vec(80, &mut rng));
+
+        let evals = SubproductTree::new(&points).evaluate(&poly);
+        let expected: Vec<Fr> = points.iter().map(|x| poly.evaluate(*x)).collect();
+
+        assert_eq!(evals, expected);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let points = random_vec(21, &mut rng);
+        let ys = random_vec(21, &mut rng);
+
+        let poly = SubproductTree::new(&points).interpolate(&ys);
+
+        assert_eq!(poly.coefficients.len(), 21);
+        for (x, y) in points.iter().zip(ys.iter()) {
+            assert_eq!(poly.evaluate(*x), *y);
+        }
+    }
+}
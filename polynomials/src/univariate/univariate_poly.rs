@@ -1,6 +1,16 @@
-use ark_ff::{PrimeField, BigInteger};
+use crate::univariate::subproduct_tree::SubproductTree;
+use ark_ff::{BigInteger, PrimeField};
+use fft::domain::Radix2Domain;
 use std::iter::{Product, Sum};
-use std::ops::{Add, Mul};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Below this many coefficients (in the smaller operand) schoolbook multiplication
+/// beats the three NTTs needed by `fft_mul`.
+pub const FFT_MUL_THRESHOLD: usize = 64;
+
+/// From this many points on, `interpolate` switches from the Lagrange basis
+/// to the subproduct tree.
+pub const FAST_INTERPOLATION_THRESHOLD: usize = 64;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct UnivariatePoly<F: PrimeField> {
@@ -26,12 +36,42 @@ impl<F: PrimeField> UnivariatePoly<F> {
     }
 
     pub fn interpolate(xs: Vec<F>, ys: Vec<F>) -> Self {
+        if xs.len() >= FAST_INTERPOLATION_THRESHOLD {
+            Self::fast_interpolate(&xs, &ys)
+        } else {
+            Self::naive_interpolate(xs, ys)
+        }
+    }
+
+    /// Lagrange interpolation, builds one basis polynomial per point: O(n^3).
+    pub fn naive_interpolate(xs: Vec<F>, ys: Vec<F>) -> Self {
         xs.iter()
             .zip(ys.iter())
             .map(|(x, y)| Self::basis(x, &xs).scalar_mul(y))
             .sum()
     }
 
+    /// Interpolation with a subproduct tree over the points: O(n log^2 n).
+    pub fn fast_interpolate(xs: &[F], ys: &[F]) -> Self {
+        assert_eq!(
+            xs.len(),
+            ys.len(),
+            "Number of points and evaluations must match"
+        );
+
+        SubproductTree::new(xs).interpolate(ys)
+    }
+
+    /// Evaluates the polynomial at every point in `xs`, going through a subproduct
+    /// tree once there are enough points to make it worthwhile.
+    pub fn multipoint_evaluate(&self, xs: &[F]) -> Vec<F> {
+        if xs.len() >= FAST_INTERPOLATION_THRESHOLD {
+            SubproductTree::new(xs).evaluate(self)
+        } else {
+            xs.iter().map(|x| self.evaluate(*x)).collect()
+        }
+    }
+
     pub fn evaluate_sum_over_boolean_hypercube(&self) -> F {
         let sum = self.evaluate(F::zero()) + self.evaluate(F::one());
 
@@ -69,12 +109,13 @@ impl<F: PrimeField> UnivariatePoly<F> {
 
         serializable_points
     }
-}
 
-impl<F: PrimeField> Mul for UnivariatePoly<F> {
-    type Output = Self;
+    /// Schoolbook multiplication: O(n * m).
+    pub fn naive_mul(&self, rhs: &Self) -> Self {
+        if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            return Self::zero();
+        }
 
-    fn mul(self, rhs: Self) -> Self::Output {
         // mul for dense polynomials
         let mut result = vec![F::zero(); (self.degree() + rhs.degree() + 1).try_into().unwrap()];
         for (i, coeff1) in self.coefficients.iter().enumerate() {
@@ -86,6 +127,302 @@ impl<F: PrimeField> Mul for UnivariatePoly<F> {
             coefficients: result,
         }
     }
+
+    /// Multiplication by pointwise products over an NTT domain: O(n log n).
+    /// Falls back to `naive_mul` if the field has no domain big enough.
+    pub fn fft_mul(&self, rhs: &Self) -> Self {
+        if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            return Self::zero();
+        }
+
+        let result_len = self.coefficients.len() + rhs.coefficients.len() - 1;
+
+        let domain = match Radix2Domain::<F>::new(result_len) {
+            Some(domain) => domain,
+            None => return self.naive_mul(rhs),
+        };
+
+        let lhs_evals = domain.fft(&self.coefficients);
+        let rhs_evals = domain.fft(&rhs.coefficients);
+
+        let product_evals: Vec<F> = lhs_evals
+            .iter()
+            .zip(rhs_evals.iter())
+            .map(|(a, b)| *a * b)
+            .collect();
+
+        let mut coefficients = domain.ifft(&product_evals);
+        coefficients.truncate(result_len);
+
+        UnivariatePoly { coefficients }
+    }
+
+    pub fn zero() -> Self {
+        UnivariatePoly::new(vec![F::zero()])
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|coeff| coeff.is_zero())
+    }
+
+    /// Drops trailing zero coefficients, keeping at least one coefficient
+    /// so that `degree()` of the zero polynomial stays 0.
+    pub fn trim(&mut self) {
+        while self.coefficients.len() > 1 && self.coefficients.last().unwrap().is_zero() {
+            self.coefficients.pop();
+        }
+
+        if self.coefficients.is_empty() {
+            self.coefficients.push(F::zero());
+        }
+    }
+
+    /// Highest non-zero coefficient, zero for the zero polynomial.
+    pub fn leading_coefficient(&self) -> F {
+        self.coefficients
+            .iter()
+            .rev()
+            .find(|coeff| !coeff.is_zero())
+            .copied()
+            .unwrap_or(F::zero())
+    }
+
+    /// Returns `(q, r)` such that `self = q * divisor + r` and `deg(r) < deg(divisor)`.
+    /// Uses long division for small inputs and Newton iteration otherwise.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let (dividend, divisor) = Self::division_operands(self, divisor);
+
+        if dividend.len() < divisor.len() {
+            return (Self::zero(), Self::new(dividend));
+        }
+
+        if divisor.len() < FFT_MUL_THRESHOLD || dividend.len() - divisor.len() < FFT_MUL_THRESHOLD {
+            Self::long_division(&dividend, &divisor)
+        } else {
+            Self::newton_division(&dividend, &divisor)
+        }
+    }
+
+    /// Schoolbook long division: O(n * m).
+    pub fn naive_div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let (dividend, divisor) = Self::division_operands(self, divisor);
+
+        if dividend.len() < divisor.len() {
+            return (Self::zero(), Self::new(dividend));
+        }
+
+        Self::long_division(&dividend, &divisor)
+    }
+
+    /// Division through the reversed polynomials, `rev(q) = rev(f) / rev(g) mod X^(m - n + 1)`,
+    /// with the power series inverse computed by Newton iteration: O(n log n).
+    pub fn fast_div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let (dividend, divisor) = Self::division_operands(self, divisor);
+
+        if dividend.len() < divisor.len() {
+            return (Self::zero(), Self::new(dividend));
+        }
+
+        Self::newton_division(&dividend, &divisor)
+    }
+
+    /// Synthetic division by `X - a`, returns the quotient and the remainder `f(a)`.
+    pub fn div_by_linear(&self, a: F) -> (Self, F) {
+        if self.coefficients.len() <= 1 {
+            return (Self::zero(), self.evaluate(a));
+        }
+
+        let coeffs = &self.coefficients;
+        let mut quotient = vec![F::zero(); coeffs.len() - 1];
+        quotient[coeffs.len() - 2] = coeffs[coeffs.len() - 1];
+
+        for i in (0..coeffs.len() - 2).rev() {
+            quotient[i] = coeffs[i + 1] + a * quotient[i + 1];
+        }
+
+        let remainder = coeffs[0] + a * quotient[0];
+
+        (UnivariatePoly::new(quotient), remainder)
+    }
+
+    /// The vanishing polynomial `Z_H(X) = X^n - 1` of the `n`-th roots of unity.
+    pub fn vanishing_poly(n: usize) -> Self {
+        let mut coefficients = vec![F::zero(); n + 1];
+        coefficients[0] = -F::one();
+        coefficients[n] = F::one();
+
+        UnivariatePoly::new(coefficients)
+    }
+
+    /// Divides by `X^n - 1` in linear time, using `X^n = 1 mod Z_H`.
+    pub fn div_by_vanishing_poly(&self, n: usize) -> (Self, Self) {
+        assert!(n > 0, "Vanishing polynomial must have a positive degree");
+
+        if self.coefficients.len() <= n {
+            return (Self::zero(), self.clone());
+        }
+
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![F::zero(); self.coefficients.len() - n];
+
+        for i in (n..remainder.len()).rev() {
+            let coeff = remainder[i];
+            quotient[i - n] = coeff;
+            remainder[i - n] += coeff;
+            remainder[i] = F::zero();
+        }
+
+        remainder.truncate(n);
+        let mut remainder = UnivariatePoly::new(remainder);
+        remainder.trim();
+
+        (UnivariatePoly::new(quotient), remainder)
+    }
+
+    /// Formal derivative `sum i * c_i * X^(i - 1)`.
+    pub fn derivative(&self) -> Self {
+        if self.coefficients.len() <= 1 {
+            return Self::zero();
+        }
+
+        UnivariatePoly::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, coeff)| F::from(i as u64) * coeff)
+                .collect(),
+        )
+    }
+
+    /// Extended Euclidean algorithm, returns `(g, s, t)` with `s * a + t * b = g`
+    /// and `g` the monic greatest common divisor of `a` and `b`.
+    pub fn xgcd(a: &Self, b: &Self) -> (Self, Self, Self) {
+        let (mut r_0, mut r_1) = (a.clone(), b.clone());
+        let (mut s_0, mut s_1) = (Self::new(vec![F::one()]), Self::zero());
+        let (mut t_0, mut t_1) = (Self::zero(), Self::new(vec![F::one()]));
+
+        while !r_1.is_zero() {
+            let (q, r) = r_0.div_rem(&r_1);
+
+            let s_2 = s_0 - q.clone() * s_1.clone();
+            let t_2 = t_0 - q * t_1.clone();
+
+            (r_0, r_1) = (r_1, r);
+            (s_0, s_1) = (s_1, s_2);
+            (t_0, t_1) = (t_1, t_2);
+        }
+
+        if r_0.is_zero() {
+            return (Self::zero(), Self::zero(), Self::zero());
+        }
+
+        let lead_inv = r_0.leading_coefficient().inverse().unwrap();
+        let mut g = r_0.scalar_mul(&lead_inv);
+        let mut s = s_0.scalar_mul(&lead_inv);
+        let mut t = t_0.scalar_mul(&lead_inv);
+        g.trim();
+        s.trim();
+        t.trim();
+
+        (g, s, t)
+    }
+
+    fn division_operands(dividend: &Self, divisor: &Self) -> (Vec<F>, Vec<F>) {
+        assert!(!divisor.is_zero(), "Division by the zero polynomial");
+
+        let mut dividend = dividend.clone();
+        let mut divisor = divisor.clone();
+        dividend.trim();
+        divisor.trim();
+
+        (dividend.coefficients, divisor.coefficients)
+    }
+
+    fn long_division(dividend: &[F], divisor: &[F]) -> (Self, Self) {
+        let mut remainder = dividend.to_vec();
+        let mut quotient = vec![F::zero(); dividend.len() - divisor.len() + 1];
+        let lead_inv = divisor.last().unwrap().inverse().unwrap();
+
+        for i in (0..quotient.len()).rev() {
+            let coeff = remainder[i + divisor.len() - 1] * lead_inv;
+            quotient[i] = coeff;
+
+            for (j, divisor_coeff) in divisor.iter().enumerate() {
+                remainder[i + j] -= coeff * divisor_coeff;
+            }
+        }
+
+        remainder.truncate(divisor.len() - 1);
+        let mut remainder = UnivariatePoly::new(remainder);
+        remainder.trim();
+
+        (UnivariatePoly::new(quotient), remainder)
+    }
+
+    fn newton_division(dividend: &[F], divisor: &[F]) -> (Self, Self) {
+        let quotient_len = dividend.len() - divisor.len() + 1;
+
+        let rev_dividend: Vec<F> = dividend.iter().rev().take(quotient_len).copied().collect();
+        let rev_divisor: Vec<F> = divisor.iter().rev().copied().collect();
+        let rev_divisor_inv = Self::inverse_mod_xk(&rev_divisor, quotient_len);
+
+        let mut quotient =
+            (UnivariatePoly::new(rev_dividend) * UnivariatePoly::new(rev_divisor_inv)).coefficients;
+        quotient.resize(quotient_len, F::zero());
+        quotient.reverse();
+        let quotient = UnivariatePoly::new(quotient);
+
+        let q_times_divisor = quotient.clone() * UnivariatePoly::new(divisor.to_vec());
+
+        let mut remainder = UnivariatePoly::new(
+            dividend
+                .iter()
+                .zip(q_times_divisor.coefficients.iter())
+                .take(divisor.len() - 1)
+                .map(|(a, b)| *a - b)
+                .collect(),
+        );
+        remainder.trim();
+
+        (quotient, remainder)
+    }
+
+    /// Computes `h` such that `g * h = 1 mod X^k`. Requires `g[0] != 0`.
+    fn inverse_mod_xk(g: &[F], k: usize) -> Vec<F> {
+        let mut h = vec![g[0].inverse().expect("Constant term must be invertible")];
+        let mut precision = 1;
+
+        while precision < k {
+            precision = (2 * precision).min(k);
+
+            let g_truncated: Vec<F> = g.iter().take(precision).copied().collect();
+            let mut g_h =
+                (UnivariatePoly::new(g_truncated) * UnivariatePoly::new(h.clone())).coefficients;
+            g_h.resize(precision, F::zero());
+
+            // h <- h * (2 - g * h)
+            let mut correction: Vec<F> = g_h.iter().map(|c| -*c).collect();
+            correction[0] += F::from(2u64);
+
+            h = (UnivariatePoly::new(h) * UnivariatePoly::new(correction)).coefficients;
+            h.resize(precision, F::zero());
+        }
+
+        h
+    }
+}
+
+impl<F: PrimeField> Mul for UnivariatePoly<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.coefficients.len().min(rhs.coefficients.len()) >= FFT_MUL_THRESHOLD {
+            self.fft_mul(&rhs)
+        } else {
+            self.naive_mul(&rhs)
+        }
+    }
 }
 
 impl<F: PrimeField> Sum for UnivariatePoly<F> {
@@ -121,10 +458,48 @@ impl<F: PrimeField> Add for UnivariatePoly<F> {
     }
 }
 
+impl<F: PrimeField> Neg for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn neg(self) -> Self::Output {
+        UnivariatePoly::new(self.coefficients.iter().map(|coeff| -*coeff).collect())
+    }
+}
+
+impl<F: PrimeField> Sub for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<F: PrimeField> Div for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).0
+    }
+}
+
+impl<F: PrimeField> Rem for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).1
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use ark_bn254::Fq;
+    use ark_bn254::{Fq, Fr};
+    use ark_ff::{FftField, Field, UniformRand, Zero};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn random_poly(n: usize, rng: &mut StdRng) -> UnivariatePoly<Fr> {
+        UnivariatePoly::new((0..n).map(|_| Fr::rand(rng)).collect())
+    }
 
     #[test]
     fn test_dense_polynomials() {
@@ -184,4 +559,181 @@ mod test {
         );
         assert_eq!(fibonacci_poly.evaluate(Fq::from(8)), Fq::from(21));
     }
+
+    #[test]
+    fn test_fft_mul_matches_naive_mul() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for (n, m) in [(1, 1), (3, 70), (64, 64), (100, 257), (513, 300)] {
+            let a = random_poly(n, &mut rng);
+            let b = random_poly(m, &mut rng);
+
+            assert_eq!(a.fft_mul(&b), a.naive_mul(&b));
+            assert_eq!(a.clone() * b.clone(), a.naive_mul(&b));
+        }
+    }
+
+    #[test]
+    fn test_mul_by_empty_poly_is_zero() {
+        let empty = UnivariatePoly::<Fr>::new(vec![]);
+        let poly = UnivariatePoly::new(vec![Fr::from(1), Fr::from(2)]);
+
+        for (a, b) in [(&empty, &empty), (&empty, &poly), (&poly, &empty)] {
+            assert_eq!(a.fft_mul(b), UnivariatePoly::zero());
+            assert_eq!(a.naive_mul(b), UnivariatePoly::zero());
+        }
+    }
+
+    #[test]
+    fn test_fast_interpolate_matches_naive_interpolate() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for n in [1, 2, 7, FAST_INTERPOLATION_THRESHOLD + 5] {
+            let xs: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+            let ys: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+
+            let naive = UnivariatePoly::naive_interpolate(xs.clone(), ys.clone());
+            let fast = UnivariatePoly::fast_interpolate(&xs, &ys);
+
+            assert_eq!(fast, naive);
+            assert_eq!(UnivariatePoly::interpolate(xs, ys), naive);
+        }
+    }
+
+    #[test]
+    fn test_multipoint_evaluate() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let poly = random_poly(150, &mut rng);
+
+        for n in [5, FAST_INTERPOLATION_THRESHOLD, 200] {
+            let xs: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+            let expected: Vec<Fr> = xs.iter().map(|x| poly.evaluate(*x)).collect();
+
+            assert_eq!(poly.multipoint_evaluate(&xs), expected);
+        }
+    }
+
+    #[test]
+    fn test_fast_interpolate_recovers_poly() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let poly = random_poly(1000, &mut rng);
+        let xs: Vec<Fr> = (0..1000u64).map(Fr::from).collect();
+
+        let ys = poly.multipoint_evaluate(&xs);
+
+        assert_eq!(UnivariatePoly::interpolate(xs, ys), poly);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let mut rng = StdRng::seed_from_u64(4);
+
+        for (n, m) in [(5, 7), (10, 3), (40, 40), (300, 100), (1000, 70)] {
+            let f = random_poly(n, &mut rng);
+            let g = random_poly(m, &mut rng);
+
+            let (q, r) = f.div_rem(&g);
+            assert!(r.degree() < g.degree());
+
+            let mut recomposed = q * g.clone() + r;
+            recomposed.trim();
+            assert_eq!(recomposed, f);
+        }
+    }
+
+    #[test]
+    fn test_fast_div_rem_matches_naive_div_rem() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let f = random_poly(400, &mut rng);
+        let g = random_poly(150, &mut rng);
+
+        assert_eq!(f.fast_div_rem(&g), f.naive_div_rem(&g));
+    }
+
+    #[test]
+    fn test_div_rem_exact() {
+        // (x^2 + 3x + 2) / (x + 1) = x + 2
+        let f = UnivariatePoly::new(vec![Fr::from(2), Fr::from(3), Fr::from(1)]);
+        let g = UnivariatePoly::new(vec![Fr::from(1), Fr::from(1)]);
+
+        assert_eq!(
+            f.clone() / g.clone(),
+            UnivariatePoly::new(vec![Fr::from(2), Fr::from(1)])
+        );
+        assert!((f % g).is_zero());
+    }
+
+    #[test]
+    fn test_div_by_linear() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let f = random_poly(33, &mut rng);
+        let a = Fr::rand(&mut rng);
+
+        let (q, r) = f.div_by_linear(a);
+        let (expected_q, expected_r) = f.div_rem(&UnivariatePoly::new(vec![-a, Fr::from(1)]));
+
+        assert_eq!(r, f.evaluate(a));
+        assert_eq!(UnivariatePoly::new(vec![r]), expected_r);
+        assert_eq!(q, expected_q);
+    }
+
+    #[test]
+    fn test_vanishing_poly() {
+        let z_h = UnivariatePoly::<Fr>::vanishing_poly(8);
+        let root = Fr::get_root_of_unity(8).unwrap();
+
+        assert_eq!(z_h.degree(), 8);
+        for i in 0..8 {
+            assert!(z_h.evaluate(root.pow([i])).is_zero());
+        }
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let f = random_poly(30, &mut rng);
+        assert_eq!(f.div_by_vanishing_poly(8), f.div_rem(&z_h));
+    }
+
+    #[test]
+    fn test_derivative() {
+        // d/dx (1 + 2x + 3x^2 + 4x^3) = 2 + 6x + 12x^2
+        let f = UnivariatePoly::new(vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)]);
+
+        assert_eq!(
+            f.derivative(),
+            UnivariatePoly::new(vec![Fr::from(2), Fr::from(6), Fr::from(12)])
+        );
+        assert_eq!(
+            UnivariatePoly::new(vec![Fr::from(9)]).derivative(),
+            UnivariatePoly::zero()
+        );
+    }
+
+    #[test]
+    fn test_xgcd() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let common = random_poly(4, &mut rng);
+        let a = common.clone() * random_poly(6, &mut rng);
+        let b = common.clone() * random_poly(9, &mut rng);
+
+        let (g, s, t) = UnivariatePoly::xgcd(&a, &b);
+
+        let mut bezout = s * a + t * b;
+        bezout.trim();
+        assert_eq!(bezout, g);
+        assert_eq!(g.leading_coefficient(), Fr::from(1));
+        assert_eq!(g.degree(), common.degree());
+        assert!((common % g).is_zero());
+    }
+
+    #[test]
+    fn test_sub_and_neg() {
+        let f = UnivariatePoly::new(vec![Fr::from(5), Fr::from(7), Fr::from(1)]);
+        let g = UnivariatePoly::new(vec![Fr::from(2), Fr::from(3)]);
+
+        assert_eq!(
+            f.clone() - g.clone(),
+            UnivariatePoly::new(vec![Fr::from(3), Fr::from(4), Fr::from(1)])
+        );
+        assert!((f.clone() - f.clone()).is_zero());
+        assert_eq!(-(-f.clone()), f);
+    }
 }
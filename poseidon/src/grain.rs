@@ -0,0 +1,96 @@
+use ark_ff::{BigInteger, PrimeField};
+
+/// Bits of state in the shift register.
+const STATE_LEN: usize = 80;
+
+/// Output bits thrown away after seeding, before anything is drawn.
+const WARMUP: usize = 160;
+
+/// The Grain LFSR the Poseidon paper's reference script draws round constants and MDS
+/// matrices from.
+///
+/// It is seeded with the field and the instance (`width`, `full_rounds`,
+/// `partial_rounds`), so every implementation that follows the script, circomlib and
+/// Noir included, derives the very same parameters from the same instance.
+#[derive(Clone, Debug)]
+pub struct Grain {
+    state: [bool; STATE_LEN],
+    head: usize,
+}
+
+impl Grain {
+    /// Seeds the register for a prime field of `field_bits` bits and the `x^alpha`
+    /// S-box, then discards the first 160 bits.
+    pub fn new(field_bits: u64, width: u64, full_rounds: u64, partial_rounds: u64) -> Self {
+        let mut state = [true; STATE_LEN];
+        let fields: [(u64, usize); 6] = [
+            // 1: prime field, 0: x^alpha S-box
+            (1, 2),
+            (0, 4),
+            (field_bits, 12),
+            (width, 12),
+            (full_rounds, 10),
+            (partial_rounds, 10),
+        ];
+
+        let mut i = 0;
+        for (value, len) in fields {
+            for bit in (0..len).rev() {
+                state[i] = (value >> bit) & 1 == 1;
+                i += 1;
+            }
+        }
+
+        let mut grain = Self { state, head: 0 };
+        for _ in 0..WARMUP {
+            grain.update();
+        }
+        grain
+    }
+
+    /// Shifts the register once and returns the bit shifted in.
+    fn update(&mut self) -> bool {
+        let bit = |i: usize| self.state[(self.head + i) % STATE_LEN];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+
+        self.state[self.head] = new_bit;
+        self.head = (self.head + 1) % STATE_LEN;
+
+        new_bit
+    }
+
+    /// Next output bit. Bits come in pairs and the second of a pair is kept only when
+    /// the first is set.
+    pub fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.update();
+            let bit = self.update();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// The next `F::MODULUS_BIT_SIZE` bits as a big-endian integer.
+    fn next_bigint<F: PrimeField>(&mut self) -> F::BigInt {
+        let bits: Vec<bool> = (0..F::MODULUS_BIT_SIZE).map(|_| self.next_bit()).collect();
+
+        F::BigInt::from_bits_be(&bits)
+    }
+
+    /// Next field element drawn by rejection, integers at or above the modulus are
+    /// skipped. This is how round constants are drawn.
+    pub fn next_field_element<F: PrimeField>(&mut self) -> F {
+        loop {
+            if let Some(element) = F::from_bigint(self.next_bigint::<F>()) {
+                return element;
+            }
+        }
+    }
+
+    /// Next field element reduced modulo the field order. This is how the points of the
+    /// MDS matrix are drawn.
+    pub fn next_field_element_mod_order<F: PrimeField>(&mut self) -> F {
+        F::from_be_bytes_mod_order(&self.next_bigint::<F>().to_bytes_be())
+    }
+}
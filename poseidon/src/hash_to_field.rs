@@ -0,0 +1,86 @@
+use crate::{parameters::PoseidonParameters, sponge::PoseidonSponge};
+use ark_ff::PrimeField;
+
+/// Hashes `message` to `count` field elements under `domain`.
+///
+/// The domain and the message are absorbed with [`PoseidonSponge::absorb_bytes`], so
+/// both are length-prefixed and no two (domain, message) pairs absorb the same
+/// elements. The outputs are squeezed straight from the permutation, so unlike hashing
+/// bytes and reducing them there is no modular bias to widen away.
+pub fn hash_to_field<F: PrimeField>(
+    parameters: &PoseidonParameters<F>,
+    domain: &[u8],
+    message: &[u8],
+    count: usize,
+) -> Vec<F> {
+    let mut sponge = PoseidonSponge::new(parameters.clone());
+    sponge.absorb_bytes(domain);
+    sponge.absorb_bytes(message);
+
+    sponge.squeeze_n(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr as BlsFr;
+    use ark_bn254::Fr;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_hash_to_field_is_domain_separated() {
+        let parameters = PoseidonParameters::<Fr>::circom(3);
+
+        assert_eq!(
+            hash_to_field(&parameters, b"zk", b"hello", 1),
+            hash_to_field(&parameters, b"zk", b"hello", 1)
+        );
+        assert_ne!(
+            hash_to_field(&parameters, b"zk", b"hello", 1),
+            hash_to_field(&parameters, b"kz", b"hello", 1)
+        );
+        // the same bytes split differently between domain and message
+        assert_ne!(
+            hash_to_field(&parameters, b"ab", b"c", 1),
+            hash_to_field(&parameters, b"a", b"bc", 1)
+        );
+        // trailing zero bytes still count
+        assert_ne!(
+            hash_to_field(&parameters, b"zk", b"hello", 1),
+            hash_to_field(&parameters, b"zk", b"hello\0", 1)
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field_squeezes_a_prefix() {
+        let parameters = PoseidonParameters::<BlsFr>::circom(3);
+
+        let elements = hash_to_field(&parameters, b"zk", &[7; 100], 5);
+        assert_eq!(elements.len(), 5);
+        assert_eq!(
+            hash_to_field(&parameters, b"zk", &[7; 100], 2),
+            elements[..2]
+        );
+    }
+
+    // Computed with a Python model of the sponge over the circomlib constants, absorbing
+    // `2, "zk", 5, "hello"` with the strings packed little-endian into one element.
+    #[test]
+    fn test_hash_to_field_known_answer() {
+        let parameters = PoseidonParameters::<Fr>::circom(3);
+
+        assert_eq!(
+            hash_to_field(&parameters, b"zk", b"hello", 2),
+            vec![
+                Fr::from_str(
+                    "10659779377739685160162735951713195352177509345180856340299032495660654719659"
+                )
+                .unwrap(),
+                Fr::from_str(
+                    "366686123296400483636416645472014764740981483673656967263671692518019361867"
+                )
+                .unwrap(),
+            ]
+        );
+    }
+}
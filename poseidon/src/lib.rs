@@ -0,0 +1,4 @@
+pub mod grain;
+pub mod hash_to_field;
+pub mod parameters;
+pub mod sponge;
@@ -0,0 +1,285 @@
+use crate::grain::Grain;
+use ark_ff::{BigInteger, PrimeField};
+
+/// Exponent of the S-box. `x^5` is a permutation of every field in the repo, whose
+/// orders minus one are all coprime to 5.
+pub const ALPHA: u64 = 5;
+
+/// Full rounds of every circomlib instance.
+pub const FULL_ROUNDS: usize = 8;
+
+/// Partial rounds of circomlib's instances, by width from 2 to 17, which are the
+/// Poseidon paper's round numbers for 128 bits of security with `x^5`.
+pub const CIRCOM_PARTIAL_ROUNDS: [usize; 16] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+/// An instance of the Poseidon permutation: the state width, the number of rounds and
+/// the constants they use.
+///
+/// Rounds are `full_rounds / 2` full rounds, then `partial_rounds` partial rounds, then
+/// the other half of the full rounds. Every round adds its constants, applies the
+/// S-box (to the whole state in a full round, to the first element in a partial one)
+/// and multiplies the state by the MDS matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoseidonParameters<F: PrimeField> {
+    pub width: usize,
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    /// `width` constants per round.
+    pub round_constants: Vec<Vec<F>>,
+    /// `width x width`, row major.
+    pub mds: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> PoseidonParameters<F> {
+    /// Derives the constants of an instance from the Grain LFSR, the way the reference
+    /// script of the Poseidon paper does, so `PoseidonParameters::<ark_bn254::Fr>::new(3,
+    /// 8, 57)` is the instance behind circomlib's and Noir's two-input Poseidon.
+    ///
+    /// The MDS matrix is the Cauchy matrix `1 / (x_i + y_j)` on the first `2 * width`
+    /// distinct points drawn. Later versions of the script also screen the matrix for
+    /// invariant subspace trails and draw again if it fails; circomlib's constants
+    /// predate that and so do these.
+    pub fn new(width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        assert!(
+            width >= 2,
+            "Poseidon needs a state of at least two elements"
+        );
+        assert!(
+            full_rounds.is_multiple_of(2),
+            "Full rounds are split evenly around the partial ones"
+        );
+        assert!(
+            alpha_is_permutation::<F>(),
+            "x^5 is not a permutation of this field"
+        );
+
+        let mut grain = Grain::new(
+            F::MODULUS_BIT_SIZE as u64,
+            width as u64,
+            full_rounds as u64,
+            partial_rounds as u64,
+        );
+
+        let round_constants = (0..full_rounds + partial_rounds)
+            .map(|_| (0..width).map(|_| grain.next_field_element()).collect())
+            .collect();
+
+        let mds = loop {
+            let points: Vec<F> = (0..2 * width)
+                .map(|_| grain.next_field_element_mod_order())
+                .collect();
+
+            let distinct = points
+                .iter()
+                .enumerate()
+                .all(|(i, p)| !points[..i].contains(p));
+            if !distinct {
+                continue;
+            }
+
+            let (xs, ys) = points.split_at(width);
+            let mds: Option<Vec<Vec<F>>> = xs
+                .iter()
+                .map(|x| ys.iter().map(|y| (*x + y).inverse()).collect())
+                .collect();
+
+            if let Some(mds) = mds {
+                break mds;
+            }
+        };
+
+        Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// circomlib's instance for `width - 1` inputs, see [`Self::hash`].
+    pub fn circom(width: usize) -> Self {
+        assert!(
+            (2..2 + CIRCOM_PARTIAL_ROUNDS.len()).contains(&width),
+            "circomlib has instances of width 2 to 17"
+        );
+
+        Self::new(width, FULL_ROUNDS, CIRCOM_PARTIAL_ROUNDS[width - 2])
+    }
+
+    /// Hashes `width - 1` elements the way circomlib's `Poseidon(n)` template and
+    /// Noir's `poseidon::bn254::hash_n` do: the inputs follow a zero in the state, and
+    /// the first element of the permuted state is the hash.
+    pub fn hash(&self, inputs: &[F]) -> F {
+        assert_eq!(
+            inputs.len(),
+            self.width - 1,
+            "The instance hashes width - 1 elements"
+        );
+
+        let mut state = Vec::with_capacity(self.width);
+        state.push(F::zero());
+        state.extend_from_slice(inputs);
+        self.permute(&mut state);
+
+        state[0]
+    }
+
+    /// Applies the permutation to `state` in place.
+    pub fn permute(&self, state: &mut [F]) {
+        assert_eq!(
+            state.len(),
+            self.width,
+            "State does not match the instance width"
+        );
+
+        let half_full = self.full_rounds / 2;
+
+        for (round, constants) in self.round_constants.iter().enumerate() {
+            for (s, c) in state.iter_mut().zip(constants) {
+                *s += c;
+            }
+
+            if round < half_full || round >= half_full + self.partial_rounds {
+                state.iter_mut().for_each(|s| *s = s.pow([ALPHA]));
+            } else {
+                state[0] = state[0].pow([ALPHA]);
+            }
+
+            let mixed: Vec<F> = self
+                .mds
+                .iter()
+                .map(|row| row.iter().zip(state.iter()).map(|(m, s)| *m * s).sum())
+                .collect();
+            state.copy_from_slice(&mixed);
+        }
+    }
+}
+
+/// `x^5` permutes the field exactly when 5 does not divide `p - 1`.
+fn alpha_is_permutation<F: PrimeField>() -> bool {
+    let p_mod_alpha = F::MODULUS
+        .to_bytes_be()
+        .iter()
+        .fold(0, |acc, byte| (acc * 256 + *byte as u64) % ALPHA);
+
+    p_mod_alpha != 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr as BlsFr;
+    use ark_bn254::Fr;
+    use std::str::FromStr;
+
+    fn fr(s: &str) -> Fr {
+        Fr::from_str(s).unwrap()
+    }
+
+    fn from_hex<F: PrimeField>(hex: &str) -> F {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+
+        F::from_be_bytes_mod_order(&bytes)
+    }
+
+    #[test]
+    fn test_grain_matches_circomlib_constants() {
+        let parameters = PoseidonParameters::<Fr>::new(3, 8, 57);
+
+        assert_eq!(parameters.round_constants.len(), 65);
+        // circomlib's C[0] and M[0][0] for t = 3
+        assert_eq!(
+            parameters.round_constants[0][0],
+            fr("6745197990210204598374042828761989596302876299545964402857411729872131034734")
+        );
+        assert_eq!(
+            parameters.mds[0][0],
+            fr("7511745149465107256748700652201246547602992235352608707588321460060273774987")
+        );
+    }
+
+    // circomlib's `poseidon([a, b])` is the first element of the permutation of
+    // `[0, a, b]`.
+    #[test]
+    fn test_permutation_matches_circomlib() {
+        let parameters = PoseidonParameters::<Fr>::new(3, 8, 57);
+
+        let mut state = [Fr::from(0), Fr::from(1), Fr::from(2)];
+        parameters.permute(&mut state);
+        assert_eq!(
+            state[0],
+            fr("7853200120776062878684798364095072458815029376092732009249414926327459813530")
+        );
+
+        // `poseidon([0, 0])`, the parent of two empty leaves in Poseidon Merkle trees
+        let mut state = [Fr::from(0); 3];
+        parameters.permute(&mut state);
+        assert_eq!(
+            state[0],
+            fr("14744269619966411208579211824598458697587494354926760081771325075741142829156")
+        );
+    }
+
+    // Test vectors of the Poseidon paper's reference implementation for the x^5
+    // permutation of `[0, 1, 2]`, `poseidonperm_x5_254_3` and `poseidonperm_x5_255_3`.
+    #[test]
+    fn test_permutation_matches_reference_implementation() {
+        let parameters = PoseidonParameters::<Fr>::new(3, 8, 57);
+        let mut state = [Fr::from(0), Fr::from(1), Fr::from(2)];
+        parameters.permute(&mut state);
+        assert_eq!(
+            state,
+            [
+                from_hex("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"),
+                from_hex("0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29"),
+                from_hex("0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c"),
+            ]
+        );
+
+        let parameters = PoseidonParameters::<BlsFr>::new(3, 8, 57);
+        let mut state = [BlsFr::from(0), BlsFr::from(1), BlsFr::from(2)];
+        parameters.permute(&mut state);
+        assert_eq!(
+            state,
+            [
+                from_hex("28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a"),
+                from_hex("51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4"),
+                from_hex("3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a"),
+            ]
+        );
+    }
+
+    // circomlibjs' `poseidon([1])`, `poseidon([1, 2])` and `poseidon([1, 2, 3, 4])`,
+    // which Noir's `hash_1`, `hash_2` and `hash_4` reproduce.
+    #[test]
+    fn test_hash_matches_circomlib() {
+        let inputs: Vec<Fr> = (1..=4).map(Fr::from).collect();
+
+        assert_eq!(
+            PoseidonParameters::circom(2).hash(&inputs[..1]),
+            fr("18586133768512220936620570745912940619677854269274689475585506675881198879027")
+        );
+        assert_eq!(
+            PoseidonParameters::circom(3).hash(&inputs[..2]),
+            fr("7853200120776062878684798364095072458815029376092732009249414926327459813530")
+        );
+        assert_eq!(
+            PoseidonParameters::circom(5).hash(&inputs),
+            fr("18821383157269793795438455681495246036402687001665670618754263018637548127333")
+        );
+    }
+
+    #[test]
+    fn test_alpha_is_a_permutation_of_the_repo_fields() {
+        assert!(alpha_is_permutation::<Fr>());
+        assert!(alpha_is_permutation::<BlsFr>());
+        assert!(alpha_is_permutation::<ark_bn254::Fq>());
+        assert!(alpha_is_permutation::<ark_bls12_381::Fq>());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::parameters::PoseidonParameters;
+use ark_ff::PrimeField;
+
+/// Where the sponge is in the rate, and whether it was last absorbing or squeezing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Absorbing { next: usize },
+    Squeezing { next: usize },
+}
+
+/// Duplex sponge over the Poseidon permutation.
+///
+/// The first element of the state is the capacity and is never read or written from
+/// outside, the other `width - 1` are the rate. Absorbed elements are added into the
+/// rate, and the state is permuted whenever the rate is full or the sponge switches
+/// from absorbing to squeezing, so every squeezed element depends on everything
+/// absorbed before it.
+#[derive(Clone, Debug)]
+pub struct PoseidonSponge<F: PrimeField> {
+    parameters: PoseidonParameters<F>,
+    state: Vec<F>,
+    mode: Mode,
+}
+
+impl<F: PrimeField> PoseidonSponge<F> {
+    pub fn new(parameters: PoseidonParameters<F>) -> Self {
+        Self {
+            state: vec![F::zero(); parameters.width],
+            parameters,
+            mode: Mode::Absorbing { next: 0 },
+        }
+    }
+
+    fn rate(&self) -> usize {
+        self.parameters.width - 1
+    }
+
+    pub fn absorb(&mut self, elements: &[F]) {
+        for element in elements {
+            let next = match self.mode {
+                Mode::Absorbing { next } if next == self.rate() => {
+                    self.parameters.permute(&mut self.state);
+                    0
+                }
+                Mode::Absorbing { next } => next,
+                Mode::Squeezing { .. } => 0,
+            };
+
+            self.state[1 + next] += element;
+            self.mode = Mode::Absorbing { next: next + 1 };
+        }
+    }
+
+    /// Absorbs `bytes` as their length followed by little-endian chunks of as many
+    /// bytes as fit below the modulus, 31 on BN254 and BLS12-381.
+    pub fn absorb_bytes(&mut self, bytes: &[u8]) {
+        let chunk_len = (F::MODULUS_BIT_SIZE as usize - 1) / 8;
+
+        self.absorb(&[F::from(bytes.len() as u64)]);
+        for chunk in bytes.chunks(chunk_len) {
+            self.absorb(&[F::from_le_bytes_mod_order(chunk)]);
+        }
+    }
+
+    pub fn squeeze(&mut self) -> F {
+        let next = match self.mode {
+            Mode::Squeezing { next } if next < self.rate() => next,
+            _ => {
+                self.parameters.permute(&mut self.state);
+                0
+            }
+        };
+
+        self.mode = Mode::Squeezing { next: next + 1 };
+        self.state[1 + next]
+    }
+
+    pub fn squeeze_n(&mut self, n: usize) -> Vec<F> {
+        (0..n).map(|_| self.squeeze()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use std::str::FromStr;
+
+    fn sponge() -> PoseidonSponge<Fr> {
+        PoseidonSponge::new(PoseidonParameters::new(3, 8, 57))
+    }
+
+    #[test]
+    fn test_squeeze_permutes_the_absorbed_state() {
+        let mut sponge = sponge();
+        sponge.absorb(&[Fr::from(1), Fr::from(2)]);
+
+        let mut state = [Fr::from(0), Fr::from(1), Fr::from(2)];
+        sponge.parameters.permute(&mut state);
+        assert_eq!(sponge.squeeze_n(2), state[1..].to_vec());
+
+        // the rate is spent, the next squeeze permutes again
+        sponge.parameters.permute(&mut state);
+        assert_eq!(sponge.squeeze(), state[1]);
+    }
+
+    #[test]
+    fn test_absorbing_more_than_the_rate() {
+        let mut a = sponge();
+        a.absorb(&[Fr::from(1), Fr::from(2), Fr::from(3)]);
+
+        let mut b = sponge();
+        b.absorb(&[Fr::from(1), Fr::from(2)]);
+        b.absorb(&[Fr::from(3)]);
+
+        let mut c = sponge();
+        c.absorb(&[Fr::from(1), Fr::from(2)]);
+
+        let challenge = a.squeeze();
+        assert_eq!(challenge, b.squeeze());
+        assert_ne!(challenge, c.squeeze());
+    }
+
+    #[test]
+    fn test_absorbing_after_squeezing_changes_the_output() {
+        let mut a = sponge();
+        a.absorb(&[Fr::from(1)]);
+        let first = a.squeeze();
+        a.absorb(&[Fr::from(1)]);
+        let second = a.squeeze();
+
+        let mut b = sponge();
+        b.absorb(&[Fr::from(1)]);
+        assert_eq!(b.squeeze(), first);
+        b.squeeze();
+        assert_ne!(b.squeeze(), second);
+    }
+
+    // Computed with a Python model of the sponge over the circomlib constants.
+    #[test]
+    fn test_sponge_known_answer() {
+        let mut sponge = sponge();
+        sponge.absorb(&[Fr::from(1), Fr::from(2), Fr::from(3)]);
+
+        assert_eq!(
+            sponge.squeeze(),
+            Fr::from_str(
+                "13768011111804142631127668044625572167973611018876333646202099751981190899146"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use ark_bn254::Fr;
+use ark_ff::UniformRand;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use criterion::{criterion_group, criterion_main, Criterion};
+use polynomials::{
+    composed::{product_poly::ProductPoly, sum_poly::SumPoly},
+    multilinear::multilinear_poly::MultilinearPoly,
+};
+use sha3::Keccak256;
+use std::hint::black_box;
+use sumcheck::fiat_shamir::FiatShamir;
+use sumcheck::sumcheck_protocol::{naive_partial_prove, naive_prove, partial_prove, prove};
+
+// Same polynomial as `get_2_20_poly` in the sumcheck tests.
+fn get_2_20_poly() -> MultilinearPoly<Fr> {
+    let n_vars = 20;
+    let evals = (0..1u64 << n_vars).map(Fr::from).collect();
+
+    MultilinearPoly::new(evals, n_vars)
+}
+
+fn prove_benchmark(c: &mut Criterion) {
+    let poly = get_2_20_poly();
+    let sum: Fr = poly.evals.iter().sum();
+
+    let mut group = c.benchmark_group("sumcheck_prove/20 variables");
+    group.sample_size(10);
+
+    group.bench_function("naive", |b| b.iter(|| black_box(naive_prove(&poly, sum))));
+
+    group.bench_function("bookkeeping", |b| b.iter(|| black_box(prove(&poly, sum))));
+
+    group.finish();
+}
+
+// A GKR shaped sum of degree 2 products.
+fn partial_prove_benchmark(c: &mut Criterion, n_vars: usize) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut random_poly = || {
+        MultilinearPoly::new(
+            (0..1 << n_vars).map(|_| Fr::rand(&mut rng)).collect(),
+            n_vars,
+        )
+    };
+
+    let poly = SumPoly::new(vec![
+        ProductPoly::new(vec![random_poly(), random_poly()]),
+        ProductPoly::new(vec![random_poly(), random_poly()]),
+    ]);
+    let sum: Fr = poly.reduce().iter().sum();
+
+    let mut group = c.benchmark_group(format!("sumcheck_partial_prove/{} variables", n_vars));
+    group.sample_size(10);
+
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            black_box(naive_partial_prove(
+                &poly,
+                sum,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+            ))
+        })
+    });
+
+    group.bench_function("bookkeeping", |b| {
+        b.iter(|| {
+            black_box(partial_prove(
+                &poly,
+                sum,
+                &mut FiatShamir::<Keccak256, Fr>::new(),
+            ))
+        })
+    });
+
+    group.finish();
+}
+
+fn sumcheck_benchmarks(c: &mut Criterion) {
+    prove_benchmark(c);
+
+    for n_vars in [12, 16] {
+        partial_prove_benchmark(c, n_vars);
+    }
+}
+
+criterion_group!(benches, sumcheck_benchmarks);
+criterion_main!(benches);
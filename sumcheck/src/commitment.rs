@@ -0,0 +1,46 @@
+use crate::transcript::Transcript;
+use ark_ff::PrimeField;
+use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+use std::fmt::Debug;
+
+/// A commitment scheme for multilinear polynomials, opened at any point.
+///
+/// Protocols that only commit and open, like GKR's input layer, can be written once
+/// over this trait and run on the pairing-based KZG or a transparent hash-based
+/// scheme alike. Points follow [`MultilinearPoly::evaluate`]: `point[0]` binds the top
+/// variable.
+pub trait MultilinearCommitment<F: PrimeField> {
+    /// What the prover commits and opens with.
+    type ProverParameters;
+    /// What the verifier checks openings with.
+    type VerifierParameters;
+    type Commitment: Clone + Debug + PartialEq;
+    /// What the prover keeps from [`Self::commit`] to open the commitment later.
+    type CommitmentData;
+    type Proof: Clone + Debug;
+    type Error: std::error::Error;
+
+    fn commit(
+        parameters: &Self::ProverParameters,
+        poly: &MultilinearPoly<F>,
+    ) -> Result<(Self::Commitment, Self::CommitmentData), Self::Error>;
+
+    /// The value of `poly` at `point`, and a proof of it.
+    fn open<T: Transcript<F>>(
+        parameters: &Self::ProverParameters,
+        poly: &MultilinearPoly<F>,
+        data: &Self::CommitmentData,
+        point: &[F],
+        transcript: &mut T,
+    ) -> Result<(F, Self::Proof), Self::Error>;
+
+    /// Checks the polynomial behind `commitment` takes `value` at `point`.
+    fn verify<T: Transcript<F>>(
+        parameters: &Self::VerifierParameters,
+        commitment: &Self::Commitment,
+        point: &[F],
+        value: F,
+        proof: &Self::Proof,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// Why a sumcheck proof was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The proof has a different number of rounds than the polynomial has variables.
+    InvalidProofLength { expected: usize, got: usize },
+    /// The round polynomial of `round` does not have one evaluation per point.
+    InvalidRoundPolyLength {
+        round: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// The round polynomial of `round` does not sum to the running claim over {0, 1}.
+    RoundSumMismatch { round: usize },
+    /// The last round's claim disagrees with the polynomial at the challenges.
+    FinalEvalMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidProofLength { expected, got } => {
+                write!(f, "expected {expected} round polynomials, got {got}")
+            }
+            Self::InvalidRoundPolyLength {
+                round,
+                expected,
+                got,
+            } => write!(
+                f,
+                "round {round} polynomial has {got} evaluations, expected {expected}"
+            ),
+            Self::RoundSumMismatch { round } => {
+                write!(f, "round {round} polynomial does not match the claimed sum")
+            }
+            Self::FinalEvalMismatch => {
+                write!(f, "final claim does not match the polynomial evaluation")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -1,10 +1,12 @@
 // implementing fiat-shamir heuristic for removing interactivity in sumcheck protocol
 use ark_ff::PrimeField;
-use ark_std::rand::rngs::StdRng;
-use ark_std::rand::SeedableRng;
 use digest::{Digest, FixedOutputReset};
 use std::marker::PhantomData;
 
+/// Bytes reduced into each challenge, twice the size of the fields in use so the
+/// reduction is statistically uniform.
+const WIDE_LEN: usize = 64;
+
 pub struct FiatShamir<T: Digest, F: PrimeField> {
     pub hasher: T,
     pub _field: PhantomData<F>,
@@ -29,12 +31,27 @@ impl<T: Digest + Default + FixedOutputReset, F: PrimeField> FiatShamir<T, F> {
         data.iter().for_each(|f| self.absorb(*f));
     }
 
+    /// Derives a challenge from everything absorbed so far.
+    ///
+    /// The digest `d` is stretched to 64 bytes as `H(d || 0) || H(d || 1) || ...` and
+    /// reduced modulo the field order, which is off uniform by at most `2^-(512 - log p)`.
+    /// `d` is then absorbed again, so consecutive squeezes differ. Nothing but the
+    /// hash is involved, so the challenges can be recomputed in any language.
     pub fn squeeze(&mut self) -> F {
-        let hash_result = self.hasher.finalize_reset();
-        let seed: [u8; 32] = hash_result.as_slice()[..32].try_into().unwrap(); // Ensure correct size (32 bytes)
-        Digest::update(&mut self.hasher, &hash_result);
-        let mut rng = StdRng::from_seed(seed);
-        F::rand(&mut rng)
+        let state = self.hasher.finalize_reset();
+
+        let mut wide = Vec::with_capacity(WIDE_LEN + state.len());
+        let mut counter = 0u8;
+        while wide.len() < WIDE_LEN {
+            Digest::update(&mut self.hasher, &state);
+            Digest::update(&mut self.hasher, [counter]);
+            wide.extend_from_slice(&self.hasher.finalize_reset());
+            counter += 1;
+        }
+
+        Digest::update(&mut self.hasher, &state);
+
+        F::from_le_bytes_mod_order(&wide[..WIDE_LEN])
     }
 
     pub fn squeeze_n(&mut self, n: usize) -> Vec<F> {
@@ -54,8 +71,10 @@ impl<T: Digest + Default + FixedOutputReset, F: PrimeField> FiatShamir<T, F> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_bn254::Fq;
+    use ark_bn254::{Fq, Fr};
+    use sha2::Sha256;
     use sha3::Keccak256;
+    use std::str::FromStr;
 
     #[test]
     fn test_fiat_shamir_instantiation() {
@@ -90,4 +109,52 @@ mod tests {
 
         assert_ne!(random_element, Fq::from(element)); // verify randomness
     }
+
+    // Computed independently, with Python's hashlib:
+    //   state = sha256(absorbed)
+    //   wide = sha256(state || 0x00) || sha256(state || 0x01)
+    //   challenge = int.from_bytes(wide, "little") % r
+    // where a second squeeze absorbs `state` alone first.
+    #[test]
+    fn test_squeeze_known_answers() {
+        let mut transcript = FiatShamir::<Sha256, Fr>::new();
+        assert_eq!(
+            transcript.squeeze(),
+            Fr::from_str(
+                "12706503635228208757894793041196422775865709178403500326311506710405936720882"
+            )
+            .unwrap()
+        );
+
+        let mut transcript = FiatShamir::<Sha256, Fr>::new();
+        transcript.absorb(b"zk");
+        assert_eq!(
+            transcript.squeeze_n(2),
+            vec![
+                Fr::from_str(
+                    "3998771691859121953157925146335013094147647653493676512824650972436839371183"
+                )
+                .unwrap(),
+                Fr::from_str(
+                    "11355215616143167412068869022994022716500943261142565008408861549127604330784"
+                )
+                .unwrap(),
+            ]
+        );
+    }
+
+    // Pinned, Keccak is what every protocol in the repo runs its transcripts on.
+    #[test]
+    fn test_squeeze_keccak_known_answer() {
+        let mut transcript = FiatShamir::<Keccak256, Fr>::new();
+        transcript.absorb(b"zk");
+
+        assert_eq!(
+            transcript.squeeze(),
+            Fr::from_str(
+                "12311726274674330839059235531711832473165199155443819590847525692287594078758"
+            )
+            .unwrap()
+        );
+    }
 }
@@ -1,3 +1,6 @@
+pub mod commitment;
+pub mod error;
 pub mod fiat_shamir;
 // pub mod schnorr_protocol;
 pub mod sumcheck_protocol;
+pub mod transcript;
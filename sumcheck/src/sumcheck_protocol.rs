@@ -1,37 +1,330 @@
+use crate::error::Error;
 use crate::fiat_shamir::FiatShamir;
-use ark_ff::{BigInteger, PrimeField};
-use polynomials::{
-    composed::sum_poly::SumPoly, multilinear::multilinear_poly::MultilinearPoly,
-    univariate::univariate_poly::UnivariatePoly,
+use crate::transcript::Transcript;
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
 };
+use polynomials::{composed::sum_poly::SumPoly, multilinear::multilinear_poly::MultilinearPoly};
 use sha3::Keccak256;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Leading byte of every serialized proof, bumped whenever the encoding changes.
+const PROOF_VERSION: u8 = 1;
+
+/// Opens every sumcheck on the transcript.
+const DOMAIN_SEPARATOR: &[u8] = b"sumcheck";
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Proof<F: PrimeField> {
-    claimed_sum: F,
-    round_polys: Vec<[F; 2]>,
+    pub claimed_sum: F,
+    pub round_polys: Vec<[F; 2]>,
 }
 
-#[derive(Clone, Debug)]
+/// Sumcheck proof over a [`SumPoly`]. Every round polynomial is sent in evaluation
+/// form, as its values at `0, 1, ..., d` where `d` is the degree of the sum.
+#[derive(Clone, Debug, PartialEq)]
 pub struct PartialProof<F: PrimeField> {
     pub initial_claimed_sum: F,
-    pub round_polys: Vec<UnivariatePoly<F>>,
+    pub round_polys: Vec<Vec<F>>,
     pub rand_challenges: Vec<F>,
 }
 
+impl<F: PrimeField> CanonicalSerialize for Proof<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.claimed_sum
+            .serialize_with_mode(&mut writer, compress)?;
+        self.round_polys
+            .serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.claimed_sum.serialized_size(compress)
+            + self.round_polys.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for Proof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.claimed_sum.check()?;
+        self.round_polys.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for Proof<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let proof = Self {
+            claimed_sum: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            round_polys: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+        if validate == Validate::Yes {
+            proof.check()?;
+        }
+
+        Ok(proof)
+    }
+}
+
+impl<F: PrimeField> CanonicalSerialize for PartialProof<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        PROOF_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.initial_claimed_sum
+            .serialize_with_mode(&mut writer, compress)?;
+        self.round_polys
+            .serialize_with_mode(&mut writer, compress)?;
+        self.rand_challenges
+            .serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        PROOF_VERSION.serialized_size(compress)
+            + self.initial_claimed_sum.serialized_size(compress)
+            + self.round_polys.serialized_size(compress)
+            + self.rand_challenges.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for PartialProof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.initial_claimed_sum.check()?;
+        self.round_polys.check()?;
+        self.rand_challenges.check()?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for PartialProof<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != PROOF_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let proof = Self {
+            initial_claimed_sum: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            round_polys: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            rand_challenges: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+        if validate == Validate::Yes {
+            proof.check()?;
+        }
+
+        Ok(proof)
+    }
+}
+
+/// Proves `claimed_sum` is the sum of `poly` over the boolean hypercube.
+///
+/// The evaluations are kept in a bookkeeping table that is folded in place with each
+/// challenge, so a round costs one pass over the table and the whole proof is linear
+/// in its size.
 pub fn prove<F: PrimeField>(poly: &MultilinearPoly<F>, claimed_sum: F) -> Proof<F> {
-    let mut round_polys: Vec<[F; 2]> = vec![];
+    prove_with_transcript(poly, claimed_sum, &mut FiatShamir::<Keccak256, F>::new())
+}
 
-    let mut transcript = FiatShamir::<Keccak256, F>::new();
+/// [`prove`] on a transcript of the caller's choosing, which the verifier has to run
+/// [`verify_with_transcript`] on.
+pub fn prove_with_transcript<F: PrimeField, T: Transcript<F>>(
+    poly: &MultilinearPoly<F>,
+    claimed_sum: F,
+    transcript: &mut T,
+) -> Proof<F> {
+    let mut round_polys: Vec<[F; 2]> = Vec::with_capacity(poly.n_vars);
+
+    transcript.domain_separator(DOMAIN_SEPARATOR);
+    transcript.append_poly(b"poly", poly);
+    transcript.append_field(b"claimed_sum", &claimed_sum);
+
+    let mut table = poly.evals.clone();
+
+    for _ in 0..poly.n_vars {
+        let (low, high) = table.split_at(table.len() / 2);
+        #[cfg(feature = "parallel")]
+        let round_poly: [F; 2] = [low.par_iter().sum(), high.par_iter().sum()];
+        #[cfg(not(feature = "parallel"))]
+        let round_poly: [F; 2] = [low.iter().sum(), high.iter().sum()];
+
+        transcript.append_fields(b"round_poly", &round_poly);
+        round_polys.push(round_poly);
+
+        let challenge = transcript.challenge_scalar(b"challenge");
+
+        fold_top_variable(&mut table, challenge);
+    }
+
+    Proof {
+        claimed_sum,
+        round_polys,
+    }
+}
+
+/// Proves `initial_claimed_sum` is the sum of `poly` over the boolean hypercube. Each
+/// round polynomial has the degree of the sum, so it is evaluated at
+/// `poly.max_degree() + 1` points, and never fewer than the two needed for the sum
+/// over {0, 1}.
+///
+/// Every factor of every product gets a bookkeeping table. A round walks the tables
+/// once, extending each factor from its values at 0 and 1 to the other points by
+/// repeated addition, then folds every table in place with the challenge.
+pub fn partial_prove<F: PrimeField, T: Transcript<F>>(
+    poly: &SumPoly<F>,
+    initial_claimed_sum: F,
+    transcript: &mut T,
+) -> PartialProof<F> {
+    let n_vars = poly.n_vars() as usize;
+    let degree = poly.max_degree().max(1);
+    let mut round_polys: Vec<Vec<F>> = Vec::with_capacity(n_vars);
+    let mut rand_challenges: Vec<F> = Vec::with_capacity(n_vars);
+
+    transcript.domain_separator(DOMAIN_SEPARATOR);
+    transcript.append_field(b"claimed_sum", &initial_claimed_sum);
 
-    transcript.absorb(
-        poly.evals
-            .iter()
-            .flat_map(|f| f.into_bigint().to_bytes_le())
-            .collect::<Vec<_>>()
-            .as_slice(),
+    let mut tables: Vec<Vec<Vec<F>>> = poly
+        .polys
+        .iter()
+        .map(|product| product.polys.iter().map(|f| f.evals.clone()).collect())
+        .collect();
+
+    for _ in 0..n_vars {
+        let round_poly = round_evaluations(&tables, degree);
+        transcript.append_fields(b"round_poly", &round_poly);
+
+        round_polys.push(round_poly);
+
+        let challenge = transcript.challenge_scalar(b"challenge");
+
+        rand_challenges.push(challenge);
+
+        for table in tables.iter_mut().flatten() {
+            fold_top_variable(table, challenge);
+        }
+    }
+
+    PartialProof {
+        initial_claimed_sum,
+        round_polys,
+        rand_challenges,
+    }
+}
+
+/// Values at `0, 1, ..., degree` of the sum of the products of `tables` with the top
+/// variable left free and every other one summed over the hypercube. With the
+/// `parallel` feature the hypercube is split across threads, field addition being
+/// exact the sums do not depend on how.
+pub fn round_evaluations<F: PrimeField>(tables: &[Vec<Vec<F>>], degree: usize) -> Vec<F> {
+    let half = tables
+        .iter()
+        .flatten()
+        .next()
+        .map_or(0, |table| table.len() / 2);
+    let max_factors = tables.iter().map(Vec::len).max().unwrap_or(0);
+
+    // running round polynomial with scratch space for the factors at one point
+    let init = || {
+        (
+            vec![F::zero(); degree + 1],
+            vec![F::zero(); max_factors],
+            vec![F::zero(); max_factors],
+        )
+    };
+
+    let add_point = |(mut round_poly, mut values, mut steps): (Vec<F>, Vec<F>, Vec<F>),
+                     i: usize| {
+        for product in tables {
+            let (values, steps) = (&mut values[..product.len()], &mut steps[..product.len()]);
+
+            for (j, table) in product.iter().enumerate() {
+                values[j] = table[i];
+                steps[j] = table[i + half] - table[i];
+            }
+
+            for eval in round_poly.iter_mut() {
+                *eval += values.iter().product::<F>();
+                for (value, step) in values.iter_mut().zip(steps.iter()) {
+                    *value += step;
+                }
+            }
+        }
+
+        (round_poly, values, steps)
+    };
+
+    #[cfg(feature = "parallel")]
+    let (round_poly, _, _) = (0..half).into_par_iter().fold(init, add_point).reduce(
+        init,
+        |(mut left, values, steps), (right, _, _)| {
+            for (l, r) in left.iter_mut().zip(right) {
+                *l += r;
+            }
+
+            (left, values, steps)
+        },
     );
+    #[cfg(not(feature = "parallel"))]
+    let (round_poly, _, _) = (0..half).fold(init(), add_point);
 
-    transcript.absorb(claimed_sum.into_bigint().to_bytes_le().as_slice());
+    round_poly
+}
+
+/// Binds the top variable of a table to `r`, in the layout of
+/// [`MultilinearPoly::partial_evaluate`]: `t[i] = t[i] + r * (t[i + half] - t[i])`.
+pub fn fold_top_variable<F: PrimeField>(table: &mut Vec<F>, r: F) {
+    let half = table.len() / 2;
+    let (low, high) = table.split_at_mut(half);
+
+    #[cfg(feature = "parallel")]
+    low.par_iter_mut()
+        .zip(high.par_iter())
+        .for_each(|(low, high)| *low += r * (*high - *low));
+    #[cfg(not(feature = "parallel"))]
+    for (low, high) in low.iter_mut().zip(high.iter()) {
+        *low += r * (*high - *low);
+    }
+
+    table.truncate(half);
+}
+
+/// [`prove`] by partially evaluating a copy of the polynomial twice per round. Kept as
+/// the reference the bookkeeping prover is tested and benchmarked against.
+pub fn naive_prove<F: PrimeField>(poly: &MultilinearPoly<F>, claimed_sum: F) -> Proof<F> {
+    let mut round_polys: Vec<[F; 2]> = vec![];
+
+    let mut transcript = FiatShamir::<Keccak256, F>::new();
+
+    transcript.domain_separator(DOMAIN_SEPARATOR);
+    transcript.append_poly(b"poly", poly);
+    transcript.append_field(b"claimed_sum", &claimed_sum);
 
     let mut poly = poly.clone();
 
@@ -43,16 +336,10 @@ pub fn prove<F: PrimeField>(poly: &MultilinearPoly<F>, claimed_sum: F) -> Proof<
             poly.partial_evaluate((idx, F::one())).evals.iter().sum(),
         ];
 
-        transcript.absorb(
-            round_poly
-                .iter()
-                .flat_map(|f| f.into_bigint().to_bytes_le())
-                .collect::<Vec<_>>()
-                .as_slice(),
-        );
+        transcript.append_fields(b"round_poly", round_poly.as_slice());
         round_polys.push(round_poly);
 
-        let challenge = transcript.squeeze();
+        let challenge = transcript.challenge_scalar(b"challenge");
 
         poly = poly.partial_evaluate((idx, challenge))
     }
@@ -63,61 +350,43 @@ pub fn prove<F: PrimeField>(poly: &MultilinearPoly<F>, claimed_sum: F) -> Proof<
     }
 }
 
-pub fn partial_prove<F: PrimeField>(
+/// [`partial_prove`] by partially evaluating and reducing a copy of the whole sum at
+/// every point of every round. Kept as the reference the bookkeeping prover is tested
+/// and benchmarked against.
+pub fn naive_partial_prove<F: PrimeField, T: Transcript<F>>(
     poly: &SumPoly<F>,
     initial_claimed_sum: F,
-    transcript: &mut FiatShamir<Keccak256, F>,
+    transcript: &mut T,
 ) -> PartialProof<F> {
-    let n_vars = poly.n_vars();
-    let mut round_polys: Vec<UnivariatePoly<F>> = Vec::with_capacity(n_vars.try_into().unwrap());
-    let mut rand_challenges: Vec<F> = Vec::with_capacity(n_vars.try_into().unwrap());
+    let n_vars = poly.n_vars() as usize;
+    let degree = poly.max_degree().max(1);
+    let mut round_polys: Vec<Vec<F>> = Vec::with_capacity(n_vars);
+    let mut rand_challenges: Vec<F> = Vec::with_capacity(n_vars);
 
-    // transcript.absorb(&poly.to_bytes());
-    // transcript.absorb(&initial_claimed_sum.into_bigint().to_bytes_le());
+    transcript.domain_separator(DOMAIN_SEPARATOR);
+    transcript.append_field(b"claimed_sum", &initial_claimed_sum);
 
     let mut poly = poly.clone();
 
-    // dbg!(&n_vars);
-
     for _ in 0..n_vars {
         let idx: usize = (poly.n_vars() - 1).try_into().unwrap();
-        let mut claimed_sum = F::zero();
 
-        // implement reduce func. for polynomials functions sum_poly et product_poly
-        let round_poly: UnivariatePoly<F> = UnivariatePoly::interpolate(
-            vec![F::zero(), F::one(), F::from(2)],
-            vec![
-                poly.partial_evaluate((idx, F::zero()))
-                    .reduce()
-                    .iter()
-                    .sum(),
-                poly.partial_evaluate((idx, F::one())).reduce().iter().sum(),
-                poly.partial_evaluate((idx, F::from(2)))
+        let round_poly: Vec<F> = (0..=degree as u64)
+            .map(|x| {
+                poly.partial_evaluate((idx, F::from(x)))
                     .reduce()
                     .iter()
-                    .sum(),
-            ],
-        );
-
-        // dbg!(&round_poly);
+                    .sum()
+            })
+            .collect();
 
-        claimed_sum = round_poly.evaluate_sum_over_boolean_hypercube();
-
-        // committing the claimed_sum and round_poly to the transcript
-        transcript.absorb_n(&[
-            &claimed_sum.into_bigint().to_bytes_le(),
-            &round_poly.to_bytes(),
-        ]);
+        transcript.append_fields(b"round_poly", &round_poly);
 
         round_polys.push(round_poly);
 
-        let challenge = transcript.squeeze();
-
-        rand_challenges.push(challenge.clone());
+        let challenge = transcript.challenge_scalar(b"challenge");
 
-        // dbg!(&poly.n_vars());
-        // dbg!(&idx);
-        // dbg!(&challenge);
+        rand_challenges.push(challenge);
 
         poly = poly.partial_evaluate((idx, challenge))
     }
@@ -129,41 +398,40 @@ pub fn partial_prove<F: PrimeField>(
     }
 }
 
-pub fn verify<F: PrimeField>(proof: &Proof<F>, poly: &mut MultilinearPoly<F>) -> bool {
+pub fn verify<F: PrimeField>(proof: &Proof<F>, poly: &mut MultilinearPoly<F>) -> Result<(), Error> {
+    verify_with_transcript(proof, poly, &mut FiatShamir::<Keccak256, F>::new())
+}
+
+/// Checks a proof made by [`prove_with_transcript`] on a fresh transcript of the same
+/// kind.
+pub fn verify_with_transcript<F: PrimeField, T: Transcript<F>>(
+    proof: &Proof<F>,
+    poly: &mut MultilinearPoly<F>,
+    transcript: &mut T,
+) -> Result<(), Error> {
     if proof.round_polys.len() != poly.n_vars {
-        return false;
+        return Err(Error::InvalidProofLength {
+            expected: poly.n_vars,
+            got: proof.round_polys.len(),
+        });
     }
 
     let mut challenges = vec![];
 
-    let mut transcript = FiatShamir::<Keccak256, F>::new();
-
-    transcript.absorb(
-        poly.evals
-            .iter()
-            .flat_map(|f| f.into_bigint().to_bytes_le())
-            .collect::<Vec<_>>()
-            .as_slice(),
-    );
-
-    transcript.absorb(proof.claimed_sum.into_bigint().to_bytes_le().as_slice());
+    transcript.domain_separator(DOMAIN_SEPARATOR);
+    transcript.append_poly(b"poly", poly);
+    transcript.append_field(b"claimed_sum", &proof.claimed_sum);
 
     let mut claimed_sum = proof.claimed_sum;
 
-    for round_poly in &proof.round_polys {
+    for (round, round_poly) in proof.round_polys.iter().enumerate() {
         if claimed_sum != round_poly.iter().sum() {
-            return false;
+            return Err(Error::RoundSumMismatch { round });
         }
 
-        transcript.absorb(
-            round_poly
-                .iter()
-                .flat_map(|f| f.into_bigint().to_bytes_le())
-                .collect::<Vec<_>>()
-                .as_slice(),
-        );
+        transcript.append_fields(b"round_poly", round_poly.as_slice());
 
-        let challenge = transcript.squeeze();
+        let challenge = transcript.challenge_scalar(b"challenge");
 
         challenges.push(challenge);
 
@@ -171,57 +439,89 @@ pub fn verify<F: PrimeField>(proof: &Proof<F>, poly: &mut MultilinearPoly<F>) ->
     }
 
     if claimed_sum != poly.evaluate(challenges) {
-        return false;
+        return Err(Error::FinalEvalMismatch);
     }
 
-    true
+    Ok(())
 }
 
-pub fn partial_verify<F: PrimeField>(
+/// Checks every round of a proof made by [`partial_prove`] for a sum of degree
+/// `degree` (at least 1) and returns the challenges with the final claim, which the caller has to
+/// check against the polynomial itself.
+pub fn partial_verify<F: PrimeField, T: Transcript<F>>(
     proof: &PartialProof<F>,
-    transcript: &mut FiatShamir<Keccak256, F>,
-) -> (Vec<F>, F) {
+    degree: usize,
+    transcript: &mut T,
+) -> Result<(Vec<F>, F), Error> {
+    let degree = degree.max(1);
     let mut challenges = vec![];
     let mut claimed_sum = proof.initial_claimed_sum;
 
-    // dbg!(&proof.round_polys);
+    transcript.domain_separator(DOMAIN_SEPARATOR);
+    transcript.append_field(b"claimed_sum", &claimed_sum);
 
-    for round_poly in &proof.round_polys {
-        if round_poly.evaluate_sum_over_boolean_hypercube() != claimed_sum {
-            println!("Exiting from loop hereeeeee");
-            return (challenges, claimed_sum);
+    for (round, round_poly) in proof.round_polys.iter().enumerate() {
+        if round_poly.len() != degree + 1 {
+            return Err(Error::InvalidRoundPolyLength {
+                round,
+                expected: degree + 1,
+                got: round_poly.len(),
+            });
         }
 
-        transcript.absorb_n(&[
-            &claimed_sum.into_bigint().to_bytes_le(),
-            &round_poly.to_bytes(),
-        ]);
+        if round_poly[0] + round_poly[1] != claimed_sum {
+            return Err(Error::RoundSumMismatch { round });
+        }
+
+        transcript.append_fields(b"round_poly", round_poly);
 
-        let challenge = transcript.squeeze();
+        let challenge = transcript.challenge_scalar(b"challenge");
 
         challenges.push(challenge);
 
-        claimed_sum = round_poly.evaluate(challenge);
+        claimed_sum = evaluate_round_poly(round_poly, challenge);
     }
 
-    // if claimed_sum != poly.evaluate(challenges) {
-    //     return false;
-    // }
+    Ok((challenges, claimed_sum))
+}
+
+/// Evaluates the polynomial with values `evals` at `0, 1, ..., d` at `x` with the
+/// Lagrange basis over those points.
+pub fn evaluate_round_poly<F: PrimeField>(evals: &[F], x: F) -> F {
+    let points: Vec<F> = (0..evals.len() as u64).map(F::from).collect();
 
-    // true
-    (challenges, claimed_sum)
+    evals
+        .iter()
+        .zip(points.iter())
+        .map(|(eval, x_i)| {
+            let (numerator, denominator) = points
+                .iter()
+                .filter(|x_j| *x_j != x_i)
+                .fold((F::one(), F::one()), |(num, den), x_j| {
+                    (num * (x - x_j), den * (*x_i - x_j))
+                });
+
+            *eval * numerator * denominator.inverse().unwrap()
+        })
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::sumcheck_protocol::{partial_prove, partial_verify, prove, verify};
+    use crate::transcript::{
+        Blake3Transcript, KeccakTranscript, PoseidonTranscript, Sha256Transcript,
+    };
     use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
     use field_tracker::{print_summary, Ft};
     use polynomials::{
         composed::{product_poly::ProductPoly, sum_poly::SumPoly},
         multilinear::multilinear_poly::MultilinearPoly,
     };
+    use std::str::FromStr;
 
     // type Fr = Ft!(ark_bn254::Fr);
 
@@ -233,6 +533,35 @@ mod tests {
     pub fn test_sumcheck() {
         let mut poly = MultilinearPoly::new(to_field(vec![0, 0, 0, 3, 0, 0, 2, 5]), 3);
         let proof = prove(&poly, Fr::from(10));
+
+        assert_eq!(verify(&proof, &mut poly), Ok(()));
+    }
+
+    #[test]
+    pub fn test_sumcheck_errors() {
+        let mut poly = MultilinearPoly::new(to_field(vec![0, 0, 0, 3, 0, 0, 2, 5]), 3);
+
+        let wrong_sum = prove(&poly, Fr::from(11));
+        assert_eq!(
+            verify(&wrong_sum, &mut poly),
+            Err(Error::RoundSumMismatch { round: 0 })
+        );
+
+        let mut truncated = prove(&poly, Fr::from(10));
+        truncated.round_polys.pop();
+        assert_eq!(
+            verify(&truncated, &mut poly),
+            Err(Error::InvalidProofLength {
+                expected: 3,
+                got: 2
+            })
+        );
+
+        let mut tampered = prove(&poly, Fr::from(10));
+        let last = tampered.round_polys.last_mut().unwrap();
+        last[0] += Fr::from(1);
+        last[1] -= Fr::from(1);
+        assert_eq!(verify(&tampered, &mut poly), Err(Error::FinalEvalMismatch));
     }
 
     #[test]
@@ -257,8 +586,234 @@ mod tests {
 
         let sum_check_proof = partial_prove(&initial_polynomial, Fr::from(12), &mut transcript_p);
 
-        let res = partial_verify(&sum_check_proof, &mut transcript_v);
-        // assert!(.0);
+        let (challenges, _) = partial_verify(&sum_check_proof, 2, &mut transcript_v).unwrap();
+        assert_eq!(challenges, sum_check_proof.rand_challenges);
+
+        let mut wrong_claim = sum_check_proof.clone();
+        wrong_claim.initial_claimed_sum = Fr::from(13);
+        assert_eq!(
+            partial_verify(&wrong_claim, 2, &mut FiatShamir::<Keccak256, Fr>::new()),
+            Err(Error::RoundSumMismatch { round: 0 })
+        );
+    }
+
+    #[test]
+    pub fn test_sumcheck_on_every_transcript() {
+        fn check<T: Transcript<Fr>>(transcript: fn() -> T) {
+            let mut poly = MultilinearPoly::new(to_field(vec![0, 0, 0, 3, 0, 0, 2, 5]), 3);
+            let proof = prove_with_transcript(&poly, Fr::from(10), &mut transcript());
+            assert_eq!(
+                verify_with_transcript(&proof, &mut poly, &mut transcript()),
+                Ok(())
+            );
+
+            let mut rng = StdRng::seed_from_u64(0);
+            let sum_poly = random_product_sum(3, 4, &mut rng);
+            let sum = sum_poly.reduce().iter().sum();
+            let proof = partial_prove(&sum_poly, sum, &mut transcript());
+            let (challenges, _) = partial_verify(&proof, 3, &mut transcript()).unwrap();
+            assert_eq!(challenges, proof.rand_challenges);
+        }
+
+        check(KeccakTranscript::<Fr>::new);
+        check(Sha256Transcript::<Fr>::new);
+        check(Blake3Transcript::<Fr>::new);
+        check(PoseidonTranscript::<Fr>::new);
+    }
+
+    #[test]
+    pub fn test_proofs_do_not_verify_across_transcripts() {
+        let mut poly = MultilinearPoly::new(to_field(vec![0, 0, 0, 3, 0, 0, 2, 5]), 3);
+        let proof = prove_with_transcript(&poly, Fr::from(10), &mut PoseidonTranscript::new());
+
+        assert_eq!(
+            verify_with_transcript(&proof, &mut poly, &mut PoseidonTranscript::new()),
+            Ok(())
+        );
+        assert!(verify(&proof, &mut poly).is_err());
+    }
+
+    fn random_product_sum(degree: usize, n_vars: usize, rng: &mut StdRng) -> SumPoly<Fr> {
+        let mut random_poly =
+            || MultilinearPoly::new((0..1 << n_vars).map(|_| Fr::rand(rng)).collect(), n_vars);
+
+        // a full degree product next to a lower degree one
+        let high = ProductPoly::new((0..degree).map(|_| random_poly()).collect());
+        let low = ProductPoly::new((0..degree / 2).map(|_| random_poly()).collect());
+
+        SumPoly::new(vec![high, low])
+    }
+
+    #[test]
+    pub fn test_partial_sumcheck_high_degree() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for degree in 3..=8 {
+            let poly = random_product_sum(degree, 4, &mut rng);
+            let sum: Fr = poly.reduce().iter().sum();
+
+            let proof = partial_prove(&poly, sum, &mut FiatShamir::<Keccak256, Fr>::new());
+            assert!(proof
+                .round_polys
+                .iter()
+                .all(|round_poly| round_poly.len() == degree + 1));
+
+            let (challenges, final_claim) =
+                partial_verify(&proof, degree, &mut FiatShamir::<Keccak256, Fr>::new()).unwrap();
+            assert_eq!(challenges, proof.rand_challenges);
+            assert_eq!(final_claim, poly.evaluate(challenges));
+        }
+    }
+
+    #[test]
+    pub fn test_partial_sumcheck_rejects_wrong_degree() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let poly = random_product_sum(5, 3, &mut rng);
+        let sum: Fr = poly.reduce().iter().sum();
+
+        let proof = partial_prove(&poly, sum, &mut FiatShamir::<Keccak256, Fr>::new());
+
+        // the old fixed three point interpolation only fits degree 2
+        assert_eq!(
+            partial_verify(&proof, 2, &mut FiatShamir::<Keccak256, Fr>::new()),
+            Err(Error::InvalidRoundPolyLength {
+                round: 0,
+                expected: 3,
+                got: 6
+            })
+        );
+
+        let mut tampered = proof.clone();
+        tampered.round_polys[2][4] += Fr::from(1);
+        let (challenges, final_claim) =
+            partial_verify(&tampered, 5, &mut FiatShamir::<Keccak256, Fr>::new()).unwrap();
+        assert_ne!(final_claim, poly.evaluate(challenges));
+    }
+
+    #[test]
+    pub fn test_bookkeeping_prove_matches_naive() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let evals: Vec<Fr> = (0..1 << 6).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = MultilinearPoly::new(evals, 6);
+        let sum: Fr = poly.evals.iter().sum();
+
+        assert_eq!(prove(&poly, sum), naive_prove(&poly, sum));
+    }
+
+    #[test]
+    pub fn test_bookkeeping_partial_prove_matches_naive() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for degree in 2..=5 {
+            let poly = random_product_sum(degree, 4, &mut rng);
+            let sum: Fr = poly.reduce().iter().sum();
+
+            let mut transcript = FiatShamir::<Keccak256, Fr>::new();
+            let mut naive_transcript = FiatShamir::<Keccak256, Fr>::new();
+
+            assert_eq!(
+                partial_prove(&poly, sum, &mut transcript),
+                naive_partial_prove(&poly, sum, &mut naive_transcript)
+            );
+            assert_eq!(
+                transcript.challenge_scalar(b"next"),
+                naive_transcript.challenge_scalar(b"next")
+            );
+        }
+    }
+
+    // Pinned on the sequential build, so `--features parallel` has to produce the
+    // same transcript byte for byte.
+    #[test]
+    pub fn test_partial_prove_transcript_is_pinned() {
+        let n_vars = 12;
+        let poly = |offset: u64| {
+            MultilinearPoly::new(
+                (0..1u64 << n_vars)
+                    .map(|i| Fr::from(i * i + offset))
+                    .collect(),
+                n_vars,
+            )
+        };
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![poly(1), poly(2), poly(3)]),
+            ProductPoly::new(vec![poly(4), poly(5)]),
+        ]);
+        let sum: Fr = sum_poly.reduce().iter().sum();
+
+        let mut transcript = FiatShamir::<Keccak256, Fr>::new();
+        partial_prove(&sum_poly, sum, &mut transcript);
+
+        assert_eq!(
+            transcript.challenge_scalar(b"next"),
+            Fr::from_str(
+                "14536230309857782037960355768847033961696310123482093416786233956071290456871"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_proof_serialization_round_trip() {
+        let poly = MultilinearPoly::new(to_field(vec![0, 0, 0, 3, 0, 0, 2, 5]), 3);
+        let proof = prove(&poly, Fr::from(10));
+
+        let mut rng = StdRng::seed_from_u64(4);
+        let sum_poly = random_product_sum(3, 4, &mut rng);
+        let sum: Fr = sum_poly.reduce().iter().sum();
+        let partial_proof = partial_prove(&sum_poly, sum, &mut FiatShamir::<Keccak256, Fr>::new());
+
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            proof.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), proof.serialized_size(compress));
+            assert_eq!(
+                Proof::<Fr>::deserialize_with_mode(bytes.as_slice(), compress, Validate::Yes)
+                    .unwrap(),
+                proof
+            );
+
+            let mut bytes = Vec::new();
+            partial_proof
+                .serialize_with_mode(&mut bytes, compress)
+                .unwrap();
+            assert_eq!(bytes.len(), partial_proof.serialized_size(compress));
+            assert_eq!(
+                PartialProof::<Fr>::deserialize_with_mode(
+                    bytes.as_slice(),
+                    compress,
+                    Validate::Yes
+                )
+                .unwrap(),
+                partial_proof
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_proof_serialization_rejects_unknown_version() {
+        let poly = MultilinearPoly::new(to_field(vec![0, 0, 0, 3, 0, 0, 2, 5]), 3);
+        let mut bytes = Vec::new();
+        prove(&poly, Fr::from(10))
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+
+        bytes[0] = PROOF_VERSION + 1;
+        assert!(matches!(
+            Proof::<Fr>::deserialize_compressed(bytes.as_slice()),
+            Err(SerializationError::InvalidData)
+        ));
+    }
+
+    #[test]
+    pub fn test_evaluate_round_poly() {
+        // 2x^3 - x + 5 from its values at 0..=3
+        let f = |x: Fr| Fr::from(2) * x * x * x - x + Fr::from(5);
+        let evals: Vec<Fr> = (0..4u64).map(|x| f(Fr::from(x))).collect();
+
+        for x in [Fr::from(7), Fr::from(123456), -Fr::from(3)] {
+            assert_eq!(evaluate_round_poly(&evals, x), f(x));
+        }
     }
 
     pub fn get_2_20_poly() -> MultilinearPoly<Fr> {
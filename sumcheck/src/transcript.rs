@@ -0,0 +1,312 @@
+use crate::fiat_shamir::FiatShamir;
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use digest::{Digest, FixedOutputReset};
+use polynomials::multilinear::multilinear_poly::MultilinearPoly;
+use poseidon::{parameters::PoseidonParameters, sponge::PoseidonSponge};
+use sha2::Sha256;
+use sha3::Keccak256;
+use std::slice;
+
+/// The transcript every protocol in the repo ran on before the hash became pluggable.
+pub type KeccakTranscript<F> = FiatShamir<Keccak256, F>;
+pub type Sha256Transcript<F> = FiatShamir<Sha256, F>;
+pub type Blake3Transcript<F> = FiatShamir<blake3::Hasher, F>;
+
+/// Labelled Fiat-Shamir transcript.
+///
+/// Every message is absorbed together with a label naming what it is, and every
+/// challenge is drawn under a label of its own, so the prover and the verifier only
+/// agree on a challenge if they absorbed the same values, in the same order, for the
+/// same purpose. Protocols open with a [`Transcript::domain_separator`] so two
+/// protocols sharing a transcript never read each other's messages as their own.
+pub trait Transcript<F: PrimeField> {
+    /// Absorbs `message` under `label`. Both are length-prefixed, so no two different
+    /// sequences of appends hash the same bytes.
+    fn append_message(&mut self, label: &[u8], message: &[u8]);
+
+    /// Draws a challenge bound to `label` and everything absorbed so far.
+    fn challenge_scalar(&mut self, label: &[u8]) -> F;
+
+    /// Marks the start of `protocol` on the transcript.
+    fn domain_separator(&mut self, protocol: &[u8]) {
+        self.append_message(b"dom-sep", protocol);
+    }
+
+    fn append_field(&mut self, label: &[u8], value: &F) {
+        self.append_message(label, &value.into_bigint().to_bytes_le());
+    }
+
+    fn append_fields(&mut self, label: &[u8], values: &[F]) {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.into_bigint().to_bytes_le())
+            .collect();
+
+        self.append_message(label, &bytes);
+    }
+
+    /// Absorbs a curve point (or any other arkworks value) in compressed form.
+    fn append_point<G: CanonicalSerialize>(&mut self, label: &[u8], point: &G) {
+        let mut bytes = Vec::with_capacity(point.compressed_size());
+        point
+            .serialize_compressed(&mut bytes)
+            .expect("Serializing into a Vec cannot fail");
+
+        self.append_message(label, &bytes);
+    }
+
+    /// Absorbs a multilinear polynomial through its evaluations over the hypercube.
+    fn append_poly(&mut self, label: &[u8], poly: &MultilinearPoly<F>) {
+        self.append_fields(label, &poly.evals);
+    }
+
+    /// Draws `n` challenges under the same label, one after the other.
+    fn challenge_scalars(&mut self, label: &[u8], n: usize) -> Vec<F> {
+        (0..n).map(|_| self.challenge_scalar(label)).collect()
+    }
+}
+
+impl<T: Digest + Default + FixedOutputReset, F: PrimeField> Transcript<F> for FiatShamir<T, F> {
+    fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        Digest::update(&mut self.hasher, (label.len() as u64).to_le_bytes());
+        Digest::update(&mut self.hasher, label);
+        Digest::update(&mut self.hasher, (message.len() as u64).to_le_bytes());
+        Digest::update(&mut self.hasher, message);
+    }
+
+    fn challenge_scalar(&mut self, label: &[u8]) -> F {
+        self.append_message(b"challenge", label);
+
+        self.squeeze()
+    }
+}
+
+/// Tags absorbed ahead of every append, so bytes are never read as field elements.
+const BYTES_TAG: u64 = 0;
+const FIELDS_TAG: u64 = 1;
+
+/// Transcript on a Poseidon sponge over the proof's own field.
+///
+/// Field elements are absorbed as they are, which is what makes the transcript cheap
+/// to replay inside a circuit over the same field. Labels and byte messages go
+/// through [`PoseidonSponge::absorb_bytes`], length-prefixed and packed 31 bytes to an
+/// element on BN254.
+#[derive(Clone, Debug)]
+pub struct PoseidonTranscript<F: PrimeField> {
+    sponge: PoseidonSponge<F>,
+}
+
+impl<F: PrimeField> PoseidonTranscript<F> {
+    /// Transcript on circomlib's width 3 instance, the one circomlib and Noir hash two
+    /// elements with.
+    pub fn new() -> Self {
+        Self::with_parameters(PoseidonParameters::circom(3))
+    }
+
+    pub fn with_parameters(parameters: PoseidonParameters<F>) -> Self {
+        Self {
+            sponge: PoseidonSponge::new(parameters),
+        }
+    }
+}
+
+impl<F: PrimeField> Default for PoseidonTranscript<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> Transcript<F> for PoseidonTranscript<F> {
+    fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.sponge.absorb(&[F::from(BYTES_TAG)]);
+        self.sponge.absorb_bytes(label);
+        self.sponge.absorb_bytes(message);
+    }
+
+    fn challenge_scalar(&mut self, label: &[u8]) -> F {
+        self.append_message(b"challenge", label);
+
+        self.sponge.squeeze()
+    }
+
+    fn append_field(&mut self, label: &[u8], value: &F) {
+        self.append_fields(label, slice::from_ref(value));
+    }
+
+    fn append_fields(&mut self, label: &[u8], values: &[F]) {
+        self.sponge.absorb(&[F::from(FIELDS_TAG)]);
+        self.sponge.absorb_bytes(label);
+        self.sponge.absorb(&[F::from(values.len() as u64)]);
+        self.sponge.absorb(values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use std::str::FromStr;
+
+    /// Runs a check generic over the transcript on every backend.
+    macro_rules! for_each_backend {
+        ($check:ident) => {
+            $check(KeccakTranscript::<Fr>::new);
+            $check(Sha256Transcript::<Fr>::new);
+            $check(Blake3Transcript::<Fr>::new);
+            $check(PoseidonTranscript::<Fr>::new);
+        };
+    }
+
+    fn same_messages_yield_same_challenges<T: Transcript<Fr>>(transcript: fn() -> T) {
+        let mut prover = transcript();
+        let mut verifier = transcript();
+
+        for t in [&mut prover, &mut verifier] {
+            t.domain_separator(b"test");
+            t.append_field(b"claim", &Fr::from(7));
+            t.append_point(b"commitment", &G1Projective::generator());
+        }
+
+        assert_eq!(
+            prover.challenge_scalars(b"r", 3),
+            verifier.challenge_scalars(b"r", 3)
+        );
+    }
+
+    fn differing_labels_yield_differing_challenges<T: Transcript<Fr>>(transcript: fn() -> T) {
+        let mut a = transcript();
+        let mut b = transcript();
+        a.append_field(b"claimed_sum", &Fr::from(7));
+        b.append_field(b"evaluation", &Fr::from(7));
+        assert_ne!(a.challenge_scalar(b"r"), b.challenge_scalar(b"r"));
+
+        let mut a = transcript();
+        let mut b = transcript();
+        a.append_field(b"claim", &Fr::from(7));
+        b.append_field(b"claim", &Fr::from(7));
+        assert_ne!(a.challenge_scalar(b"alpha"), b.challenge_scalar(b"beta"));
+    }
+
+    fn differing_domains_yield_differing_challenges<T: Transcript<Fr>>(transcript: fn() -> T) {
+        let mut a = transcript();
+        let mut b = transcript();
+        a.domain_separator(b"sumcheck");
+        b.domain_separator(b"gkr");
+
+        assert_ne!(a.challenge_scalar(b"r"), b.challenge_scalar(b"r"));
+    }
+
+    fn messages_are_framed<T: Transcript<Fr>>(transcript: fn() -> T) {
+        // the same bytes split differently between label and message
+        let mut a = transcript();
+        let mut b = transcript();
+        a.append_message(b"ab", b"c");
+        b.append_message(b"a", b"bc");
+        assert_ne!(a.challenge_scalar(b"r"), b.challenge_scalar(b"r"));
+
+        // and between two messages
+        let mut a = transcript();
+        let mut b = transcript();
+        a.append_fields(b"evals", &[Fr::from(1), Fr::from(2)]);
+        b.append_fields(b"evals", &[Fr::from(1)]);
+        b.append_fields(b"evals", &[Fr::from(2)]);
+        assert_ne!(a.challenge_scalar(b"r"), b.challenge_scalar(b"r"));
+    }
+
+    #[test]
+    fn test_same_messages_yield_same_challenges() {
+        for_each_backend!(same_messages_yield_same_challenges);
+    }
+
+    #[test]
+    fn test_differing_labels_yield_differing_challenges() {
+        for_each_backend!(differing_labels_yield_differing_challenges);
+    }
+
+    #[test]
+    fn test_differing_domains_yield_differing_challenges() {
+        for_each_backend!(differing_domains_yield_differing_challenges);
+    }
+
+    #[test]
+    fn test_messages_are_framed() {
+        for_each_backend!(messages_are_framed);
+    }
+
+    #[test]
+    fn test_backends_yield_differing_challenges() {
+        fn challenge<T: Transcript<Fr>>(mut transcript: T) -> Fr {
+            transcript.domain_separator(b"test");
+            transcript.append_field(b"x", &Fr::from(7));
+            transcript.challenge_scalar(b"r")
+        }
+
+        let challenges = [
+            challenge(KeccakTranscript::new()),
+            challenge(Sha256Transcript::new()),
+            challenge(Blake3Transcript::new()),
+            challenge(PoseidonTranscript::new()),
+        ];
+
+        for (i, a) in challenges.iter().enumerate() {
+            assert!(challenges[i + 1..].iter().all(|b| a != b));
+        }
+    }
+
+    #[test]
+    fn test_poseidon_never_reads_bytes_as_fields() {
+        let value = Fr::from(7);
+
+        let mut a = PoseidonTranscript::new();
+        let mut b = PoseidonTranscript::new();
+        a.append_field(b"x", &value);
+        b.append_message(b"x", &value.into_bigint().to_bytes_le());
+        assert_ne!(a.challenge_scalar(b"r"), b.challenge_scalar(b"r"));
+
+        // a message filling exactly one element and one spilling into a second
+        let mut a = PoseidonTranscript::<Fr>::new();
+        let mut b = PoseidonTranscript::<Fr>::new();
+        a.append_message(b"m", &[1; 31]);
+        b.append_message(b"m", &[1; 32]);
+        assert_ne!(a.challenge_scalar(b"r"), b.challenge_scalar(b"r"));
+    }
+
+    // Computed with Python's hashlib from the framing in `append_message`, each
+    // message as `len(label) || label || len(message) || message` with u64 LE lengths
+    // and the challenge label absorbed under `challenge`, then squeezed as in
+    // `FiatShamir::squeeze`.
+    #[test]
+    fn test_challenge_known_answer() {
+        let mut transcript = Sha256Transcript::<Fr>::new();
+        transcript.domain_separator(b"test");
+        transcript.append_field(b"x", &Fr::from(7));
+
+        assert_eq!(
+            transcript.challenge_scalar(b"r"),
+            Fr::from_str(
+                "14081626463382542355072964620661721314744350085858105998983030800218448861871"
+            )
+            .unwrap()
+        );
+    }
+
+    // Computed with a Python model of the sponge over the circomlib constants, absorbing
+    // `0, 7, "dom-sep", 4, "test"`, then `1, 1, "x", 1, 7`, then
+    // `0, 9, "challenge", 1, "r"`, strings packed little-endian into one element each.
+    #[test]
+    fn test_poseidon_challenge_known_answer() {
+        let mut transcript = PoseidonTranscript::<Fr>::new();
+        transcript.domain_separator(b"test");
+        transcript.append_field(b"x", &Fr::from(7));
+
+        assert_eq!(
+            transcript.challenge_scalar(b"r"),
+            Fr::from_str(
+                "2426087182870699181670771889041785211467990660482765563517225673317395129963"
+            )
+            .unwrap()
+        );
+    }
+}